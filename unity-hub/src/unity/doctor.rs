@@ -0,0 +1,298 @@
+//! Integrity checks for Unity installations.
+//!
+//! [`diagnose`] cross-checks an installation against the release manifest it was installed
+//! from (`modules.json`) and the files on disk. [`diagnose_hub_editor`] does the same for an
+//! entry of the Unity Hub `editors.json`. Issues which only affect the bookkeeping in
+//! `modules.json` can be repaired with [`fix`].
+use crate::unity::hub::editors::EditorInstallation;
+use crate::unity::hub::module::Module;
+use crate::unity::installation::Installation;
+use crate::unity::error::UnityError;
+use log::debug;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use unity_version::Version;
+
+/// A single inconsistency found in an installation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// The editor executable doesn't exist.
+    MissingExecutable(PathBuf),
+    /// `modules.json` is missing or can't be parsed.
+    UnreadableModules(String),
+    /// A module is flagged as installed but its destination is missing or empty.
+    MissingModule { id: String, destination: PathBuf },
+    /// The version read from the installation differs from the expected version.
+    VersionMismatch { expected: Version, found: Version },
+    /// The version of the installation can't be read.
+    UnknownVersion(String),
+    /// A Unity Hub `editors.json` entry doesn't point to an installation of its version.
+    StaleHubEntry { version: Version, location: PathBuf },
+}
+
+impl Issue {
+    /// Returns `true` if [`fix`] can repair the issue.
+    pub fn is_fixable(&self) -> bool {
+        matches!(self, Issue::MissingModule { .. })
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::MissingExecutable(path) => {
+                write!(f, "editor executable {} is missing", path.display())
+            }
+            Issue::UnreadableModules(reason) => write!(f, "modules.json is unreadable: {}", reason),
+            Issue::MissingModule { id, destination } => write!(
+                f,
+                "module {} is marked as installed but {} is missing or empty",
+                id,
+                destination.display()
+            ),
+            Issue::VersionMismatch { expected, found } => {
+                write!(f, "expected Unity {} but found Unity {}", expected, found)
+            }
+            Issue::UnknownVersion(reason) => write!(f, "unable to read the editor version: {}", reason),
+            Issue::StaleHubEntry { version, location } => write!(
+                f,
+                "Unity Hub lists {} at {} but no installation of that version exists there",
+                version,
+                location.display()
+            ),
+        }
+    }
+}
+
+/// The result of checking one installation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    pub version: Version,
+    pub path: PathBuf,
+    pub issues: Vec<Issue>,
+}
+
+impl Diagnosis {
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn fixable_issues(&self) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(|issue| issue.is_fixable())
+    }
+}
+
+/// Checks the editor executable, the version and every module flagged as installed.
+pub fn diagnose<I: Installation>(installation: &I) -> Diagnosis {
+    let path = installation.path().to_path_buf();
+    let mut issues = Vec::new();
+
+    let exec_path = installation.exec_path();
+    if !exec_path.exists() {
+        issues.push(Issue::MissingExecutable(exec_path));
+    }
+
+    match Version::from_path(&path) {
+        Ok(found) if &found != installation.version() => issues.push(Issue::VersionMismatch {
+            expected: installation.version().to_owned(),
+            found,
+        }),
+        Ok(_) => (),
+        Err(e) => issues.push(Issue::UnknownVersion(e.to_string())),
+    }
+
+    match installation.get_modules() {
+        Ok(modules) => issues.extend(
+            modules
+                .iter()
+                .filter(|module| module.is_installed)
+                .filter_map(|module| missing_module(module, &path)),
+        ),
+        Err(e) => issues.push(Issue::UnreadableModules(unity_error_reason(&e))),
+    }
+
+    Diagnosis {
+        version: installation.version().to_owned(),
+        path,
+        issues,
+    }
+}
+
+/// Checks that a Unity Hub `editors.json` entry points to an installation of its version.
+pub fn diagnose_hub_editor(editor: &EditorInstallation) -> Option<Issue> {
+    match Version::from_path(editor.location()) {
+        Ok(version) if &version == editor.version() => None,
+        _ => Some(Issue::StaleHubEntry {
+            version: editor.version().to_owned(),
+            location: editor.location().to_path_buf(),
+        }),
+    }
+}
+
+/// Repairs the fixable issues of `diagnosis` and returns how many were fixed.
+///
+/// Modules whose files are missing are flagged as not installed in `modules.json`, so they
+/// can be installed again.
+#[cfg(feature = "mutate")]
+pub fn fix<I: Installation>(installation: &I, diagnosis: &Diagnosis) -> Result<usize, UnityError> {
+    let missing: Vec<&str> = diagnosis
+        .issues
+        .iter()
+        .filter_map(|issue| match issue {
+            Issue::MissingModule { id, .. } => Some(id.as_str()),
+            _ => None,
+        })
+        .collect();
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    let mut modules = installation.get_modules()?;
+    let mut fixed = 0;
+    for module in modules.iter_mut() {
+        if module.is_installed && missing.contains(&module.id()) {
+            debug!("mark module {} as not installed", module.id());
+            module.is_installed = false;
+            fixed += 1;
+        }
+    }
+    installation.write_modules(modules)?;
+    Ok(fixed)
+}
+
+fn missing_module(module: &Module, base: &Path) -> Option<Issue> {
+    let destination = module.install_path(base)?;
+    let is_present = fs::read_dir(&destination)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if is_present {
+        None
+    } else {
+        debug!("module {} missing at {}", module.id(), destination.display());
+        Some(Issue::MissingModule {
+            id: module.id().to_string(),
+            destination,
+        })
+    }
+}
+
+fn unity_error_reason(error: &UnityError) -> String {
+    match error {
+        UnityError::ModulesJsonNotFound { source } => source.to_string(),
+        UnityError::ModulesJsonParseError(source) => source.to_string(),
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::unity::UnityInstallation;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    fn module_json(id: &str, destination: &str, is_installed: bool) -> String {
+        format!(
+            r#"{{
+                "id": "{id}",
+                "name": "{id}",
+                "description": "",
+                "category": "Platforms",
+                "downloadSize": 1000,
+                "installedSize": 2000,
+                "url": "https://example.com/{id}.tar.xz",
+                "destination": "{destination}",
+                "isInstalled": {is_installed}
+            }}"#
+        )
+    }
+
+    fn create_installation(modules: &[String]) -> (TempDir, UnityInstallation) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("2022.3.10f1");
+        fs::create_dir_all(path.join("Editor")).unwrap();
+        File::create(path.join("Editor/Unity")).unwrap();
+        fs::write(path.join("modules.json"), format!("[{}]", modules.join(","))).unwrap();
+        let installation = UnityInstallation::new(&path).unwrap();
+        (temp_dir, installation)
+    }
+
+    #[test]
+    fn diagnose_healthy_installation() {
+        let (_t, installation) = create_installation(&[
+            module_json("webgl", "{UNITY_PATH}/Editor/Data/PlaybackEngines/WebGLSupport", true),
+            module_json("android", "{UNITY_PATH}/Editor/Data/PlaybackEngines/AndroidPlayer", false),
+        ]);
+        let webgl = installation.path().join("Editor/Data/PlaybackEngines/WebGLSupport");
+        fs::create_dir_all(&webgl).unwrap();
+        File::create(webgl.join("modules.asset")).unwrap();
+
+        let diagnosis = diagnose(&installation);
+        assert!(diagnosis.is_healthy(), "{:?}", diagnosis.issues);
+    }
+
+    #[test]
+    fn diagnose_reports_missing_and_empty_module_destinations() {
+        let (_t, installation) = create_installation(&[
+            module_json("webgl", "{UNITY_PATH}/Editor/Data/PlaybackEngines/WebGLSupport", true),
+            module_json("ios", "{UNITY_PATH}/Editor/Data/PlaybackEngines", true),
+        ]);
+        fs::create_dir_all(installation.path().join("Editor/Data/PlaybackEngines/iOSSupport"))
+            .unwrap();
+
+        let diagnosis = diagnose(&installation);
+        let ids: Vec<&str> = diagnosis
+            .fixable_issues()
+            .filter_map(|issue| match issue {
+                Issue::MissingModule { id, .. } => Some(id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ids, vec!["webgl", "ios"]);
+    }
+
+    #[test]
+    fn diagnose_reports_unreadable_modules_json() {
+        let (_t, installation) = create_installation(&[]);
+        fs::write(installation.path().join("modules.json"), "{").unwrap();
+
+        let diagnosis = diagnose(&installation);
+        assert!(matches!(diagnosis.issues.as_slice(), [Issue::UnreadableModules(_)]));
+        assert_eq!(diagnosis.fixable_issues().count(), 0);
+    }
+
+    #[test]
+    fn diagnose_reports_stale_hub_entry() {
+        let (_t, installation) = create_installation(&[]);
+        let version: Version = "2021.3.1f1".parse().unwrap();
+        let editor = EditorInstallation::new(version.clone(), installation.path().to_path_buf());
+
+        assert_eq!(
+            diagnose_hub_editor(&editor),
+            Some(Issue::StaleHubEntry {
+                version,
+                location: installation.path().to_path_buf()
+            })
+        );
+        let editor = EditorInstallation::new(
+            installation.version().to_owned(),
+            installation.path().to_path_buf(),
+        );
+        assert_eq!(diagnose_hub_editor(&editor), None);
+    }
+
+    #[test]
+    #[cfg(feature = "mutate")]
+    fn fix_marks_missing_modules_as_not_installed() {
+        let (_t, installation) = create_installation(&[
+            module_json("webgl", "{UNITY_PATH}/Editor/Data/PlaybackEngines/WebGLSupport", true),
+        ]);
+
+        let diagnosis = diagnose(&installation);
+        assert_eq!(fix(&installation, &diagnosis).unwrap(), 1);
+
+        let modules = installation.get_modules().unwrap();
+        assert!(!modules[0].is_installed);
+        assert!(diagnose(&installation).is_healthy());
+    }
+}
@@ -0,0 +1,128 @@
+//! Access to the default editor of Unity Hub stored in `defaultEditor.json`.
+//!
+//! The file contains the version of the default editor as a JSON string, e.g.
+//! `"2022.3.10f1"`.
+use super::paths;
+use crate::error::UnityHubError;
+use log::debug;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use unity_version::Version;
+
+type Result<T> = std::result::Result<T, UnityHubError>;
+
+const CONFIG_NAME: &str = "defaultEditor.json";
+
+/// Returns the version of the default editor, or `None` if no default editor is set.
+pub fn default_editor() -> Result<Option<Version>> {
+    let path = paths::default_editor_config_path().ok_or(UnityHubError::ConfigDirectoryNotFound)?;
+    read_default_editor(&path)
+}
+
+/// Makes the editor with `version` the default editor of Unity Hub.
+///
+/// The version is not checked against the installed editors.
+#[cfg(feature = "mutate")]
+pub fn set_default_editor(version: &Version) -> Result<()> {
+    let path = paths::default_editor_config_path().ok_or(UnityHubError::ConfigDirectoryNotFound)?;
+    write_default_editor(&path, version)
+}
+
+fn read_default_editor(path: &Path) -> Result<Option<Version>> {
+    if !path.exists() {
+        debug!("{} doesn't exist, no default editor set", path.display());
+        return Ok(None);
+    }
+
+    let read_error = |source: anyhow::Error| UnityHubError::ReadConfigError {
+        config: CONFIG_NAME.to_string(),
+        source,
+    };
+    let content = fs::read_to_string(path).map_err(|e| read_error(e.into()))?;
+    let content = content.trim();
+    if content.is_empty() {
+        return Ok(None);
+    }
+
+    // Older Hub versions wrote the plain version without JSON quotes.
+    let version = match serde_json::from_str::<Option<String>>(content) {
+        Ok(version) => version,
+        Err(_) => Some(content.to_string()),
+    };
+    version
+        .filter(|version| !version.is_empty())
+        .map(|version| Version::from_str(&version).map_err(|e| read_error(e.into())))
+        .transpose()
+}
+
+#[cfg(feature = "mutate")]
+fn write_default_editor(path: &Path, version: &Version) -> Result<()> {
+    if let Some(config_dir) = path.parent() {
+        fs::create_dir_all(config_dir)
+            .map_err(|source| UnityHubError::FailedToCreateConfigDirectory { source })?;
+    }
+    let content = serde_json::to_string(&version.to_string()).map_err(|source| {
+        UnityHubError::WriteConfigError {
+            config: CONFIG_NAME.to_string(),
+            source: source.into(),
+        }
+    })?;
+    fs::write(path, content).map_err(|source| UnityHubError::WriteConfigError {
+        config: CONFIG_NAME.to_string(),
+        source: source.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn read_missing_config_returns_none() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_NAME);
+        assert_eq!(read_default_editor(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn read_json_and_plain_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_NAME);
+        let expected = Version::from_str("2022.3.10f1").unwrap();
+
+        for content in ["\"2022.3.10f1\"", "2022.3.10f1\n"] {
+            fs::write(&path, content).unwrap();
+            assert_eq!(read_default_editor(&path).unwrap(), Some(expected.clone()));
+        }
+
+        for content in ["", "null", "\"\""] {
+            fs::write(&path, content).unwrap();
+            assert_eq!(read_default_editor(&path).unwrap(), None, "{}", content);
+        }
+    }
+
+    #[test]
+    fn read_invalid_version_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_NAME);
+        fs::write(&path, "\"latest\"").unwrap();
+        assert!(matches!(
+            read_default_editor(&path),
+            Err(UnityHubError::ReadConfigError { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "mutate")]
+    fn write_creates_config_readable_by_hub() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("UnityHub").join(CONFIG_NAME);
+        let version = Version::from_str("2021.3.16f1").unwrap();
+
+        write_default_editor(&path, &version).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\"2021.3.16f1\"");
+        assert_eq!(read_default_editor(&path).unwrap(), Some(version));
+    }
+}
@@ -20,6 +20,18 @@ pub struct Editors {
 
 impl Editors {
     pub fn load() -> Result<Editors> {
+        Ok(Editors::create(Self::read_config()?))
+    }
+
+    /// Loads all entries of `editors.json`, including the ones [`Editors::create`] would filter
+    /// out because their location holds no installation of the listed version.
+    pub fn load_entries() -> Result<Vec<EditorInstallation>> {
+        let mut entries: Vec<EditorInstallation> = Self::read_config()?.into_values().collect();
+        entries.sort();
+        Ok(entries)
+    }
+
+    fn read_config() -> Result<HashMap<Version, EditorInstallation>> {
         let path = paths::editors_config_path()
             .ok_or_else(|| (UnityHubError::ConfigDirectoryNotFound))?;
 
@@ -39,7 +51,7 @@ impl Editors {
             HashMap::new()
         };
         trace!("loaded editors map: {:?}", map);
-        Ok(Editors::create(map))
+        Ok(map)
     }
 
     pub fn create(mut map: HashMap<Version, EditorInstallation>) -> Editors {
@@ -1,3 +1,4 @@
+pub mod default_editor;
 pub mod editors;
 pub mod paths;
 pub mod module;
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +16,25 @@ impl Module {
     pub fn id(&self) -> &str {
         &self.base.id()
     }
+
+    /// Returns the directory the module is installed to inside the installation at `base`.
+    ///
+    /// The `{UNITY_PATH}` placeholder is replaced with `base`; destinations without the
+    /// placeholder are relative to `base`. Modules without a destination return `None`.
+    pub fn install_path(&self, base: &Path) -> Option<PathBuf> {
+        let destination = self.base.destination()?;
+        if destination.is_empty() {
+            return None;
+        }
+
+        if destination.contains("{UNITY_PATH}") {
+            Some(PathBuf::from(
+                destination.replace("{UNITY_PATH}", base.to_string_lossy().as_ref()),
+            ))
+        } else {
+            Some(base.join(destination))
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
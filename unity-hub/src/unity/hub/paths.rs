@@ -47,6 +47,10 @@ pub fn cache_dir() -> Option<PathBuf> {
     dirs_2::cache_dir().map(|path| path.join("com.github.larusso.unity-version-manager"))
 }
 
+pub fn uvm_config_dir() -> Option<PathBuf> {
+    dirs_2::config_dir().map(|path| path.join("com.github.larusso.unity-version-manager"))
+}
+
 pub fn locks_dir() -> Option<PathBuf> {
     cache_dir().map(|path| path.join("locks"))
 }
@@ -81,5 +85,6 @@ mod tests {
         );
         println!("cache_dir:                           {:?}", cache_dir());
         println!("locks:                               {:?}", locks_dir());
+        println!("uvm_config_dir:                      {:?}", uvm_config_dir());
     }
 }
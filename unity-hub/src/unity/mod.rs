@@ -1,6 +1,7 @@
 pub mod hub;
 mod installation;
 pub mod error;
+pub mod doctor;
 
 use crate::error::{UnityError, UnityHubError};
 pub use installation::FromInstallation;
@@ -163,6 +164,15 @@ pub fn find_installation(version: &Version) -> Result<UnityInstallation, UnityHu
     })
 }
 
+/// Returns the installation of the Unity Hub default editor, or `None` if no default is set.
+///
+/// Fails with [`UnityHubError::InstallationNotFound`] if the default editor isn't installed.
+pub fn find_default_installation() -> Result<Option<UnityInstallation>, UnityHubError> {
+    hub::default_editor::default_editor()?
+        .map(|version| find_installation(&version))
+        .transpose()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,6 +6,9 @@ pub enum VersionError {
     #[error("Failed to parse unity version string: {0}")]
     ParsingFailed(String),
 
+    #[error("Failed to parse version requirement '{requirement}': {reason}")]
+    RequirementParsingFailed { requirement: String, reason: String },
+
     #[error("Provided Path does not exist: {0}")]
     PathContainsNoVersion(String),
 
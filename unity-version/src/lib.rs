@@ -6,5 +6,6 @@ pub use version::Version;
 pub use version::RevisionHash;
 pub use version::CompleteVersion;
 pub use version::ReleaseType;
+pub use version::VersionReq;
 
 
@@ -3,28 +3,45 @@ use derive_more::Display;
 use nom::{
     branch::alt,
     character::complete::{char, digit1, hex_digit1, space1},
-    combinator::{map_res, verify},
+    combinator::{map_res, opt, verify},
     error::context,
     sequence::delimited,
     IResult, Parser,
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::path::{Path, PathBuf};
-use std::{cmp::Ordering, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 use regex::Regex;
 
 mod release_type;
 mod revision_hash;
+mod version_req;
 use crate::error::VersionError;
 pub use release_type::ReleaseType;
 pub use revision_hash::RevisionHash;
-
-#[derive(Eq, Debug, Clone, Hash, PartialOrd, Display)]
-#[display("{}{}{}", base, release_type, revision)]
+pub use version_req::VersionReq;
+
+/// A Unity editor version like `2022.3.10f1`.
+///
+/// Supported shapes are `major.minor.patch<release type><revision>` with the release types
+/// `a`, `b`, `p`, `f`, `x` (experimental) and `c` (China), e.g. `6000.0.23f1` or `2023.1.0x1`,
+/// optionally followed by the revision of a China build, e.g. `2022.3.10f1c1`.
+#[derive(Eq, Debug, Clone, Hash, PartialOrd)]
 pub struct Version {
     base: semver::Version,
     release_type: ReleaseType,
     revision: u64,
+    china_revision: Option<u64>,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.base, self.release_type, self.revision)?;
+        if let Some(china_revision) = self.china_revision {
+            write!(f, "{}{}", ReleaseType::China, china_revision)?;
+        }
+        Ok(())
+    }
 }
 
 impl Serialize for Version {
@@ -53,6 +70,7 @@ impl Ord for Version {
             .cmp(&other.base)
             .then(self.release_type.cmp(&other.release_type))
             .then(self.revision.cmp(&other.revision))
+            .then(self.china_revision.cmp(&other.china_revision))
     }
 }
 
@@ -61,6 +79,7 @@ impl PartialEq for Version {
         self.base == other.base
             && self.release_type == other.release_type
             && self.revision == other.revision
+            && self.china_revision == other.china_revision
     }
 }
 
@@ -140,9 +159,16 @@ impl Version {
             base,
             release_type,
             revision,
+            china_revision: None,
         }
     }
 
+    /// Returns the version with the revision of a China build, e.g. `2022.3.10f1c1`.
+    pub fn with_china_revision(mut self, china_revision: u64) -> Version {
+        self.china_revision = Some(china_revision);
+        self
+    }
+
     pub fn release_type(&self) -> ReleaseType {
         self.release_type
     }
@@ -163,6 +189,16 @@ impl Version {
         self.revision
     }
 
+    /// The revision of a China build, e.g. `1` for `2022.3.10f1c1`.
+    pub fn china_revision(&self) -> Option<u64> {
+        self.china_revision
+    }
+
+    /// Returns `true` for versions released in the China stream.
+    pub fn is_china(&self) -> bool {
+        self.release_type == ReleaseType::China || self.china_revision.is_some()
+    }
+
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, VersionError> {
         version_impl::read_version_from_path(path)
     }
@@ -181,19 +217,16 @@ impl Version {
         // Enhanced regex to capture versions with optional hash suffixes
         static VERSION_REGEX: OnceLock<Regex> = OnceLock::new();
         let regex = VERSION_REGEX.get_or_init(|| {
-            Regex::new(r"([0-9]{1,4})\.([0-9]{1,4})\.([0-9]{1,4})(f|p|b|a)([0-9]{1,4})(_([a-z0-9]{12})| \(([a-z0-9]{12})\)|/([a-z0-9]{12}))?").unwrap()
+            Regex::new(r"([0-9]{1,4}\.[0-9]{1,4}\.[0-9]{1,4}[fpbacx][0-9]{1,4}(?:c[0-9]{1,4})?)(_([a-z0-9]{12})| \(([a-z0-9]{12})\)|/([a-z0-9]{12}))?").unwrap()
         });
         
         // Priority 1: Look for versions with parentheses hash format (most authoritative)
         for captures in regex.captures_iter(text) {
-            if captures.get(8).is_some() {
+            if captures.get(4).is_some() {
                 // This version has a hash in parentheses format: "version (hash)"
-                let version_string = format!(
-                    "{}.{}.{}{}{}",
-                    &captures[1], &captures[2], &captures[3], &captures[4], &captures[5]
-                );
+                let version_string = &captures[1];
                 
-                if let Ok(version) = Version::from_str(&version_string) {
+                if let Ok(version) = Version::from_str(version_string) {
                     return Some(version);
                 }
             }
@@ -201,14 +234,11 @@ impl Version {
         
         // Priority 2: Look for versions with underscore hash format
         for captures in regex.captures_iter(text) {
-            if captures.get(7).is_some() {
+            if captures.get(3).is_some() {
                 // This version has a hash in underscore format
-                let version_string = format!(
-                    "{}.{}.{}{}{}",
-                    &captures[1], &captures[2], &captures[3], &captures[4], &captures[5]
-                );
+                let version_string = &captures[1];
                 
-                if let Ok(version) = Version::from_str(&version_string) {
+                if let Ok(version) = Version::from_str(version_string) {
                     return Some(version);
                 }
             }
@@ -216,14 +246,11 @@ impl Version {
         
         // Priority 3: Look for versions with slash hash format
         for captures in regex.captures_iter(text) {
-            if captures.get(9).is_some() {
+            if captures.get(5).is_some() {
                 // This version has a hash in slash format
-                let version_string = format!(
-                    "{}.{}.{}{}{}",
-                    &captures[1], &captures[2], &captures[3], &captures[4], &captures[5]
-                );
+                let version_string = &captures[1];
                 
-                if let Ok(version) = Version::from_str(&version_string) {
+                if let Ok(version) = Version::from_str(version_string) {
                     return Some(version);
                 }
             }
@@ -231,12 +258,9 @@ impl Version {
         
         // Priority 4: Fallback to any version string found (without hash requirement)
         for captures in regex.captures_iter(text) {
-            let version_string = format!(
-                "{}.{}.{}{}{}",
-                &captures[1], &captures[2], &captures[3], &captures[4], &captures[5]
-            );
+            let version_string = &captures[1];
             
-            if let Ok(version) = Version::from_str(&version_string) {
+            if let Ok(version) = Version::from_str(version_string) {
                 return Some(version);
             }
         }
@@ -374,7 +398,7 @@ impl PartialOrd for CompleteVersion {
 fn parse_release_type(input: &str) -> IResult<&str, ReleaseType> {
     context(
         "release type",
-        map_res(alt((char('f'), char('b'), char('a'), char('p'))), |c| {
+        map_res(alt((char('f'), char('b'), char('a'), char('p'), char('x'), char('c'))), |c| {
             ReleaseType::try_from(c)
         }),
     ).parse(input)
@@ -391,19 +415,30 @@ fn parse_version(input: &str) -> IResult<&str, Version> {
             context("patch version", map_res(digit1, |s: &str| s.parse::<u64>())),
             parse_release_type,
             context("revision", map_res(digit1, |s: &str| s.parse::<u64>())),
+            opt(parse_china_revision),
         )
     )
-    .map(|(major, _, minor, _, patch, release_type, revision)| {
+    .map(|(major, _, minor, _, patch, release_type, revision, china_revision)| {
         let base = semver::Version::new(major, minor, patch);
         Version {
             base,
             release_type,
             revision,
+            china_revision,
         }
     })
     .parse(input)
 }
 
+fn parse_china_revision(input: &str) -> IResult<&str, u64> {
+    context(
+        "china revision",
+        (char('c'), map_res(digit1, |s: &str| s.parse::<u64>())),
+    )
+    .map(|(_, revision)| revision)
+    .parse(input)
+}
+
 fn parse_revision_hash(input: &str) -> IResult<&str, RevisionHash> {
     context(
         "revision hash",
@@ -452,6 +487,90 @@ mod tests {
         assert_eq!(version.revision, 4, "parse correct revision component");
     }
 
+    #[test]
+    fn round_trips_all_unity_version_shapes() {
+        let versions = [
+            "5.6.7f1",
+            "5.6.7p4",
+            "2017.4.40f1",
+            "2019.4.40f1",
+            "2020.1.0b16",
+            "2021.2.0a21",
+            "2021.3.45f1",
+            "2022.3.10f1",
+            "2022.3.10f1c1",
+            "2021.3.36f1c1",
+            "2020.3.48c1",
+            "2023.1.0x1",
+            "2023.3.0b10",
+            "6000.0.23f1",
+            "6000.1.0a9",
+            "6000.2.0f1c2",
+        ];
+        for version_string in versions {
+            let version = Version::from_str(version_string).unwrap();
+            assert_eq!(version.to_string(), version_string);
+            assert_eq!(Version::from_str(&version.to_string()).unwrap(), version);
+        }
+    }
+
+    #[test]
+    fn parses_experimental_and_china_versions() {
+        let version = Version::from_str("2023.1.0x1").unwrap();
+        assert_eq!(version.release_type(), ReleaseType::Experimental);
+        assert_eq!(version.revision(), 1);
+        assert!(!version.is_china());
+
+        let version = Version::from_str("2022.3.10f1c1").unwrap();
+        assert_eq!(version.release_type(), ReleaseType::Final);
+        assert_eq!(version.revision(), 1);
+        assert_eq!(version.china_revision(), Some(1));
+        assert!(version.is_china());
+        assert_eq!(
+            version,
+            Version::new(2022, 3, 10, ReleaseType::Final, 1).with_china_revision(1)
+        );
+
+        let version = Version::from_str("2020.3.48c1").unwrap();
+        assert_eq!(version.release_type(), ReleaseType::China);
+        assert_eq!(version.china_revision(), None);
+        assert!(version.is_china());
+    }
+
+    #[test]
+    fn orders_unity_versions() {
+        let mut versions: Vec<Version> = [
+            "6000.0.23f1",
+            "2022.3.10f1c1",
+            "2022.3.10f1",
+            "2022.3.10p1",
+            "2022.3.10b1",
+            "2022.3.10a1",
+            "2022.3.10x1",
+            "2022.3.9f1",
+            "2022.3.10f1c2",
+        ]
+        .iter()
+        .map(|v| Version::from_str(v).unwrap())
+        .collect();
+        versions.sort();
+        let versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        assert_eq!(
+            versions,
+            vec![
+                "2022.3.9f1",
+                "2022.3.10x1",
+                "2022.3.10a1",
+                "2022.3.10b1",
+                "2022.3.10p1",
+                "2022.3.10f1",
+                "2022.3.10f1c1",
+                "2022.3.10f1c2",
+                "6000.0.23f1",
+            ]
+        );
+    }
+
     #[test]
     fn test_complete_version_from_str() {
         // Test successful parsing
@@ -785,6 +904,15 @@ __libc_start_main
         assert_eq!(version.revision, 1);
     }
 
+    #[test]
+    fn extracts_china_and_experimental_versions_from_text() {
+        let version = Version::from_string_containing("Unity 2022.3.10f1c1 (abc123def456)").unwrap();
+        assert_eq!(version.to_string(), "2022.3.10f1c1");
+
+        let version = Version::from_string_containing("version 2023.1.0x1_abc123def456").unwrap();
+        assert_eq!(version.to_string(), "2023.1.0x1");
+    }
+
     #[test]
     fn handles_fallback_to_versions_without_hashes() {
         // Test content with only versions without hashes
@@ -819,7 +947,7 @@ Even more text
             major in 0u64..=u64::MAX,
             minor in 0u64..=u64::MAX,
             patch in 0u64..=u64::MAX,
-            release_type in prop_oneof!["f", "p", "b", "a"],
+            release_type in prop_oneof!["f", "p", "b", "a", "x", "c"],
             revision in 0u64..=u64::MAX,
         ) {
             let version_string = format!("{}.{}.{}{}{}", major, minor, patch, release_type, revision);
@@ -832,5 +960,23 @@ Even more text
             assert_eq!(version.release_type, ReleaseType::from_str(&release_type).unwrap());
             assert!(version.revision == revision, "parse correct revision component");
         }
+
+        #[test]
+        fn display_round_trips_all_valid_cases(
+            major in 0u64..=u64::MAX,
+            minor in 0u64..=u64::MAX,
+            patch in 0u64..=u64::MAX,
+            release_type in prop_oneof!["f", "p", "b", "a", "x", "c"],
+            revision in 0u64..=u64::MAX,
+            china_revision in proptest::option::of(0u64..=u64::MAX),
+        ) {
+            let mut version_string = format!("{}.{}.{}{}{}", major, minor, patch, release_type, revision);
+            if let Some(china_revision) = china_revision {
+                version_string.push_str(&format!("c{}", china_revision));
+            }
+            let version = Version::from_str(&version_string).unwrap();
+            prop_assert_eq!(version.to_string(), version_string);
+            prop_assert_eq!(version.china_revision(), china_revision);
+        }
     }
 }
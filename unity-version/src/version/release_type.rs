@@ -5,10 +5,12 @@ use thiserror::Error;
 #[derive(PartialEq, Eq, Ord, Hash, Debug, Clone, Copy, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum ReleaseType {
+    Experimental,
     Alpha,
     Beta,
     Patch,
     Final,
+    China,
 }
 
 impl PartialOrd for ReleaseType {
@@ -25,6 +27,8 @@ impl fmt::Display for ReleaseType {
                 ReleaseType::Patch => write!(f, "patch"),
                 ReleaseType::Beta => write!(f, "beta"),
                 ReleaseType::Alpha => write!(f, "alpha"),
+                ReleaseType::Experimental => write!(f, "experimental"),
+                ReleaseType::China => write!(f, "china"),
             }
         } else {
             match *self {
@@ -32,6 +36,8 @@ impl fmt::Display for ReleaseType {
                 ReleaseType::Patch => write!(f, "p"),
                 ReleaseType::Beta => write!(f, "b"),
                 ReleaseType::Alpha => write!(f, "a"),
+                ReleaseType::Experimental => write!(f, "x"),
+                ReleaseType::China => write!(f, "c"),
             }
         }
     }
@@ -61,6 +67,8 @@ impl FromStr for ReleaseType {
             "b" => Ok(ReleaseType::Beta),
             "p" => Ok(ReleaseType::Patch),
             "f" => Ok(ReleaseType::Final),
+            "x" => Ok(ReleaseType::Experimental),
+            "c" => Ok(ReleaseType::China),
             _ => Err(ReleaseTypeError::UnknownType(s.to_string())),
         }
     }
@@ -75,6 +83,8 @@ impl TryFrom<char> for ReleaseType {
             'b' => Ok(ReleaseType::Beta),
             'a' => Ok(ReleaseType::Alpha),
             'p' => Ok(ReleaseType::Patch),
+            'x' => Ok(ReleaseType::Experimental),
+            'c' => Ok(ReleaseType::China),
             _ => Err(ReleaseTypeError::InvalidCharacter(value)),
         }
     }
@@ -107,6 +117,16 @@ mod tests {
         assert_eq!(result, Ok(ReleaseType::Patch));
     }
     #[test]
+    fn should_return_experimental_when_given_x_as_input() {
+        let result = ReleaseType::try_from('x');
+        assert_eq!(result, Ok(ReleaseType::Experimental));
+    }
+    #[test]
+    fn should_return_china_when_given_c_as_input() {
+        let result = ReleaseType::try_from('c');
+        assert_eq!(result, Ok(ReleaseType::China));
+    }
+    #[test]
     fn should_return_error_when_given_empty_input() {
         let result = ReleaseType::try_from(' ');
         assert_eq!(result, Err(ReleaseTypeError::InvalidCharacter(' ')));
@@ -118,6 +138,8 @@ mod tests {
         assert_eq!(ReleaseType::Patch.to_string(), "p");
         assert_eq!(ReleaseType::Alpha.to_string(), "a");
         assert_eq!(ReleaseType::Beta.to_string(), "b");
+        assert_eq!(ReleaseType::Experimental.to_string(), "x");
+        assert_eq!(ReleaseType::China.to_string(), "c");
     }
     #[test]
     fn should_format_using_correct_alternative_format() {
@@ -125,6 +147,32 @@ mod tests {
         assert_eq!(&format!("{:#}", ReleaseType::Patch), "patch");
         assert_eq!(&format!("{:#}", ReleaseType::Beta), "beta");
         assert_eq!(&format!("{:#}", ReleaseType::Alpha), "alpha");
+        assert_eq!(&format!("{:#}", ReleaseType::Experimental), "experimental");
+        assert_eq!(&format!("{:#}", ReleaseType::China), "china");
+    }
+
+    #[test]
+    fn should_order_release_types_by_maturity() {
+        let mut release_types = vec![
+            ReleaseType::China,
+            ReleaseType::Final,
+            ReleaseType::Beta,
+            ReleaseType::Experimental,
+            ReleaseType::Patch,
+            ReleaseType::Alpha,
+        ];
+        release_types.sort();
+        assert_eq!(
+            release_types,
+            vec![
+                ReleaseType::Experimental,
+                ReleaseType::Alpha,
+                ReleaseType::Beta,
+                ReleaseType::Patch,
+                ReleaseType::Final,
+                ReleaseType::China,
+            ]
+        );
     }
 
     proptest! {
@@ -134,7 +182,7 @@ mod tests {
         }
 
         #[test]
-        fn from_str_supports_all_valid_cases(s in "(a|b|f|p)") {
+        fn from_str_supports_all_valid_cases(s in "(a|b|c|f|p|x)") {
             ReleaseType::from_str(&s).unwrap();
         }
     }
@@ -0,0 +1,537 @@
+use super::{parse_release_type, ReleaseType, Version};
+use crate::error::VersionError;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1, space0},
+    combinator::{all_consuming, map, map_res, opt, value},
+    error::context,
+    sequence::{delimited, preceded},
+    IResult, Parser,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+const FINAL_ONLY: &str = "final-only";
+
+/// A requirement on Unity versions, the Unity aware counterpart of [`semver::VersionReq`].
+///
+/// Unlike comparing the [`Version::base`] of a version with a semver requirement, release
+/// types and revisions take part in the comparison: `2022.3.0b5` doesn't match `>=2022.3.0f1`.
+///
+/// # Syntax
+///
+/// A requirement is a comma separated list of comparators which all have to match:
+///
+/// - `2022.3.10f1`, `=2022.3` - the version or every version starting with the given components
+/// - `>2022.3.10f1`, `>=2022.3`, `<2023`, `<=2022.3.10` - compared with the given components
+/// - `~2022.3.10f1` - at least the given version within the same minor line
+/// - `^2022.3.10f1` - at least the given version within the same major line
+/// - `2022.3.*`, `2022.x` - wildcards for the minor or patch component
+/// - `2022.3.*f` - the release type alone, e.g. every final release of the `2022.3` line
+/// - `final-only` - only final releases
+/// - `*` - every version
+///
+/// Alpha, beta and experimental versions only match if one of the comparators names a
+/// pre-release type, e.g. `>=2023.1.0b1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Comparator {
+    Version { op: Op, pattern: Pattern },
+    FinalOnly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+/// A partial version. `None` components are wildcards or not specified.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Pattern {
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    release_type: Option<ReleaseType>,
+    revision: Option<u64>,
+}
+
+impl VersionReq {
+    /// A requirement which matches every version.
+    pub const STAR: VersionReq = VersionReq {
+        comparators: Vec::new(),
+    };
+
+    /// Returns `true` if `version` satisfies the requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+            && (!is_pre_release(version.release_type()) || self.allows_pre_releases())
+    }
+
+    /// Returns `true` if `version` satisfies the comparators of the requirement, including
+    /// alpha, beta and experimental versions which [`matches`](Self::matches) excludes.
+    ///
+    /// Useful when the candidates are pre-releases already, e.g. releases of the beta stream.
+    pub fn matches_pre_release(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+
+    fn allows_pre_releases(&self) -> bool {
+        self.comparators.iter().any(|c| match c {
+            Comparator::Version { pattern, .. } => pattern.release_type.is_some_and(is_pre_release),
+            Comparator::FinalOnly => false,
+        })
+    }
+}
+
+fn is_pre_release(release_type: ReleaseType) -> bool {
+    matches!(
+        release_type,
+        ReleaseType::Experimental | ReleaseType::Alpha | ReleaseType::Beta
+    )
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        match self {
+            Comparator::FinalOnly => version.release_type() == ReleaseType::Final,
+            Comparator::Version { op, pattern } => {
+                let ordering = pattern.compare(version);
+                match op {
+                    Op::Exact => ordering == Ordering::Equal,
+                    Op::Greater => ordering == Ordering::Greater,
+                    Op::GreaterEq => ordering != Ordering::Less,
+                    Op::Less => ordering == Ordering::Less,
+                    Op::LessEq => ordering != Ordering::Greater,
+                    Op::Tilde => {
+                        ordering != Ordering::Less
+                            && version.major() == pattern.major
+                            && pattern.minor.is_none_or(|minor| version.minor() == minor)
+                    }
+                    Op::Caret => ordering != Ordering::Less && version.major() == pattern.major,
+                }
+            }
+        }
+    }
+}
+
+impl Pattern {
+    /// Compares `version` with the specified components of the pattern. Wildcards are skipped.
+    fn compare(&self, version: &Version) -> Ordering {
+        let components = [
+            (Some(self.major), version.major()),
+            (self.minor, version.minor()),
+            (self.patch, version.patch()),
+        ];
+        components
+            .iter()
+            .filter_map(|(expected, actual)| expected.map(|expected| actual.cmp(&expected)))
+            .chain(
+                self.release_type
+                    .map(|release_type| version.release_type().cmp(&release_type)),
+            )
+            .chain(
+                self.revision
+                    .map(|revision| version.revision().cmp(&revision)),
+            )
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.comparators.is_empty() {
+            return write!(f, "*");
+        }
+        for (index, comparator) in self.comparators.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", comparator)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Comparator::FinalOnly => write!(f, "{}", FINAL_ONLY),
+            Comparator::Version { op, pattern } => write!(f, "{}{}", op, pattern),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+        };
+        write!(f, "{}", op)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.major)?;
+        match (self.minor, self.patch, self.release_type) {
+            (None, None, None) => return Ok(()),
+            (Some(minor), _, _) => write!(f, ".{}", minor)?,
+            (None, _, _) => write!(f, ".*")?,
+        }
+        match (self.patch, self.release_type) {
+            (None, None) => return Ok(()),
+            (Some(patch), _) => write!(f, ".{}", patch)?,
+            (None, _) => write!(f, ".*")?,
+        }
+        if let Some(release_type) = self.release_type {
+            write!(f, "{}", release_type)?;
+        }
+        if let Some(revision) = self.revision {
+            write!(f, "{}", revision)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |reason: String| VersionError::RequirementParsingFailed {
+            requirement: s.to_string(),
+            reason,
+        };
+
+        if s.trim() == "*" {
+            return Ok(VersionReq::STAR);
+        }
+
+        let comparators = s
+            .split(',')
+            .map(
+                |comparator| match all_consuming(parse_comparator).parse(comparator) {
+                    Ok((_, comparator)) => Ok(comparator),
+                    Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+                        Err(error(format!("unexpected input '{}'", e.input.trim())))
+                    }
+                    Err(nom::Err::Incomplete(_)) => Err(error("incomplete input".to_string())),
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(VersionReq { comparators })
+    }
+}
+
+impl TryFrom<&str> for VersionReq {
+    type Error = VersionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        VersionReq::from_str(value)
+    }
+}
+
+impl Serialize for VersionReq {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionReq {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        VersionReq::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+fn parse_op(input: &str) -> IResult<&str, Op> {
+    context(
+        "operator",
+        alt((
+            value(Op::GreaterEq, tag(">=")),
+            value(Op::LessEq, tag("<=")),
+            value(Op::Greater, char('>')),
+            value(Op::Less, char('<')),
+            value(Op::Exact, char('=')),
+            value(Op::Tilde, char('~')),
+            value(Op::Caret, char('^')),
+        )),
+    )
+    .parse(input)
+}
+
+fn parse_number(input: &str) -> IResult<&str, u64> {
+    map_res(digit1, |s: &str| s.parse::<u64>()).parse(input)
+}
+
+fn parse_component(input: &str) -> IResult<&str, Option<u64>> {
+    alt((
+        map(parse_number, Some),
+        value(None, alt((char('*'), char('x'), char('X')))),
+    ))
+    .parse(input)
+}
+
+fn parse_pattern(input: &str) -> IResult<&str, Pattern> {
+    context(
+        "version pattern",
+        (
+            parse_number,
+            opt((
+                preceded(char('.'), parse_component),
+                opt((
+                    preceded(char('.'), parse_component),
+                    opt((parse_release_type, opt(parse_number))),
+                )),
+            )),
+        ),
+    )
+    .map(|(major, rest)| {
+        let (minor, rest) = rest.unwrap_or((None, None));
+        let (patch, rest) = rest.unwrap_or((None, None));
+        let (release_type, revision) = rest
+            .map(|(release_type, revision)| (Some(release_type), revision))
+            .unwrap_or((None, None));
+        Pattern {
+            major,
+            minor,
+            patch,
+            release_type,
+            revision,
+        }
+    })
+    .parse(input)
+}
+
+fn parse_comparator(input: &str) -> IResult<&str, Comparator> {
+    delimited(
+        space0,
+        alt((
+            value(Comparator::FinalOnly, tag(FINAL_ONLY)),
+            map(
+                (opt(parse_op), space0, parse_pattern),
+                |(op, _, pattern)| Comparator::Version {
+                    op: op.unwrap_or(Op::Exact),
+                    pattern,
+                },
+            ),
+        )),
+        space0,
+    )
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn req(s: &str) -> VersionReq {
+        VersionReq::from_str(s).unwrap()
+    }
+
+    fn version(s: &str) -> Version {
+        Version::from_str(s).unwrap()
+    }
+
+    fn assert_matches(requirement: &str, matching: &[&str], not_matching: &[&str]) {
+        let requirement = req(requirement);
+        for v in matching {
+            assert!(
+                requirement.matches(&version(v)),
+                "{} should match {}",
+                requirement,
+                v
+            );
+        }
+        for v in not_matching {
+            assert!(
+                !requirement.matches(&version(v)),
+                "{} should not match {}",
+                requirement,
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn parses_and_formats_requirements() {
+        let cases = [
+            ("*", "*"),
+            ("2022.3.10f1", "=2022.3.10f1"),
+            ("=2022.3", "=2022.3"),
+            (">=2022.3.10f1, <2023", ">=2022.3.10f1, <2023"),
+            (" > 2022.3.10f1 ,<= 2023.1 ", ">2022.3.10f1, <=2023.1"),
+            ("~2022.3.10f1", "~2022.3.10f1"),
+            ("^2022.3", "^2022.3"),
+            ("2022.3.*", "=2022.3"),
+            ("2022.x", "=2022"),
+            ("2022.3.*f", "=2022.3.*f"),
+            ("2022.*.*b", "=2022.*.*b"),
+            ("2023.1.0x1", "=2023.1.0x1"),
+            ("2022.3.*x", "=2022.3.*x"),
+            ("final-only", "final-only"),
+            (">=2022.3, final-only", ">=2022.3, final-only"),
+        ];
+        for (input, expected) in cases {
+            let requirement = req(input);
+            assert_eq!(requirement.to_string(), expected, "{}", input);
+            assert_eq!(req(&requirement.to_string()), requirement, "{}", input);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_requirements() {
+        for input in [
+            "",
+            "latest",
+            ">=",
+            "2022.3.10f1c1",
+            "2022.3.10q1",
+            ">=2022,",
+            "final",
+        ] {
+            let result = VersionReq::from_str(input);
+            assert!(
+                matches!(result, Err(VersionError::RequirementParsingFailed { .. })),
+                "{} should be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn compares_release_types_and_revisions() {
+        assert_matches(
+            ">=2022.3.10f1, <2023",
+            &["2022.3.10f1", "2022.3.10f2", "2022.3.11f1", "2022.3.10f1c1"],
+            &["2022.3.9f1", "2022.3.10f0", "2022.3.10p1", "2023.1.0f1"],
+        );
+        assert_matches(
+            ">2022.3.10f1",
+            &["2022.3.10f2", "2022.3.11f1"],
+            &["2022.3.10f1"],
+        );
+        assert_matches(
+            "<=2022.3.10",
+            &["2022.3.10f5", "2021.3.1f1"],
+            &["2022.3.11f1"],
+        );
+    }
+
+    #[test]
+    fn excludes_pre_releases_unless_requested() {
+        assert_matches(">=2022.3.0", &["2022.3.0f1"], &["2022.3.0b5", "2022.3.1a1"]);
+        assert_matches(
+            ">=2023.1.0b1",
+            &["2023.1.0b1", "2023.1.0f1"],
+            &["2023.1.0a20"],
+        );
+        assert_matches("2023.1.*x", &["2023.1.0x1"], &["2023.1.0f1", "2023.1.0b1"]);
+    }
+
+    #[test]
+    fn matches_pre_release_skips_the_pre_release_gate() {
+        assert!(req("2023.3").matches_pre_release(&version("2023.3.0b4")));
+        assert!(req(">=2022.3.0").matches_pre_release(&version("2022.3.1a1")));
+        assert!(!req("2023.3").matches_pre_release(&version("2023.2.0b4")));
+        assert!(!req("final-only").matches_pre_release(&version("2023.3.0b4")));
+    }
+
+    #[test]
+    fn matches_wildcards_and_release_types() {
+        assert_matches(
+            "2022.3.*f",
+            &["2022.3.0f1", "2022.3.45f1"],
+            &["2022.3.10p1", "2022.3.0b5", "2022.4.0f1"],
+        );
+        assert_matches("2022.x", &["2022.1.0f1", "2022.3.45f1"], &["2023.1.0f1"]);
+        assert_matches("*", &["2022.3.10f1", "6000.0.23f1"], &["2023.1.0b1"]);
+    }
+
+    #[test]
+    fn matches_tilde_and_caret() {
+        assert_matches(
+            "~2022.3.10f1",
+            &["2022.3.10f1", "2022.3.20f1"],
+            &["2022.3.9f1", "2022.4.0f1"],
+        );
+        assert_matches(
+            "^2022.3.10f1",
+            &["2022.3.10f1", "2022.4.0f1"],
+            &["2022.3.9f1", "2023.1.0f1"],
+        );
+    }
+
+    #[test]
+    fn final_only_excludes_other_release_types() {
+        assert_matches(
+            "final-only",
+            &["2022.3.10f1", "6000.0.23f1", "2022.3.10f1c1"],
+            &["2022.3.10p1", "2020.3.48c1", "2023.1.0b1"],
+        );
+        assert_matches(
+            ">=2022.3, final-only",
+            &["2022.3.10f1"],
+            &["2021.3.10f1", "2022.3.10p1"],
+        );
+    }
+
+    #[test]
+    fn serializes_as_string() {
+        let requirement = req(">=2022.3.10f1, <2023");
+        let json = serde_json::to_string(&requirement).unwrap();
+        assert_eq!(json, "\">=2022.3.10f1, <2023\"");
+        assert_eq!(
+            serde_json::from_str::<VersionReq>(&json).unwrap(),
+            requirement
+        );
+        assert!(serde_json::from_str::<VersionReq>("\"latest\"").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn from_str_does_not_crash(s in "\\PC*") {
+            let _r = VersionReq::from_str(&s);
+        }
+
+        #[test]
+        fn exact_requirement_matches_version(
+            major in 0u64..10000,
+            minor in 0u64..10,
+            patch in 0u64..100,
+            release_type in prop_oneof!["f", "p", "b", "a", "x", "c"],
+            revision in 0u64..100,
+        ) {
+            let version_string = format!("{}.{}.{}{}{}", major, minor, patch, release_type, revision);
+            let requirement = req(&version_string);
+            prop_assert!(requirement.matches(&version(&version_string)));
+            prop_assert_eq!(req(&requirement.to_string()), requirement);
+        }
+    }
+}
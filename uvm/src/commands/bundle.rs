@@ -0,0 +1,100 @@
+use clap::Args;
+use console::style;
+use std::io;
+use std::path::PathBuf;
+use unity_version::Version;
+use uvm_install::{BundleOptions, InstallArchitecture};
+use uvm_live_platform::UnityReleaseDownloadPlatform;
+
+use crate::commands::progress::{is_interactive, ArcProgressCoordinator, SimpleProgressHandler};
+use crate::commands::Command;
+
+#[derive(Args, Debug)]
+pub struct BundleCommand {
+    /// Module to add to the bundle
+    ///
+    /// A support module to bundle. You can list all available
+    /// modules for a given version using `uvm modules`
+    #[arg(short, long = "module", number_of_values = 1)]
+    pub modules: Option<Vec<String>>,
+
+    /// Bundle also synced modules
+    ///
+    /// Synced modules are optional dependencies of some Unity modules.
+    /// e.g. Android SDK for the android module.
+    #[arg(long = "with-sync")]
+    pub sync: bool,
+
+    /// The platform to create the bundle for
+    #[arg(long, value_enum, default_value_t = UnityReleaseDownloadPlatform::default())]
+    pub platform: UnityReleaseDownloadPlatform,
+
+    /// The architecture to create the bundle for
+    #[arg(long, value_enum, default_value_t = InstallArchitecture::default())]
+    pub architecture: InstallArchitecture,
+
+    /// The Unity version to bundle in the form of `2018.1.0f3`
+    pub editor_version: Version,
+
+    /// The directory to write the bundle to
+    ///
+    /// Install the bundle on another machine with `uvm install --from-bundle <destination> <version>`
+    pub destination: PathBuf,
+}
+
+impl Command for BundleCommand {
+    fn execute(&self) -> io::Result<i32> {
+        let mut options = BundleOptions::new(self.editor_version.to_owned())
+            .with_install_sync(self.sync)
+            .with_platform(self.platform)
+            .with_architecture(self.architecture);
+
+        if let Some(modules) = &self.modules {
+            options = options.with_requested_modules(modules);
+        }
+
+        let progress_mode = crate::commands::progress::get_progress_mode();
+        let coordinator_opt = if is_interactive() {
+            use crate::commands::progress::MultiProgressCoordinator;
+            use std::sync::Arc;
+            let coordinator = Arc::new(MultiProgressCoordinator::new(0));
+            options = options.with_progress_handler(ArcProgressCoordinator(coordinator.clone()));
+            Some(coordinator)
+        } else if progress_mode != crate::commands::progress::ProgressMode::Disabled {
+            options = options.with_progress_handler(SimpleProgressHandler::new("Unity".to_string()));
+            None
+        } else {
+            None
+        };
+
+        let result = options.create(&self.destination);
+
+        if let Some(ref coordinator) = coordinator_opt {
+            coordinator.clear();
+        }
+
+        match result {
+            Ok(manifest) => {
+                eprintln!(
+                    "\n{} Unity {} ({}, {}) → {}",
+                    style("Bundled").green().bold(),
+                    manifest.version,
+                    manifest.platform,
+                    manifest.architecture,
+                    self.destination.display(),
+                );
+                for installer in &manifest.installers {
+                    eprintln!("  {} {}", installer.id, style(&installer.file_name).dim());
+                }
+                Ok(0)
+            }
+            Err(e) => {
+                eprintln!("{}: {}", style("Error").red().bold(), e);
+                Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("Bundle creation failed: {}", e),
+                ))
+            }
+        }
+    }
+}
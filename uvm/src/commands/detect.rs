@@ -1,9 +1,11 @@
 use clap::Args;
 use log::info;
+use serde::Serialize;
 use uvm_detect::DetectOptions;
 use std::path::PathBuf;
 use std::{env, io};
 
+use crate::commands::presentation::{output_format, print_json, OutputFormat};
 use crate::commands::Command;
 
 #[derive(Args, Debug)]
@@ -22,8 +24,32 @@ impl Command for DetectCommand {
         };
         
         info!("Detect the project version at path {}", project_path.display());
-        let version = DetectOptions::new().recursive(self.recursive).detect_project_version(project_path)?; 
+        let mut options = DetectOptions::new();
+        options.recursive(self.recursive);
+
+        if output_format() == OutputFormat::Json {
+            let project_dir = options.detect_unity_project_dir(project_path)?;
+            let version = options.detect_project_version(&project_dir)?;
+            let revision = options
+                .detect_project_version_revision_hash(&project_dir)
+                .ok();
+            print_json(&DetectView {
+                project_path: project_dir.display().to_string(),
+                version: version.to_string(),
+                revision: revision.map(|revision| revision.to_string()),
+            })?;
+            return Ok(0);
+        }
+
+        let version = options.detect_project_version(project_path)?;
         println!("{}", version);
         Ok(0)
     }
 }
+
+#[derive(Serialize)]
+struct DetectView {
+    project_path: String,
+    version: String,
+    revision: Option<String>,
+}
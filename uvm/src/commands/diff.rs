@@ -0,0 +1,328 @@
+use clap::Args;
+use console::style;
+use log::info;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io;
+use unity_version::Version;
+use uvm_live_platform::{fetch_release, Download, Module, Release};
+
+use crate::commands::presentation::{
+    output_format, print_json, OutputFormat, RenderOptions, TextRenderer,
+};
+use crate::commands::release_notes::{release_notes_of, releases_between, ReleaseNotes};
+use crate::commands::Command;
+
+#[derive(Args, Debug)]
+pub struct DiffCommand {
+    /// Only show release notes entries containing one of these keywords (case-insensitive)
+    #[arg(short = 'g', long = "grep", value_name = "KEYWORD")]
+    pub keywords: Vec<String>,
+
+    /// Only compare the modules without fetching release notes
+    #[arg(long)]
+    pub modules_only: bool,
+
+    /// The installed or current version
+    pub from: Version,
+
+    /// The version to upgrade to
+    ///
+    /// Release notes are collected for every release after FROM up to TO in the stream of TO.
+    pub to: Version,
+}
+
+impl Command for DiffCommand {
+    fn execute(&self) -> io::Result<i32> {
+        if self.from >= self.to {
+            eprintln!(
+                "{}: {} must be older than {}",
+                style("Error").red().bold(),
+                self.from,
+                self.to
+            );
+            return Ok(1);
+        }
+
+        let from = load_release(&self.from)?;
+        let to = load_release(&self.to)?;
+        let mut diff = ReleaseDiff::new(&from, &to);
+
+        let mut failed = 0;
+        if !self.modules_only {
+            for version in releases_between(&self.from, &self.to, Some(to.stream))? {
+                let release = if version == self.to {
+                    Ok(to.clone())
+                } else {
+                    load_release(&version)
+                };
+                match release.and_then(release_notes_of) {
+                    Ok(notes) => diff.release_notes.push(notes.filter(&self.keywords)),
+                    Err(e) => {
+                        eprintln!(
+                            "{}: release notes of {}: {}",
+                            style("Error").red().bold(),
+                            version,
+                            e
+                        );
+                        failed += 1;
+                    }
+                }
+            }
+            if !self.keywords.is_empty() {
+                diff.release_notes
+                    .retain(|release_notes| !release_notes.sections.is_empty());
+            }
+        }
+
+        if output_format() == OutputFormat::Json {
+            print_json(&diff)?;
+        } else {
+            let renderer = TextRenderer::new(RenderOptions::default());
+            print!("{}", renderer.render_view(&diff));
+        }
+        Ok(if failed == 0 { 0 } else { 1 })
+    }
+}
+
+fn load_release(version: &Version) -> io::Result<Release> {
+    info!("fetch release {}", version);
+    fetch_release(version.clone()).map_err(|e| {
+        eprintln!("{}: {}", style("Error").red().bold(), e);
+        io::Error::other(format!("failed to load release {}: {}", version, e))
+    })
+}
+
+/// The changes between two editor releases: the release notes of every release in between
+/// and the differences of their module sets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseDiff {
+    pub from: String,
+    pub to: String,
+    pub stream: String,
+    pub release_notes: Vec<ReleaseNotes>,
+    pub modules: ModuleDiff,
+}
+
+impl ReleaseDiff {
+    pub fn new(from: &Release, to: &Release) -> Self {
+        Self {
+            from: from.version.clone(),
+            to: to.version.clone(),
+            stream: to.stream.to_string(),
+            release_notes: Vec::new(),
+            modules: ModuleDiff::new(from.downloads.first(), to.downloads.first()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct ModuleDiff {
+    pub added: Vec<ModuleSummary>,
+    pub removed: Vec<ModuleSummary>,
+    pub changed: Vec<ModuleChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleSummary {
+    pub id: String,
+    pub description: String,
+    /// Download size in bytes
+    pub download_size: u64,
+    /// Size on disk after installation in bytes
+    pub installed_size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleChange {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_size: Option<Change<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_size: Option<Change<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<Change<Option<String>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change<T> {
+    pub from: T,
+    pub to: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    fn of(from: T, to: T) -> Option<Self> {
+        if from == to {
+            None
+        } else {
+            Some(Self { from, to })
+        }
+    }
+}
+
+impl ModuleDiff {
+    /// Compares the modules, including sync modules, of the downloads of two releases.
+    pub fn new(from: Option<&Download>, to: Option<&Download>) -> Self {
+        let from = modules_by_id(from);
+        let to = modules_by_id(to);
+
+        let mut diff = Self::default();
+        for (id, module) in &to {
+            match from.get(id) {
+                None => diff.added.push(ModuleSummary::from(*module)),
+                Some(old) => {
+                    let change = ModuleChange {
+                        id: id.to_string(),
+                        download_size: Change::of(
+                            old.download_size.to_bytes() as u64,
+                            module.download_size.to_bytes() as u64,
+                        ),
+                        installed_size: Change::of(
+                            old.installed_size.to_bytes() as u64,
+                            module.installed_size.to_bytes() as u64,
+                        ),
+                        destination: Change::of(old.destination(), module.destination()),
+                    };
+                    if change.download_size.is_some()
+                        || change.installed_size.is_some()
+                        || change.destination.is_some()
+                    {
+                        diff.changed.push(change);
+                    }
+                }
+            }
+        }
+        diff.removed = from
+            .iter()
+            .filter(|(id, _)| !to.contains_key(*id))
+            .map(|(_, module)| ModuleSummary::from(*module))
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn modules_by_id(download: Option<&Download>) -> BTreeMap<&str, &Module> {
+    download
+        .map(|download| {
+            download
+                .iter_modules()
+                .map(|module| (module.id().as_str(), module))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl From<&Module> for ModuleSummary {
+    fn from(module: &Module) -> Self {
+        Self {
+            id: module.id().to_string(),
+            description: module.description().to_string(),
+            download_size: module.download_size.to_bytes() as u64,
+            installed_size: module.installed_size.to_bytes() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn download(modules: &str) -> Download {
+        serde_json::from_str(&format!(
+            r#"{{
+                "url": "https://example.com/unity.tar.xz",
+                "platform": "LINUX",
+                "architecture": "X86_64",
+                "downloadSize": 1000,
+                "installedSize": 2000,
+                "modules": {}
+            }}"#,
+            modules
+        ))
+        .unwrap()
+    }
+
+    fn module(id: &str, download_size: u64, destination: &str) -> String {
+        format!(
+            r#"{{
+                "id": "{id}",
+                "name": "{id}",
+                "description": "{id} support",
+                "category": "Platforms",
+                "url": "https://example.com/{id}.tar.xz",
+                "downloadSize": {download_size},
+                "installedSize": 2000,
+                "destination": "{destination}"
+            }}"#
+        )
+    }
+
+    #[test]
+    fn diffs_module_sets() {
+        let from = download(&format!(
+            "[{}, {}, {}]",
+            module(
+                "android",
+                1000,
+                "{UNITY_PATH}/Editor/Data/PlaybackEngines/AndroidPlayer"
+            ),
+            module("ios", 1000, "{UNITY_PATH}/Editor/Data/PlaybackEngines"),
+            module(
+                "webgl",
+                1000,
+                "{UNITY_PATH}/Editor/Data/PlaybackEngines/WebGLSupport"
+            ),
+        ));
+        let to = download(&format!(
+            "[{}, {}, {}]",
+            module(
+                "android",
+                1500,
+                "{UNITY_PATH}/Editor/Data/PlaybackEngines/AndroidPlayer"
+            ),
+            module("ios", 1000, "{UNITY_PATH}/Editor/Data/PlaybackEngines/iOS"),
+            module(
+                "linux-il2cpp",
+                1000,
+                "{UNITY_PATH}/Editor/Data/PlaybackEngines/LinuxStandaloneSupport"
+            ),
+        ));
+
+        let diff = ModuleDiff::new(Some(&from), Some(&to));
+        let ids = |modules: &[ModuleSummary]| -> Vec<String> {
+            modules.iter().map(|module| module.id.clone()).collect()
+        };
+        assert_eq!(ids(&diff.added), vec!["linux-il2cpp"]);
+        assert_eq!(ids(&diff.removed), vec!["webgl"]);
+        assert_eq!(diff.changed.len(), 2);
+        assert_eq!(diff.changed[0].id, "android");
+        assert_eq!(
+            diff.changed[0].download_size,
+            Some(Change {
+                from: 1000,
+                to: 1500
+            })
+        );
+        assert_eq!(diff.changed[0].installed_size, None);
+        assert_eq!(diff.changed[0].destination, None);
+        assert_eq!(diff.changed[1].id, "ios");
+        assert_eq!(
+            diff.changed[1].destination,
+            Some(Change {
+                from: Some("{UNITY_PATH}/Editor/Data/PlaybackEngines/iOSSupport".to_string()),
+                to: Some("{UNITY_PATH}/Editor/Data/PlaybackEngines/iOS/iOSSupport".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn identical_downloads_have_no_changes() {
+        let from = download(&format!("[{}]", module("android", 1000, "{UNITY_PATH}")));
+        assert!(ModuleDiff::new(Some(&from), Some(&from.clone())).is_empty());
+        assert!(ModuleDiff::new(None, None).is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use clap::Args;
+use console::style;
+use log::info;
+use std::io;
+use unity_hub::unity::doctor::{self, Diagnosis};
+use unity_hub::unity::{list_all_installations, UnityInstallation};
+use unity_hub::Editors;
+use unity_version::Version;
+
+use crate::commands::Command;
+
+#[derive(Args, Debug)]
+pub struct DoctorCommand {
+    /// Repair fixable issues by updating the modules.json of the installation
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Check only the installation of this version
+    pub version: Option<Version>,
+}
+
+impl Command for DoctorCommand {
+    fn execute(&self) -> io::Result<i32> {
+        let installations: Vec<UnityInstallation> = list_all_installations()
+            .map_err(|e| io::Error::other(e.to_string()))
+            .inspect_err(|e| {
+                eprintln!("{}: {}", style("Error").red().bold(), e);
+            })?
+            .filter(|installation| self.matches(installation.version()))
+            .collect();
+
+        let mut diagnoses: Vec<(Option<&UnityInstallation>, Diagnosis)> = installations
+            .iter()
+            .map(|installation| (Some(installation), doctor::diagnose(installation)))
+            .collect();
+
+        match Editors::load_entries() {
+            Ok(editors) => {
+                for editor in editors.iter().filter(|e| self.matches(e.version())) {
+                    if let Some(issue) = doctor::diagnose_hub_editor(editor) {
+                        match diagnoses
+                            .iter_mut()
+                            .find(|(_, diagnosis)| &diagnosis.version == editor.version())
+                        {
+                            Some((_, diagnosis)) => diagnosis.issues.push(issue),
+                            None => diagnoses.push((
+                                None,
+                                Diagnosis {
+                                    version: editor.version().to_owned(),
+                                    path: editor.location().to_path_buf(),
+                                    issues: vec![issue],
+                                },
+                            )),
+                        }
+                    }
+                }
+            }
+            Err(e) => info!("unable to load Unity Hub editors: {}", e),
+        }
+
+        if diagnoses.is_empty() {
+            match &self.version {
+                Some(version) => eprintln!("No installation of Unity {} found", version),
+                None => eprintln!("No Unity installations found"),
+            }
+            return Ok(0);
+        }
+
+        let mut remaining = 0;
+        for (installation, diagnosis) in &diagnoses {
+            eprintln!(
+                "{} {}",
+                style(format!("Unity {}", diagnosis.version)).bold(),
+                style(diagnosis.path.display()).dim()
+            );
+            if diagnosis.is_healthy() {
+                eprintln!("  {} no issues found", style("✓").green());
+                continue;
+            }
+
+            for issue in &diagnosis.issues {
+                let fixable = if issue.is_fixable() {
+                    style(" (fixable)").yellow().to_string()
+                } else {
+                    String::new()
+                };
+                eprintln!("  {} {}{}", style("✗").red(), issue, fixable);
+            }
+
+            let fixed = match installation {
+                Some(installation) if self.fix => doctor::fix(*installation, diagnosis)
+                    .map_err(|e| io::Error::other(e.to_string()))
+                    .inspect_err(|e| {
+                        eprintln!("{}: {}", style("Error").red().bold(), e);
+                    })?,
+                _ => 0,
+            };
+            if fixed > 0 {
+                eprintln!(
+                    "  {} marked {} module(s) as not installed in modules.json",
+                    style("fixed").green().bold(),
+                    fixed
+                );
+            }
+            remaining += diagnosis.issues.len() - fixed;
+        }
+
+        let fixable = diagnoses
+            .iter()
+            .map(|(_, diagnosis)| diagnosis.fixable_issues().count())
+            .sum::<usize>();
+        if remaining == 0 {
+            return Ok(0);
+        }
+        if !self.fix && fixable > 0 {
+            eprintln!(
+                "\n{} fixable issue(s) found, run `uvm doctor --fix` to repair them",
+                fixable
+            );
+        }
+        Ok(1)
+    }
+}
+
+impl DoctorCommand {
+    fn matches(&self, version: &Version) -> bool {
+        self.version.as_ref().is_none_or(|v| v == version)
+    }
+}
@@ -1,17 +1,54 @@
 use clap::Args;
-use console::style;
+use console::{style, Term};
 use indicatif::{HumanBytes, HumanDuration};
-use std::io;
+use log::debug;
+use std::io::{self, IsTerminal};
 use std::path::PathBuf;
 use std::time::Instant;
-use unity_version::Version;
-use uvm_install::{InstallArchitecture, InstallOptions};
+use uvm_detect::DetectOptions;
+use uvm_install::{
+    EulaAcceptance, InstallArchitecture, InstallError, InstallOptions, PlannedComponent,
+    PlannedEula, VersionRequirement,
+};
+use uvm_live_platform::{UnityReleaseEntitlement, UnityReleaseStream};
 
 use crate::commands::progress::{
-    is_interactive, ArcProgressCoordinator, SimpleProgressHandler,
+    global_multi_progress, is_interactive, ArcProgressCoordinator, SimpleProgressHandler,
+};
+use crate::commands::presentation::{
+    output_format, print_json, OutputFormat, RenderOptions, TextRenderer,
 };
+use crate::commands::requirements::ProjectRequirements;
 use crate::commands::Command;
 
+/// Acceptance of the EULAs some modules require, e.g. the Android SDK and NDK.
+#[derive(Args, Debug, Default)]
+pub struct EulaArgs {
+    /// Accept the EULAs of the modules to install
+    ///
+    /// Accepts all EULAs without a value or only those of the listed module ids, e.g.
+    /// `--accept-eula=android-sdk-ndk-tools`. Required to install modules with an EULA
+    /// without a terminal, otherwise each EULA is prompted for.
+    #[arg(
+        long,
+        value_name = "MODULES",
+        num_args = 0..=1,
+        require_equals = true,
+        value_delimiter = ','
+    )]
+    pub accept_eula: Option<Vec<String>>,
+}
+
+impl EulaArgs {
+    pub fn acceptance(&self) -> EulaAcceptance {
+        match &self.accept_eula {
+            None => EulaAcceptance::None,
+            Some(modules) if modules.is_empty() => EulaAcceptance::All,
+            Some(modules) => EulaAcceptance::Components(modules.iter().cloned().collect()),
+        }
+    }
+}
+
 #[derive(Args, Debug)]
 pub struct InstallArgs {
     /// Module to install
@@ -29,11 +66,71 @@ pub struct InstallArgs {
     pub sync: bool,
 
     /// The architecture to install
-    #[arg(long, value_enum, default_value_t = InstallArchitecture::default())]
-    pub architecture: InstallArchitecture,
+    ///
+    /// Defaults to the architecture preferred by the project manifest
+    /// when installing with `--project` or the host architecture.
+    #[arg(long, value_enum)]
+    pub architecture: Option<InstallArchitecture>,
+
+    /// Number of installers to download at the same time
+    ///
+    /// Components are still installed one after another in dependency order.
+    #[arg(long, env = "UVM_MAX_PARALLEL_DOWNLOADS", default_value_t = uvm_install::DEFAULT_MAX_PARALLEL_DOWNLOADS as u16, value_parser = clap::value_parser!(u16).range(1..))]
+    pub max_parallel_downloads: u16,
+
+    /// Install from the local installer cache without network access
+    ///
+    /// Requires a previous online installation of the same version to have cached
+    /// the release manifest and all installers.
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Install even if the cache or destination volume has not enough free space
+    ///
+    /// Without it the sizes of all missing components are checked against the free
+    /// space of both volumes before anything is downloaded.
+    #[arg(long)]
+    pub force: bool,
 
-    /// The Unity version to install in the form of `2018.1.0f3`
-    pub editor_version: Version,
+    #[command(flatten)]
+    pub eula: EulaArgs,
+
+    /// Print the install plan without downloading or installing anything
+    ///
+    /// Lists every component with its install status, sizes and destination as well as
+    /// the EULAs of the missing components. Use `--output json` for a structured plan.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Install from a bundle created with `uvm bundle`
+    ///
+    /// Implies `--offline`. The bundle must contain the requested version.
+    #[arg(long, value_name = "PATH")]
+    pub from_bundle: Option<PathBuf>,
+
+    /// Install the editor version pinned by the Unity project at PATH
+    ///
+    /// Defaults to the current directory. The release must match the revision in
+    /// `m_EditorVersionWithRevision`. Modules and build targets listed in the
+    /// project's `.uvm.toml` are installed in addition to `--module`.
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = ".", conflicts_with = "editor_version")]
+    pub project: Option<PathBuf>,
+
+    /// Only consider releases of these streams when resolving a version requirement
+    #[arg(short, long = "stream", value_enum)]
+    pub streams: Vec<UnityReleaseStream>,
+
+    /// Only consider releases with these entitlements when resolving a version requirement
+    #[arg(short, long = "entitlement", value_enum)]
+    pub entitlements: Vec<UnityReleaseEntitlement>,
+
+    /// The Unity version to install
+    ///
+    /// Either an exact version like `2018.1.0f3` or a requirement like `2022.3`,
+    /// `^2022.3`, `2023.2.x` or `latest-lts`, which is resolved to the newest
+    /// matching release.
+    #[arg(required_unless_present = "project")]
+    pub editor_version: Option<VersionRequirement>,
 
     /// A directory to install the requested version to
     pub destination: Option<PathBuf>,
@@ -42,92 +139,178 @@ pub struct InstallArgs {
 impl Command for InstallArgs {
     fn execute(&self) -> io::Result<i32> {
         let start_time = Instant::now();
-        let version = &self.editor_version;
-        let modules = &self.modules;
-        let install_sync = self.sync;
-        let destination = &self.destination;
+        let modules = self.modules.clone().unwrap_or_default();
 
-        let mut options = InstallOptions::new(version.to_owned())
-            .with_install_sync(install_sync)
-            .with_architecture(self.architecture);
-
-        if let Some(modules) = modules {
-            options = options.with_requested_modules(modules);
+        let mut options = match (&self.project, &self.editor_version) {
+            (Some(project), _) => {
+                let mut requirements = ProjectRequirements::detect(project, &DetectOptions::new())
+                    .inspect_err(|e| {
+                        eprintln!("{}: {}", style("Error").red().bold(), e);
+                    })?;
+                eprintln!(
+                    "{} {} pins Unity {}",
+                    style("Project").bold(),
+                    project.display(),
+                    requirements.version_display()
+                );
+                requirements.modules.extend(modules);
+                if self.architecture.is_some() {
+                    requirements.architecture = self.architecture;
+                }
+                requirements.install_options()
+            }
+            (None, Some(version)) => InstallOptions::new(version.to_owned())
+                .with_release_streams(self.streams.iter().copied())
+                .with_entitlements(self.entitlements.iter().copied())
+                .with_requested_modules(modules)
+                .with_architecture(self.architecture.unwrap_or_default()),
+            (None, None) => unreachable!("clap requires a version or a project"),
         }
+        .with_install_sync(self.sync)
+        .with_max_parallel_downloads(self.max_parallel_downloads as usize)
+        .with_offline(self.offline)
+        .with_force(self.force)
+        .with_eula_acceptance(self.eula.acceptance());
 
-        if let Some(destination) = destination {
+        if let Some(destination) = &self.destination {
             options = options.with_destination(destination);
         }
 
-        // Detect interactive mode and create appropriate progress handler
-        let interactive = is_interactive();
-        let progress_mode = crate::commands::progress::get_progress_mode();
-
-        let coordinator_opt = if interactive {
-            // Create a multi-progress coordinator for component installation hierarchy
-            // We start with 0 components - the library will update the count after building the graph
-            use crate::commands::progress::MultiProgressCoordinator;
-            use std::sync::Arc;
-            let coordinator = Arc::new(MultiProgressCoordinator::new(0));
-            options = options.with_progress_handler(ArcProgressCoordinator(coordinator.clone()));
-            Some(coordinator)
-        } else if progress_mode != crate::commands::progress::ProgressMode::Disabled {
-            // Non-interactive mode - use simple milestone messages (unless --no-progress)
-            let simple_handler = SimpleProgressHandler::new("Unity".to_string());
-            options = options.with_progress_handler(simple_handler);
-            None
-        } else {
-            // --no-progress: no handler at all
-            None
-        };
-
-        match options.install() {
-            Ok(installation) => {
-                let elapsed = start_time.elapsed();
-
-                // Clear progress bars before showing summary
-                if let Some(ref coordinator) = coordinator_opt {
-                    coordinator.clear();
-                }
+        if let Some(bundle) = &self.from_bundle {
+            options = options.with_bundle(bundle);
+        }
 
-                // Show installation summary
-                if let Some(ref coordinator) = coordinator_opt {
-                    let components = coordinator.components_installed();
-                    let bytes = coordinator.bytes_downloaded();
-                    eprintln!(
-                        "\n{} {} ({}) in {}",
-                        style("Installed").green().bold(),
-                        if components == 1 { "1 component".to_string() } else { format!("{} components", components) },
-                        HumanBytes(bytes),
-                        HumanDuration(elapsed),
-                    );
-                } else {
-                    eprintln!(
-                        "\n{} in {}",
-                        style("Installed").green().bold(),
-                        HumanDuration(elapsed),
-                    );
-                }
+        if self.dry_run {
+            return print_plan(&options);
+        }
+
+        run_install(options, start_time)
+    }
+}
+
+/// Asks whether to accept an EULA of a module to install which is not accepted yet.
+fn prompt_eula(component: &PlannedComponent, eula: &PlannedEula) -> bool {
+    global_multi_progress().suspend(|| {
+        eprintln!(
+            "\n{} {} ({})",
+            style("EULA").bold(),
+            eula.label,
+            style(&component.id).cyan()
+        );
+        eprintln!("  {}", eula.message);
+        eprintln!("  {}", style(&eula.url).underlined());
+        eprint!("Accept? [y/N] ");
+        match Term::stderr().read_line() {
+            Ok(answer) => matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"),
+            Err(e) => {
+                debug!("unable to read the EULA answer: {}", e);
+                false
+            }
+        }
+    })
+}
+
+fn print_plan(options: &InstallOptions) -> io::Result<i32> {
+    let plan = options.plan().map_err(|e| {
+        eprintln!("{}: {}", style("Error").red().bold(), e);
+        io::Error::other(format!("Planning the installation failed: {}", e))
+    })?;
+
+    if output_format() == OutputFormat::Json {
+        print_json(&plan)?;
+    } else {
+        let renderer = TextRenderer::new(RenderOptions {
+            verbose: true,
+            ..Default::default()
+        });
+        print!("{}", renderer.render_view(&plan));
+    }
+    Ok(0)
+}
+
+/// Runs the installation with progress reporting and prints a summary.
+pub fn run_install(mut options: InstallOptions, start_time: Instant) -> io::Result<i32> {
+    if io::stdin().is_terminal() && *options.eula_acceptance() != EulaAcceptance::All {
+        options = options.with_eula_prompt(prompt_eula);
+    }
+
+    // Detect interactive mode and create appropriate progress handler
+    let interactive = is_interactive();
+    let progress_mode = crate::commands::progress::get_progress_mode();
+
+    let coordinator_opt = if interactive {
+        // Create a multi-progress coordinator for component installation hierarchy
+        // We start with 0 components - the library will update the count after building the graph
+        use crate::commands::progress::MultiProgressCoordinator;
+        use std::sync::Arc;
+        let coordinator = Arc::new(MultiProgressCoordinator::new(0));
+        options = options.with_progress_handler(ArcProgressCoordinator(coordinator.clone()));
+        Some(coordinator)
+    } else if progress_mode != crate::commands::progress::ProgressMode::Disabled {
+        // Non-interactive mode - use simple milestone messages (unless --no-progress)
+        let simple_handler = SimpleProgressHandler::new("Unity".to_string());
+        options = options.with_progress_handler(simple_handler);
+        None
+    } else {
+        // --no-progress: no handler at all
+        None
+    };
+
+    match options.install() {
+        Ok(installation) => {
+            let elapsed = start_time.elapsed();
+
+            // Clear progress bars before showing summary
+            if let Some(ref coordinator) = coordinator_opt {
+                coordinator.clear();
+            }
+
+            // Show installation summary
+            if let Some(ref coordinator) = coordinator_opt {
+                let components = coordinator.components_installed();
+                let bytes = coordinator.bytes_downloaded();
                 eprintln!(
-                    "  Unity {} → {}",
-                    installation.version(),
-                    installation.path().display(),
+                    "\n{} {} ({}) in {}",
+                    style("Installed").green().bold(),
+                    if components == 1 { "1 component".to_string() } else { format!("{} components", components) },
+                    HumanBytes(bytes),
+                    HumanDuration(elapsed),
                 );
+            } else {
+                eprintln!(
+                    "\n{} in {}",
+                    style("Installed").green().bold(),
+                    HumanDuration(elapsed),
+                );
+            }
+            eprintln!(
+                "  Unity {} → {}",
+                installation.version(),
+                installation.path().display(),
+            );
 
-                Ok(0)
+            Ok(0)
+        }
+        Err(e) => {
+            // Clear progress display before showing error
+            if let Some(ref coordinator) = coordinator_opt {
+                coordinator.clear();
             }
-            Err(e) => {
-                // Clear progress display before showing error
-                if let Some(ref coordinator) = coordinator_opt {
-                    coordinator.clear();
-                }
 
-                eprintln!("{}: {}", style("Error").red().bold(), e);
-                Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    format!("Installation failed: {}", e),
-                ))
+            eprintln!("{}: {}", style("Error").red().bold(), e);
+            match e {
+                InstallError::InsufficientDiskSpace { .. } => {
+                    eprintln!("  free up space or pass `--force` to install anyway")
+                }
+                InstallError::EulaNotAccepted(_) => {
+                    eprintln!("  accept them with `--accept-eula` or `--accept-eula=<module>`")
+                }
+                _ => {}
             }
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("Installation failed: {}", e),
+            ))
         }
     }
 }
@@ -1,14 +1,18 @@
 use clap::{Args, ValueEnum};
 use console::style;
-use log::info;
+use log::{debug, info, warn};
 use std::env;
 use std::fmt;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::process;
-use unity_hub::unity::{find_installation, list_all_installations, UnityInstallation};
+use unity_hub::error::UnityHubError;
+use unity_hub::unity::{
+    find_default_installation, find_installation, list_all_installations, UnityInstallation,
+};
 use uvm_detect::detect_project_version;
 use uvm_detect::DetectOptions;
+use uvm_install::VersionRequirement;
 
 use crate::commands::Command;
 
@@ -66,9 +70,16 @@ pub struct LaunchCommand {
     recursive: bool,
 
     /// Will launch try to launch the project with the Unity version the project was created from.
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "unity_version")]
     force_project_version: bool,
 
+    /// Launch with the newest installed Unity version matching this version or requirement
+    ///
+    /// Accepts an exact version like `2022.3.10f1` or a requirement like `2022.3`,
+    /// `^2022.3`, `2023.2.x` or `latest-lts`.
+    #[arg(short, long, value_name = "VERSION")]
+    unity_version: Option<VersionRequirement>,
+
     /// Path to the Unity Project
     project_path: Option<PathBuf>,
 }
@@ -132,9 +143,49 @@ impl LaunchCommand {
             return Ok(installation);
         }
 
-        // For current installation, we'll use the first available installation
-        // This is a simplified approach - in a real implementation you might want to
-        // check for a specific "current" installation marker
+        if let Some(requirement) = &self.unity_version {
+            let installations: Vec<UnityInstallation> = list_all_installations()
+                .map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("Failed to list installations: {}", e),
+                    )
+                })?
+                .collect();
+            let version = requirement
+                .best_match(installations.iter().map(|i| i.version()))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!(
+                            "No installed Unity version matches {}, install one with `uvm install {}`",
+                            requirement, requirement
+                        ),
+                    )
+                })?;
+            info!("resolved {} to installed version {}", requirement, version);
+            let installation = installations
+                .into_iter()
+                .find(|installation| installation.version() == &version)
+                .expect("the best match is one of the installations");
+            return Ok(installation);
+        }
+
+        // Prefer the default editor selected with `uvm use` or in Unity Hub. Hub keeps the
+        // default of editors which have been uninstalled since, so any failure falls back to
+        // the first installation.
+        match find_default_installation() {
+            Ok(Some(installation)) => {
+                info!("use default editor {}", installation.version());
+                return Ok(installation);
+            }
+            Ok(None) => debug!("no default editor set"),
+            Err(UnityHubError::ConfigDirectoryNotFound) => {
+                debug!("no Unity Hub config directory, no default editor set")
+            }
+            Err(e) => warn!("ignore the default editor: {}", e),
+        }
+
         let installations = list_all_installations().map_err(|e| {
             io::Error::new(
                 io::ErrorKind::NotFound,
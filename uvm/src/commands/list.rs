@@ -1,4 +1,10 @@
-use crate::commands::{presentation::{as_view_iter, RenderOptions, TextRenderer}, Command};
+use crate::commands::{
+    presentation::{
+        as_view_iter, output_format, print_json, InstallationView, OutputFormat, RenderOptions,
+        TextRenderer,
+    },
+    Command,
+};
 use clap::Args;
 use log::info;
 use std::io;
@@ -49,6 +55,11 @@ impl Command for ListCommand {
         };
 
         match list_function() {
+            Ok(installations) if output_format() == OutputFormat::Json => {
+                let views: Vec<InstallationView> =
+                    installations.map(|i| InstallationView::from(&i)).collect();
+                print_json(&views)?;
+            }
             Ok(installations) => {
                 eprintln!("Installed Unity versions:");
                 let items: Vec<_> = installations.collect();
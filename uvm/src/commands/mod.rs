@@ -1,6 +1,9 @@
 use std::io;
 
+pub mod bundle;
 pub mod detect;
+pub mod diff;
+pub mod doctor;
 #[cfg(feature = "dev-commands")]
 pub mod download_modules_json;
 pub mod external;
@@ -11,7 +14,15 @@ pub mod list;
 pub mod modules;
 pub mod presentation;
 pub mod progress;
+pub mod project;
+pub mod release_notes;
+pub mod releases;
+pub mod repair;
+pub mod requirements;
+pub mod sync;
 pub mod uninstall;
+pub mod update;
+pub mod use_version;
 pub mod version;
 
 pub trait Command {
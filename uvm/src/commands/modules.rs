@@ -1,5 +1,6 @@
 use crate::commands::presentation::{
-    as_view_iter, CategoryView, ModuleView, RenderOptions, TextRenderer,
+    as_view_iter, output_format, print_json, CategoryView, ModuleView, OutputFormat,
+    RenderOptions, TextRenderer,
 };
 use anyhow::Result;
 use clap::Args;
@@ -8,6 +9,7 @@ use std::{
     collections::{HashMap, HashSet},
     io,
 };
+use unity_hub::unity::{find_installation, Installation};
 use unity_version::Version;
 use uvm_install::InstallArchitecture;
 use uvm_live_platform::{FetchRelease, Module, UnityReleaseDownloadPlatform};
@@ -53,7 +55,12 @@ fn load_modules<V: AsRef<Version>>(
         .with_extended_lts()
         .with_u7_alpha()
         .fetch()
-        .map_err(|_e| io::Error::new(io::ErrorKind::NotFound, "failed to load release"))?;
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("failed to load release: {}", e),
+            )
+        })?;
 
     let downloads = release.downloads.iter();
 
@@ -95,6 +102,15 @@ impl ModulesCommand {
 
     fn list(&self) -> Result<()> {
         let modules = load_modules(&self.version, self.show_sync_modules)?;
+        let installed_modules: HashSet<String> = find_installation(&self.version)
+            .ok()
+            .and_then(|installation| {
+                installation
+                    .installed_modules()
+                    .map(|modules| modules.into_iter().map(|m| m.id().to_string()).collect())
+                    .ok()
+            })
+            .unwrap_or_default();
         let modules = modules
             .iter()
             .filter(|m| self.all || !m.module.hidden())
@@ -110,6 +126,10 @@ impl ModulesCommand {
                     let module_view = ModuleView {
                         id: m.module.id().to_string(),
                         description: m.module.description().to_string(),
+                        category: m.module.category().to_string(),
+                        download_size: m.module.download_size.to_bytes() as u64,
+                        installed_size: m.module.installed_size.to_bytes() as u64,
+                        installed: installed_modules.contains(m.module.id()),
                         platform_architectures: vec![format!("{}/{}", m.platform, m.architecture)],
                     };
                     acc.insert(
@@ -154,6 +174,15 @@ impl ModulesCommand {
             .map(|(category, modules)| CategoryView { category, modules })
             .collect();
 
+        if output_format() == OutputFormat::Json {
+            let modules: Vec<&ModuleView> = category_views
+                .iter()
+                .flat_map(|category| category.modules.iter())
+                .collect();
+            print_json(&modules)?;
+            return Ok(());
+        }
+
         if category_views.is_empty() {
             if self.current {
                 eprintln!("No modules found for the current platform and architecture");
@@ -187,6 +216,7 @@ mod tests {
             id: id.to_string(),
             description: description.to_string(),
             platform_architectures: vec!["PLATFORM".to_string()],
+            ..Default::default()
         }
     }
 
@@ -1,15 +1,72 @@
 use console::Style;
+use indicatif::HumanBytes;
+use serde::Serialize;
 use std::io::{self, Write};
+use std::sync::Mutex;
 use unity_hub::unity::hub::module::Module as HubModule;
 use unity_hub::unity::{Installation, UnityInstallation};
+use uvm_install::InstallPlan;
+
+use crate::commands::diff::ReleaseDiff;
+use crate::commands::release_notes::{plain_text, ReleaseNotes};
+
+/// Output format of the query commands (`list`, `modules`, `detect`, `version`,
+/// `release-notes`, `diff`) and of `install --dry-run`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable text
+    #[default]
+    Text,
+    /// Stable JSON printed to stdout
+    Json,
+}
+
+static OUTPUT_FORMAT: Mutex<OutputFormat> = Mutex::new(OutputFormat::Text);
+
+/// Configure the output format based on the --output flag
+pub fn set_output_format(format: OutputFormat) {
+    *OUTPUT_FORMAT.lock().unwrap() = format;
+}
+
+/// Get the current output format
+pub fn output_format() -> OutputFormat {
+    *OUTPUT_FORMAT.lock().unwrap()
+}
+
+/// Prints `value` as pretty JSON followed by a newline to stdout.
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, value)?;
+    writeln!(stdout)
+}
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
 pub struct ModuleView {
     pub id: String,
     pub description: String,
+    pub category: String,
+    /// Download size in bytes
+    pub download_size: u64,
+    /// Size on disk after installation in bytes
+    pub installed_size: u64,
+    pub installed: bool,
     pub platform_architectures: Vec<String>,
 }
 
+impl From<&HubModule> for ModuleView {
+    fn from(module: &HubModule) -> Self {
+        Self {
+            id: module.id().to_string(),
+            description: module.base.description().to_string(),
+            category: module.base.category().to_string(),
+            download_size: module.base.download_size.to_bytes() as u64,
+            installed_size: module.base.installed_size.to_bytes() as u64,
+            installed: module.is_installed,
+            platform_architectures: vec![],
+        }
+    }
+}
+
 impl View for ModuleView {
     fn render(&self, w: &mut dyn Write, opts: &RenderOptions) -> io::Result<()> {
         if opts.verbose {
@@ -40,14 +97,29 @@ impl View for ModuleView {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct InstallationView {
     pub version: String,
     pub path: String,
+    pub architecture: Option<String>,
     pub modules: Vec<ModuleView>,
 }
 
+impl From<&UnityInstallation> for InstallationView {
+    fn from(installation: &UnityInstallation) -> Self {
+        Self {
+            version: installation.version().to_string(),
+            path: installation.path().display().to_string(),
+            architecture: uvm_install::installation_architecture(installation)
+                .map(|architecture| architecture.to_string()),
+            modules: installation
+                .installed_modules()
+                .map(|modules| modules.into_iter().map(|m| ModuleView::from(&m)).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
 impl View for InstallationView {
     fn render(&self, w: &mut dyn Write, opts: &RenderOptions) -> io::Result<()> {
         let out_style = Style::new().cyan();
@@ -79,7 +151,7 @@ impl View for InstallationView {
 
 // Using ModuleView from presentation.rs module
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct CategoryView {
     pub category: String,
     pub modules: Vec<ModuleView>,
@@ -104,6 +176,173 @@ impl View for CategoryView {
     }
 }
 
+impl View for InstallPlan {
+    fn render(&self, w: &mut dyn Write, opts: &RenderOptions) -> io::Result<()> {
+        writeln!(
+            w,
+            "Unity {} ({}, {}) → {}",
+            maybe_style(&self.version, Style::new().cyan(), opts.no_color),
+            self.revision,
+            self.architecture,
+            maybe_style(
+                self.destination.display(),
+                Style::new().italic().green(),
+                opts.no_color
+            )
+        )?;
+        for component in &self.components {
+            if component.is_missing() {
+                write!(
+                    w,
+                    "  + {} ({} download, {} installed)",
+                    maybe_style(&component.id, Style::new().yellow(), opts.no_color),
+                    HumanBytes(component.download_size),
+                    HumanBytes(component.installed_size)
+                )?;
+            } else {
+                write!(
+                    w,
+                    "  = {} (installed)",
+                    maybe_style(&component.id, Style::new().dim(), opts.no_color)
+                )?;
+            }
+            if opts.verbose {
+                write!(w, " - {}", component.description)?;
+                if let Some(destination) = &component.destination {
+                    write!(w, " → {}", destination.display())?;
+                }
+            }
+            writeln!(w)?;
+        }
+
+        if self.is_up_to_date() {
+            writeln!(w, "Nothing to install")?;
+        } else {
+            writeln!(
+                w,
+                "Download {}, install {}",
+                HumanBytes(self.download_size()),
+                HumanBytes(self.installed_size())
+            )?;
+        }
+
+        let mut eulas = self.eulas().filter(|(_, eula)| !eula.accepted).peekable();
+        if eulas.peek().is_some() {
+            writeln!(w, "EULAs to accept:")?;
+            for (component, eula) in eulas {
+                writeln!(w, "  {}: {} ({})", component.id, eula.label, eula.url)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl View for ReleaseNotes {
+    fn render(&self, w: &mut dyn Write, opts: &RenderOptions) -> io::Result<()> {
+        writeln!(
+            w,
+            "Unity {} ({}) {}",
+            maybe_style(&self.version, Style::new().cyan().bold(), opts.no_color),
+            self.revision,
+            maybe_style(&self.release_date, Style::new().dim(), opts.no_color)
+        )?;
+        for section in &self.sections {
+            if !section.title.is_empty() {
+                writeln!(
+                    w,
+                    "  {}",
+                    maybe_style(&section.title, Style::new().bold(), opts.no_color)
+                )?;
+            }
+            for entry in &section.entries {
+                let mut lines = entry.lines();
+                if let Some(line) = lines.next() {
+                    writeln!(w, "    • {}", plain_text(line))?;
+                }
+                for line in lines {
+                    writeln!(w, "      - {}", plain_text(line))?;
+                }
+            }
+        }
+        writeln!(w)
+    }
+}
+
+impl View for ReleaseDiff {
+    fn render(&self, w: &mut dyn Write, opts: &RenderOptions) -> io::Result<()> {
+        writeln!(
+            w,
+            "Unity {} → {} ({})",
+            maybe_style(&self.from, Style::new().cyan(), opts.no_color),
+            maybe_style(&self.to, Style::new().cyan(), opts.no_color),
+            self.stream
+        )?;
+
+        writeln!(w, "\n{}", maybe_style("Modules", Style::new().bold(), opts.no_color))?;
+        if self.modules.is_empty() {
+            writeln!(w, "  No module changes")?;
+        }
+        for module in &self.modules.added {
+            writeln!(
+                w,
+                "  + {} - {} ({} download, {} installed)",
+                maybe_style(&module.id, Style::new().green(), opts.no_color),
+                module.description,
+                HumanBytes(module.download_size),
+                HumanBytes(module.installed_size)
+            )?;
+        }
+        for module in &self.modules.removed {
+            writeln!(
+                w,
+                "  - {} - {}",
+                maybe_style(&module.id, Style::new().red(), opts.no_color),
+                module.description
+            )?;
+        }
+        for module in &self.modules.changed {
+            let mut changes = Vec::new();
+            if let Some(size) = &module.download_size {
+                changes.push(format!(
+                    "download {} → {}",
+                    HumanBytes(size.from),
+                    HumanBytes(size.to)
+                ));
+            }
+            if let Some(size) = &module.installed_size {
+                changes.push(format!(
+                    "installed {} → {}",
+                    HumanBytes(size.from),
+                    HumanBytes(size.to)
+                ));
+            }
+            if let Some(destination) = &module.destination {
+                changes.push(format!(
+                    "destination {} → {}",
+                    destination.from.as_deref().unwrap_or("-"),
+                    destination.to.as_deref().unwrap_or("-")
+                ));
+            }
+            writeln!(
+                w,
+                "  ~ {}: {}",
+                maybe_style(&module.id, Style::new().yellow(), opts.no_color),
+                changes.join(", ")
+            )?;
+        }
+
+        if !self.release_notes.is_empty() {
+            writeln!(
+                w,
+                "\n{}\n",
+                maybe_style("Release notes", Style::new().bold(), opts.no_color)
+            )?;
+            as_view_iter(&self.release_notes).render(w, opts)?;
+        }
+        Ok(())
+    }
+}
+
 // Helper function to conditionally apply style based on no_color option
 fn maybe_style<T: std::fmt::Display>(value: T, style: Style, no_color: bool) -> String {
     if no_color {
@@ -239,6 +478,7 @@ mod tests {
         InstallationView {
             version: v.to_string(),
             path: p.to_string(),
+            architecture: None,
             modules: vec![],
         }
     }
@@ -292,7 +532,7 @@ mod tests {
         it.modules = vec![ModuleView {
             id: "android".into(),
             description: "Android Build Support".into(),
-            platform_architectures: vec![],
+            ..Default::default()
         }];
         let s = TextRenderer::new(RenderOptions {
             list_modules: true,
@@ -311,7 +551,7 @@ mod tests {
         it.modules = vec![ModuleView {
             id: "ios".into(),
             description: "iOS Build Support".into(),
-            platform_architectures: vec![],
+            ..Default::default()
         }];
         let s = TextRenderer::new(RenderOptions {
             list_modules: true,
@@ -322,4 +562,135 @@ mod tests {
         .render_to_string(vec![it]);
         assert!(s.contains("  * ios - iOS Build Support"));
     }
+
+    #[test]
+    fn installation_view_serializes_stable_fields() {
+        let mut it = item("2021.3.1f1", "/path");
+        it.architecture = Some("x86_64".into());
+        it.modules = vec![ModuleView {
+            id: "android".into(),
+            category: "Platforms".into(),
+            download_size: 10,
+            installed_size: 20,
+            installed: true,
+            ..Default::default()
+        }];
+        let json = serde_json::to_value(&it).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": "2021.3.1f1",
+                "path": "/path",
+                "architecture": "x86_64",
+                "modules": [{
+                    "id": "android",
+                    "description": "",
+                    "category": "Platforms",
+                    "download_size": 10,
+                    "installed_size": 20,
+                    "installed": true,
+                    "platform_architectures": []
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn render_install_plan_marks_missing_components() {
+        use std::path::PathBuf;
+        use uvm_install::{ComponentStatus, PlannedComponent};
+
+        let component = |id: &str, status| PlannedComponent {
+            id: id.to_string(),
+            description: format!("{} description", id),
+            status,
+            download_size: 1000,
+            installed_size: 2000,
+            destination: None,
+            eulas: vec![],
+        };
+        let plan = InstallPlan {
+            version: "2022.3.0f1".into(),
+            revision: "abc123".into(),
+            architecture: "x86_64".into(),
+            destination: PathBuf::from("/opt/unity"),
+            components: vec![
+                component("Unity", ComponentStatus::Installed),
+                component("ios", ComponentStatus::Missing),
+            ],
+        };
+        let s = TextRenderer::new(RenderOptions {
+            no_color: true,
+            ..Default::default()
+        })
+        .render_view(&plan);
+        assert!(s.contains("Unity 2022.3.0f1 (abc123, x86_64) → /opt/unity"));
+        assert!(s.contains("  = Unity (installed)"));
+        assert!(s.contains("  + ios (1000 B download, 1.95 KiB installed)"));
+        assert!(!s.contains("ios description"));
+        assert!(!s.contains("EULAs to accept"));
+    }
+
+    #[test]
+    fn render_release_notes_as_plain_text() {
+        use crate::commands::release_notes::parse_sections;
+
+        let notes = ReleaseNotes {
+            version: "2022.3.10f1".into(),
+            revision: "ff3792e53c62".into(),
+            release_date: "2023-09-26".into(),
+            url: "https://example.com/2022.3.10f1.md".into(),
+            sections: parse_sections(
+                "### Fixes\n\n* Editor: Fixed `Undo` ([UUM-1](https://example.com/1))\n  * Also on Linux\n",
+            ),
+        };
+        let s = TextRenderer::new(RenderOptions {
+            no_color: true,
+            ..Default::default()
+        })
+        .render_view(&notes);
+        assert_eq!(
+            s,
+            "Unity 2022.3.10f1 (ff3792e53c62) 2023-09-26\n  Fixes\n    • Editor: Fixed Undo (UUM-1)\n      - Also on Linux\n\n"
+        );
+    }
+
+    #[test]
+    fn render_release_diff_lists_module_changes() {
+        use crate::commands::diff::{Change, ModuleChange, ModuleDiff, ModuleSummary};
+
+        let diff = ReleaseDiff {
+            from: "2022.3.10f1".into(),
+            to: "2022.3.20f1".into(),
+            stream: "LTS".into(),
+            release_notes: vec![],
+            modules: ModuleDiff {
+                added: vec![ModuleSummary {
+                    id: "linux-il2cpp".into(),
+                    description: "Linux Build Support (IL2CPP)".into(),
+                    download_size: 1000,
+                    installed_size: 2000,
+                }],
+                removed: vec![],
+                changed: vec![ModuleChange {
+                    id: "android".into(),
+                    download_size: Some(Change { from: 1000, to: 2048 }),
+                    installed_size: None,
+                    destination: None,
+                }],
+            },
+        };
+        let s = TextRenderer::new(RenderOptions {
+            no_color: true,
+            ..Default::default()
+        })
+        .render_view(&diff);
+        assert!(s.starts_with("Unity 2022.3.10f1 → 2022.3.20f1 (LTS)\n"));
+        assert!(s.contains(
+            "  + linux-il2cpp - Linux Build Support (IL2CPP) (1000 B download, 1.95 KiB installed)"
+        ));
+        assert!(s.contains("  ~ android: download 1000 B → 2.00 KiB"));
+        assert!(!s.contains("No module changes"));
+        assert!(!s.contains("Release notes"));
+    }
 }
@@ -0,0 +1,167 @@
+use clap::{Args, Subcommand};
+use console::style;
+use log::debug;
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::time::Instant;
+use unity_hub::unity::{find_installation, Installation};
+use uvm_detect::DetectOptions;
+use uvm_install::{installation_architecture, InstallOptions};
+
+use crate::commands::install::{run_install, EulaArgs};
+use crate::commands::releases::{fetch_minor_line_releases, newest_upgrade};
+use crate::commands::Command as CommandTrait;
+
+#[derive(Args, Debug)]
+pub struct ProjectCommand {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Upgrade the editor pinned by a project to the newest release of its minor line
+    ///
+    /// Installs the newest release with the modules installed for the current editor.
+    /// Stable projects are only upgraded to final and patch releases.
+    Upgrade(UpgradeArgs),
+}
+
+#[derive(Args, Debug)]
+struct UpgradeArgs {
+    /// Detects a Unity project recursively from current working or <project-path> directory.
+    #[arg(short, long)]
+    recursive: bool,
+
+    /// Rewrite `m_EditorVersion` and `m_EditorVersionWithRevision` of the project
+    #[arg(short, long)]
+    write: bool,
+
+    /// Report the upgrade plan without installing or writing anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Install also synced modules
+    ///
+    /// Synced modules are optional dependencies of some Unity modules.
+    /// e.g. Android SDK for the android module.
+    #[arg(long = "with-sync")]
+    sync: bool,
+
+    /// Install even if the cache or destination volume has not enough free space
+    #[arg(long)]
+    force: bool,
+
+    #[command(flatten)]
+    eula: EulaArgs,
+
+    /// Path to the Unity project (defaults to the current directory)
+    project_path: Option<PathBuf>,
+}
+
+impl CommandTrait for ProjectCommand {
+    fn execute(&self) -> io::Result<i32> {
+        match &self.command {
+            Command::Upgrade(args) => args.execute(),
+        }
+    }
+}
+
+impl UpgradeArgs {
+    fn execute(&self) -> io::Result<i32> {
+        let start_time = Instant::now();
+        let project_path = match &self.project_path {
+            Some(path) => path.to_path_buf(),
+            None => env::current_dir()?,
+        };
+
+        let mut detect_options = DetectOptions::new();
+        detect_options.recursive(self.recursive);
+        let (project_dir, current) = detect_options
+            .detect_unity_project_dir(&project_path)
+            .and_then(|dir| {
+                let version = detect_options.detect_project_version(&dir)?;
+                Ok((dir, version))
+            })
+            .inspect_err(|e| {
+                eprintln!("{}: {}", style("Error").red().bold(), e);
+            })?;
+
+        let candidates = fetch_minor_line_releases(&current).inspect_err(|e| {
+            eprintln!("{}: {}", style("Error").red().bold(), e);
+        })?;
+        let target = match newest_upgrade(&current, candidates) {
+            Some(target) => target,
+            None => {
+                eprintln!(
+                    "{} {} is on the newest release of Unity {}.{}: {}",
+                    style("Up to date").green().bold(),
+                    project_dir.display(),
+                    current.major(),
+                    current.minor(),
+                    current
+                );
+                return Ok(0);
+            }
+        };
+
+        let installation = find_installation(&current)
+            .inspect_err(|e| debug!("Unity {} is not installed: {}", current, e))
+            .ok();
+        let modules: Vec<String> = match &installation {
+            Some(installation) => installation
+                .installed_modules()
+                .map_err(|e| io::Error::other(e.to_string()))?
+                .into_iter()
+                .map(|module| module.id().to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        eprintln!("{} {}", style("Project").bold(), project_dir.display());
+        eprintln!(
+            "  upgrade: {} → {}",
+            current,
+            style(target.to_string()).cyan()
+        );
+        if !modules.is_empty() {
+            eprintln!("  modules: {}", modules.join(", "));
+        }
+        if self.write {
+            eprintln!("  rewrite: ProjectSettings/ProjectVersion.txt");
+        }
+
+        if self.dry_run {
+            eprintln!("\n{}", style("Dry run, nothing installed").yellow());
+            return Ok(0);
+        }
+
+        let options = InstallOptions::new(target.version().to_owned())
+            .with_revision(target.revision().to_owned())
+            .with_requested_modules(modules)
+            .with_architecture(
+                installation
+                    .as_ref()
+                    .and_then(installation_architecture)
+                    .unwrap_or_default(),
+            )
+            .with_install_sync(self.sync)
+            .with_force(self.force)
+            .with_eula_acceptance(self.eula.acceptance());
+        run_install(options, start_time)?;
+
+        if self.write {
+            uvm_detect::write_project_version(&project_dir, &target).inspect_err(|e| {
+                eprintln!("{}: {}", style("Error").red().bold(), e);
+            })?;
+            eprintln!(
+                "{} {} to Unity {}",
+                style("Pinned").green().bold(),
+                project_dir.display(),
+                target
+            );
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,454 @@
+use clap::Args;
+use console::style;
+use log::info;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::io;
+use std::str::FromStr;
+use unity_version::{CompleteVersion, ReleaseType, Version};
+use uvm_live_platform::{
+    fetch_release, fetch_release_notes, ListVersions, Release, UnityReleaseStream,
+};
+
+use crate::commands::presentation::{
+    as_view_iter, output_format, print_json, OutputFormat, RenderOptions, TextRenderer,
+};
+use crate::commands::Command;
+
+#[derive(Args, Debug)]
+pub struct ReleaseNotesCommand {
+    /// Show the notes of every release after this version up to VERSION
+    ///
+    /// Pre-releases are only included if VERSION is a pre-release itself.
+    #[arg(long, value_name = "VERSION")]
+    pub since: Option<Version>,
+
+    /// Only show entries containing one of these keywords (case-insensitive)
+    ///
+    /// Entries of a section whose title matches, e.g. `Fixes`, are shown as well.
+    #[arg(short = 'g', long = "grep", value_name = "KEYWORD")]
+    pub keywords: Vec<String>,
+
+    /// Print the release notes as markdown instead of terminal text
+    #[arg(long)]
+    pub markdown: bool,
+
+    /// The Unity version to show the release notes for
+    pub version: Version,
+}
+
+impl Command for ReleaseNotesCommand {
+    fn execute(&self) -> io::Result<i32> {
+        let versions = match &self.since {
+            Some(since) => {
+                if since >= &self.version {
+                    eprintln!(
+                        "{}: --since {} must be older than {}",
+                        style("Error").red().bold(),
+                        since,
+                        self.version
+                    );
+                    return Ok(1);
+                }
+                let versions = releases_between(since, &self.version, None)?;
+                if versions.is_empty() {
+                    eprintln!("No releases after {} up to {}", since, self.version);
+                    return Ok(0);
+                }
+                versions
+            }
+            None => vec![self.version.clone()],
+        };
+
+        let mut failed = 0;
+        let mut notes = Vec::new();
+        for version in versions {
+            match load_release_notes(&version) {
+                Ok(release_notes) => notes.push(release_notes.filter(&self.keywords)),
+                Err(e) => {
+                    eprintln!(
+                        "{}: release notes of {}: {}",
+                        style("Error").red().bold(),
+                        version,
+                        e
+                    );
+                    failed += 1;
+                }
+            }
+        }
+        if !self.keywords.is_empty() {
+            notes.retain(|release_notes| !release_notes.sections.is_empty());
+            if notes.is_empty() && failed == 0 {
+                eprintln!("No release notes match {}", self.keywords.join(", "));
+            }
+        }
+
+        if output_format() == OutputFormat::Json {
+            print_json(&notes)?;
+        } else if self.markdown {
+            for release_notes in &notes {
+                print!("{}", release_notes.to_markdown());
+            }
+        } else {
+            let renderer = TextRenderer::new(RenderOptions::default());
+            print!("{}", renderer.render_view(&as_view_iter(&notes)));
+        }
+        Ok(if failed == 0 { 0 } else { 1 })
+    }
+}
+
+/// The release notes of a Unity release split into sections.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub revision: String,
+    pub release_date: String,
+    pub url: String,
+    pub sections: Vec<ReleaseNotesSection>,
+}
+
+/// A headline of the release notes with the entries listed below it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseNotesSection {
+    pub title: String,
+    #[serde(skip)]
+    pub level: usize,
+    /// Markdown of the entries. Nested list items are kept as separate lines.
+    pub entries: Vec<String>,
+}
+
+impl ReleaseNotes {
+    /// Keeps the entries containing one of `keywords` and drops sections without entries.
+    pub fn filter(mut self, keywords: &[String]) -> Self {
+        let keywords: Vec<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
+        if !keywords.is_empty() {
+            for section in &mut self.sections {
+                let title = section.title.to_lowercase();
+                if keywords.iter().any(|keyword| title.contains(keyword)) {
+                    continue;
+                }
+                section.entries.retain(|entry| {
+                    let entry = entry.to_lowercase();
+                    keywords.iter().any(|keyword| entry.contains(keyword))
+                });
+            }
+        }
+        self.sections.retain(|section| !section.entries.is_empty());
+        self
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Unity {} ({})\n", self.version, self.revision);
+        for section in &self.sections {
+            let _ = write!(
+                markdown,
+                "\n{} {}\n\n",
+                "#".repeat(section.level.clamp(2, 6)),
+                section.title
+            );
+            for entry in &section.entries {
+                let _ = writeln!(markdown, "* {}", entry.replace('\n', "\n  "));
+            }
+        }
+        markdown.push('\n');
+        markdown
+    }
+}
+
+/// Splits a markdown release notes document into its sections.
+pub fn parse_sections(markdown: &str) -> Vec<ReleaseNotesSection> {
+    let mut sections: Vec<ReleaseNotesSection> = Vec::new();
+    // whether the next text line continues the last entry
+    let mut in_entry = false;
+    for line in markdown.lines() {
+        let text = line.trim();
+        if text.is_empty() {
+            in_entry = false;
+            continue;
+        }
+        if let Some((level, title)) = heading(text) {
+            sections.push(ReleaseNotesSection {
+                title: title.to_string(),
+                level,
+                entries: Vec::new(),
+            });
+            in_entry = false;
+            continue;
+        }
+        if sections.is_empty() {
+            sections.push(ReleaseNotesSection {
+                title: String::new(),
+                level: 2,
+                entries: Vec::new(),
+            });
+        }
+        let entries = &mut sections.last_mut().expect("a section").entries;
+        let nested = line.starts_with(char::is_whitespace);
+        match (list_item(text), entries.last_mut()) {
+            (Some(item), Some(entry)) if nested => {
+                entry.push('\n');
+                entry.push_str(item);
+            }
+            (Some(item), _) => entries.push(item.to_string()),
+            (None, Some(entry)) if in_entry => {
+                entry.push(' ');
+                entry.push_str(text);
+            }
+            (None, _) => entries.push(text.to_string()),
+        }
+        in_entry = true;
+    }
+    sections
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let title = &line[level..];
+    if !title.is_empty() && !title.starts_with(' ') {
+        return None;
+    }
+    Some((level, title.trim().trim_end_matches('#').trim_end()))
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    ["* ", "- ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+        .map(str::trim_start)
+}
+
+/// Strips the inline markdown of release notes for terminal output: links are replaced by
+/// their text, emphasis, code spans and escapes are removed.
+pub fn plain_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    let mut chars = markdown.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    text.push(escaped);
+                }
+            }
+            '*' | '`' => {}
+            '_' if chars.peek() == Some(&'_') => {
+                chars.next();
+            }
+            '(' if text.ends_with(']') => {
+                // drop the target of a link, `[text](url)`
+                for c in chars.by_ref() {
+                    if c == ')' {
+                        break;
+                    }
+                }
+                text.pop();
+                if let Some(start) = text.rfind('[') {
+                    text.remove(start);
+                }
+            }
+            _ => text.push(c),
+        }
+    }
+    text
+}
+
+fn load_release_notes(version: &Version) -> io::Result<ReleaseNotes> {
+    info!("fetch release notes of Unity {}", version);
+    let release = fetch_release(version.clone()).map_err(|e| io::Error::other(e.to_string()))?;
+    release_notes_of(release)
+}
+
+/// Downloads and parses the release notes of `release`.
+pub fn release_notes_of(release: Release) -> io::Result<ReleaseNotes> {
+    let markdown =
+        fetch_release_notes(&release.release_notes).map_err(|e| io::Error::other(e.to_string()))?;
+    Ok(ReleaseNotes {
+        version: release.version,
+        revision: release.short_revision,
+        release_date: release.release_date,
+        url: release.release_notes.url,
+        sections: parse_sections(&markdown),
+    })
+}
+
+/// Lists the releases after `since` up to and including `version`, oldest first.
+///
+/// Only releases of `stream` are listed if given.
+pub fn releases_between(
+    since: &Version,
+    version: &Version,
+    stream: Option<UnityReleaseStream>,
+) -> io::Result<Vec<Version>> {
+    let mut builder = ListVersions::builder()
+        .with_current_platform()
+        .with_extended_lts()
+        .with_u7_alpha()
+        .include_revision(true)
+        .autopage(true);
+    if since.major() == version.major() {
+        builder = builder.with_version(version.major().to_string());
+    }
+    if let Some(stream) = stream {
+        builder = builder.with_stream(stream);
+    }
+    let releases = builder
+        .list()
+        .map_err(|e| io::Error::other(e.to_string()))?
+        .filter_map(|release| CompleteVersion::from_str(&release).ok())
+        .map(|release| release.version().clone());
+    Ok(select_releases(since, version, releases))
+}
+
+fn select_releases<I: IntoIterator<Item = Version>>(
+    since: &Version,
+    version: &Version,
+    releases: I,
+) -> Vec<Version> {
+    let include_pre_releases = is_pre_release(version);
+    let mut releases: Vec<Version> = releases
+        .into_iter()
+        .filter(|release| release > since && release <= version)
+        .filter(|release| include_pre_releases || !is_pre_release(release))
+        .collect();
+    releases.sort();
+    releases.dedup();
+    releases
+}
+
+fn is_pre_release(version: &Version) -> bool {
+    matches!(
+        version.release_type(),
+        ReleaseType::Alpha | ReleaseType::Beta | ReleaseType::Experimental
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTES: &str = "\
+## 2022.3.10f1 Release Notes
+
+### Known Issues
+
+*   Android: Crash when building with `IL2CPP` ([UUM-1234](https://issuetracker.unity3d.com/issues/1234))
+
+### Fixes
+
+*   Android: Fixed the \\_Main\\_ activity
+    not starting.
+*   Editor: Fixed a crash on **startup**.
+    *   Also on Linux.
+*   iOS: Fixed signing.
+";
+
+    fn release_notes() -> ReleaseNotes {
+        ReleaseNotes {
+            version: "2022.3.10f1".to_string(),
+            revision: "ff3792e53c62".to_string(),
+            release_date: "2023-09-26".to_string(),
+            url: "https://example.com/2022.3.10f1.md".to_string(),
+            sections: parse_sections(NOTES),
+        }
+    }
+
+    fn versions(versions: &[&str]) -> Vec<Version> {
+        versions
+            .iter()
+            .map(|version| Version::from_str(version).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn parses_sections_and_entries() {
+        let sections = parse_sections(NOTES);
+        let titles: Vec<&str> = sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["2022.3.10f1 Release Notes", "Known Issues", "Fixes"]
+        );
+        assert!(sections[0].entries.is_empty());
+        assert_eq!(sections[2].level, 3);
+        assert_eq!(
+            sections[2].entries,
+            vec![
+                "Android: Fixed the \\_Main\\_ activity not starting.",
+                "Editor: Fixed a crash on **startup**.\nAlso on Linux.",
+                "iOS: Fixed signing.",
+            ]
+        );
+    }
+
+    #[test]
+    fn filters_entries_by_keyword() {
+        let notes = release_notes().filter(&["ANDROID".to_string()]);
+        assert_eq!(notes.sections.len(), 2);
+        assert_eq!(notes.sections[0].entries.len(), 1);
+        assert_eq!(
+            notes.sections[1].entries,
+            vec!["Android: Fixed the \\_Main\\_ activity not starting."]
+        );
+
+        let notes = release_notes().filter(&["fixes".to_string()]);
+        assert_eq!(notes.sections.len(), 1);
+        assert_eq!(notes.sections[0].entries.len(), 3);
+
+        assert!(release_notes()
+            .filter(&["webgl".to_string()])
+            .sections
+            .is_empty());
+    }
+
+    #[test]
+    fn strips_inline_markdown() {
+        assert_eq!(
+            plain_text(
+                "Crash with `IL2CPP` ([UUM-1234](https://issuetracker.unity3d.com/issues/1234))"
+            ),
+            "Crash with IL2CPP (UUM-1234)"
+        );
+        assert_eq!(
+            plain_text("Fixed the \\_Main\\_ activity on **startup**"),
+            "Fixed the _Main_ activity on startup"
+        );
+        assert_eq!(plain_text("Array [0] (first)"), "Array [0] (first)");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = release_notes()
+            .filter(&["editor".to_string()])
+            .to_markdown();
+        assert_eq!(
+            markdown,
+            "# Unity 2022.3.10f1 (ff3792e53c62)\n\n### Fixes\n\n\
+             * Editor: Fixed a crash on **startup**.\n  Also on Linux.\n\n"
+        );
+    }
+
+    #[test]
+    fn selects_releases_between_versions() {
+        let since = Version::from_str("2022.3.10f1").unwrap();
+        let version = Version::from_str("2022.3.12f1").unwrap();
+        let releases = versions(&[
+            "2022.3.12f1",
+            "2022.3.9f1",
+            "2022.3.10f1",
+            "2022.3.11f1",
+            "2022.3.13f1",
+            "2022.3.11b1",
+        ]);
+        assert_eq!(
+            select_releases(&since, &version, releases.clone()),
+            versions(&["2022.3.11f1", "2022.3.12f1"])
+        );
+
+        let version = Version::from_str("2022.3.12b1").unwrap();
+        assert_eq!(
+            select_releases(&since, &version, releases),
+            versions(&["2022.3.11b1", "2022.3.11f1"])
+        );
+    }
+}
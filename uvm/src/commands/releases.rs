@@ -0,0 +1,86 @@
+use log::info;
+use std::io;
+use std::str::FromStr;
+use unity_version::{CompleteVersion, Version, VersionReq};
+use uvm_live_platform::ListVersions;
+
+/// Lists the releases of the minor line of `version` with their revision hashes.
+pub fn fetch_minor_line_releases(version: &Version) -> io::Result<Vec<CompleteVersion>> {
+    let minor_line = format!("{}.{}", version.major(), version.minor());
+    info!("fetch releases of Unity {}", minor_line);
+    let versions = ListVersions::builder()
+        .with_current_platform()
+        .with_version(minor_line)
+        .include_revision(true)
+        .autopage(true)
+        .list()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    Ok(versions
+        .filter_map(|version| CompleteVersion::from_str(&version).ok())
+        .collect())
+}
+
+/// Returns the newest of `candidates` which is a newer release of the minor line of `current`.
+///
+/// Pre-releases are only considered for projects pinned to a pre-release.
+pub fn newest_upgrade<I: IntoIterator<Item = CompleteVersion>>(
+    current: &Version,
+    candidates: I,
+) -> Option<CompleteVersion> {
+    let requirement = VersionReq::from_str(&format!(
+        "~{}{}{}",
+        current.base(),
+        current.release_type(),
+        current.revision()
+    ))
+    .ok()?;
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.version() > current)
+        .filter(|candidate| requirement.matches(candidate.version()))
+        .max_by(|a, b| a.version().cmp(b.version()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(versions: &[&str]) -> Vec<CompleteVersion> {
+        versions
+            .iter()
+            .map(|version| {
+                CompleteVersion::from_str(&format!("{} (0da89fac8e79)", version)).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn newest_upgrade_stays_in_minor_line() {
+        let current = Version::from_str("2022.3.10f1").unwrap();
+        let releases = candidates(&[
+            "2022.3.5f1",
+            "2022.3.20f1",
+            "2022.3.21f1",
+            "2023.1.0f1",
+            "2022.3.22b1",
+        ]);
+        let target = newest_upgrade(&current, releases).unwrap();
+        assert_eq!(target.version().to_string(), "2022.3.21f1");
+    }
+
+    #[test]
+    fn newest_upgrade_returns_none_when_up_to_date() {
+        let current = Version::from_str("2022.3.21f1").unwrap();
+        assert_eq!(
+            newest_upgrade(&current, candidates(&["2022.3.20f1", "2022.3.21f1"])),
+            None
+        );
+    }
+
+    #[test]
+    fn newest_upgrade_of_pre_release_includes_pre_releases() {
+        let current = Version::from_str("2023.3.0b1").unwrap();
+        let target = newest_upgrade(&current, candidates(&["2023.3.0b4", "2023.3.0a18"])).unwrap();
+        assert_eq!(target.version().to_string(), "2023.3.0b4");
+    }
+}
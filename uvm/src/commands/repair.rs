@@ -0,0 +1,106 @@
+use clap::Args;
+use console::style;
+use log::debug;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use unity_hub::unity::hub::paths::install_path;
+use unity_hub::unity::{list_all_installations, UnityInstallation};
+use unity_version::Version;
+use uvm_install::{rollback, JOURNAL_FILE_NAME};
+
+use crate::commands::Command;
+
+#[derive(Args, Debug)]
+pub struct RepairCommand {
+    /// The Unity version to repair
+    ///
+    /// Repairs every installation with an unfinished install journal if omitted.
+    pub version: Option<Version>,
+}
+
+impl Command for RepairCommand {
+    fn execute(&self) -> io::Result<i32> {
+        let mut failed = 0;
+        let mut repaired = 0;
+        for dir in self.journaled_installations() {
+            match rollback(&dir) {
+                Ok(components) if components.is_empty() => {
+                    debug!("nothing to repair at {}", dir.display());
+                }
+                Ok(components) => {
+                    repaired += 1;
+                    eprintln!("{} {}", style("Repaired").green().bold(), dir.display());
+                    for component in components {
+                        match component.removed {
+                            Some(removed) => eprintln!(
+                                "  rolled back: {} (removed {})",
+                                component.component,
+                                removed.display()
+                            ),
+                            None => eprintln!("  rolled back: {}", component.component),
+                        }
+                    }
+                    if let Some(version) = installation_version(&dir) {
+                        eprintln!("  run `uvm install {}` to install it again", version);
+                    }
+                }
+                Err(e) => {
+                    eprintln!(
+                        "{}: failed to repair {}: {}",
+                        style("Error").red().bold(),
+                        dir.display(),
+                        e
+                    );
+                    failed += 1;
+                }
+            }
+        }
+
+        if repaired == 0 && failed == 0 {
+            eprintln!("{}", style("No interrupted installations found").green());
+        }
+        Ok(if failed == 0 { 0 } else { 1 })
+    }
+}
+
+impl RepairCommand {
+    /// Installations with an install journal. Interrupted editor installations are often not
+    /// detected as installations, so the install path is scanned as well.
+    fn journaled_installations(&self) -> BTreeSet<PathBuf> {
+        let mut dirs = BTreeSet::new();
+        match list_all_installations() {
+            Ok(installations) => {
+                dirs.extend(installations.map(|installation| installation.path().to_path_buf()))
+            }
+            Err(e) => debug!("unable to list installations: {}", e),
+        }
+        if let Some(entries) = install_path().and_then(|path| fs::read_dir(path).ok()) {
+            dirs.extend(
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path()),
+            );
+        }
+
+        dirs.into_iter()
+            .filter(|dir| dir.join(JOURNAL_FILE_NAME).exists())
+            .filter(|dir| match &self.version {
+                Some(version) => installation_version(dir).as_ref() == Some(version),
+                None => true,
+            })
+            .collect()
+    }
+}
+
+fn installation_version(dir: &Path) -> Option<Version> {
+    UnityInstallation::new(dir)
+        .map(|installation| installation.version().to_owned())
+        .ok()
+        .or_else(|| {
+            dir.file_name()
+                .and_then(|name| Version::from_str(&name.to_string_lossy()).ok())
+        })
+}
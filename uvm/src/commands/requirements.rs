@@ -0,0 +1,225 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use unity_version::{RevisionHash, Version};
+use uvm_detect::{DetectOptions, ProjectArchitecture, ProjectManifest};
+use uvm_install::{InstallArchitecture, InstallOptions};
+
+/// The editor, modules and architecture a Unity project needs.
+///
+/// The editor version is read from `ProjectSettings/ProjectVersion.txt` unless the project
+/// manifest (`.uvm.toml`) overrides it. Build targets listed in the manifest are translated
+/// to the modules providing them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectRequirements {
+    pub project_dir: PathBuf,
+    pub manifest_path: Option<PathBuf>,
+    pub version: Version,
+    pub revision: Option<RevisionHash>,
+    pub architecture: Option<InstallArchitecture>,
+    pub modules: Vec<String>,
+}
+
+impl ProjectRequirements {
+    pub fn detect(path: &Path, options: &DetectOptions) -> io::Result<Self> {
+        let project_dir = options.detect_unity_project_dir(path)?;
+        let manifest_path = ProjectManifest::find(&project_dir);
+        let manifest = manifest_path
+            .as_deref()
+            .map(ProjectManifest::load)
+            .transpose()?
+            .unwrap_or_default();
+
+        let (version, revision) = match manifest.version {
+            Some(version) => (version, manifest.revision),
+            None => {
+                let complete_version = options
+                    .detect_project_complete_version(&project_dir)
+                    .map_err(|e| {
+                        io::Error::new(
+                            e.kind(),
+                            format!(
+                                "Unable to read the pinned editor version of {}: {}",
+                                project_dir.display(),
+                                e
+                            ),
+                        )
+                    })?;
+                (
+                    complete_version.version().to_owned(),
+                    Some(complete_version.revision().to_owned()),
+                )
+            }
+        };
+
+        let mut modules: Vec<String> = Vec::new();
+        let build_target_modules = manifest
+            .build_targets
+            .iter()
+            .map(|target| build_target_modules(target))
+            .collect::<io::Result<Vec<_>>>()?;
+        for module in manifest.modules.into_iter().chain(
+            build_target_modules
+                .into_iter()
+                .flatten()
+                .map(|m| m.to_string()),
+        ) {
+            if !modules.contains(&module) {
+                modules.push(module);
+            }
+        }
+
+        Ok(Self {
+            project_dir,
+            manifest_path,
+            version,
+            revision,
+            architecture: manifest
+                .architecture
+                .map(|architecture| match architecture {
+                    ProjectArchitecture::X86_64 => InstallArchitecture::X86_64,
+                    ProjectArchitecture::Arm64 => InstallArchitecture::Arm64,
+                }),
+            modules,
+        })
+    }
+
+    /// Install options for the pinned editor with the required modules.
+    pub fn install_options(&self) -> InstallOptions {
+        let mut options = InstallOptions::new(self.version.to_owned())
+            .with_requested_modules(&self.modules)
+            .with_architecture(self.architecture.unwrap_or_default());
+        if let Some(revision) = &self.revision {
+            options = options.with_revision(revision.to_owned());
+        }
+        options
+    }
+
+    /// The pinned editor version, including the revision when known.
+    pub fn version_display(&self) -> String {
+        match &self.revision {
+            Some(revision) => format!("{} ({})", self.version, revision),
+            None => self.version.to_string(),
+        }
+    }
+}
+
+/// Returns the modules which add support for the Unity build target `target`.
+///
+/// The standalone target of the host platform ships with the editor and needs no module.
+fn build_target_modules(target: &str) -> io::Result<&'static [&'static str]> {
+    let normalized: String = target
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let modules: &'static [&'static str] = match normalized.as_str() {
+        "android" => &["android"],
+        "ios" => &["ios"],
+        "tvos" | "appletv" => &["appletv"],
+        "visionos" => &["visionos"],
+        "webgl" => &["webgl"],
+        "uwp" | "wsaplayer" | "universalwindowsplatform" => &["universal-windows-platform"],
+        "windows" | "win" | "win64" | "standalonewindows" | "standalonewindows64" => {
+            if cfg!(target_os = "windows") {
+                &[]
+            } else {
+                &["windows-mono"]
+            }
+        }
+        "mac" | "osx" | "macos" | "standaloneosx" => {
+            if cfg!(target_os = "macos") {
+                &[]
+            } else {
+                &["mac-mono"]
+            }
+        }
+        "linux" | "linux64" | "standalonelinux64" => {
+            if cfg!(target_os = "linux") {
+                &[]
+            } else {
+                &["linux-mono"]
+            }
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Unknown build target '{}'", target),
+            ))
+        }
+    };
+    Ok(modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_unity_project(dir: &Path, version_content: &str) {
+        let project_settings = dir.join("ProjectSettings");
+        fs::create_dir_all(&project_settings).unwrap();
+        fs::write(project_settings.join("ProjectVersion.txt"), version_content).unwrap();
+    }
+
+    #[test]
+    fn test_build_target_modules() {
+        assert_eq!(build_target_modules("Android").unwrap(), &["android"]);
+        assert_eq!(build_target_modules("iOS").unwrap(), &["ios"]);
+        assert_eq!(
+            build_target_modules("WSAPlayer").unwrap(),
+            &["universal-windows-platform"]
+        );
+        assert!(build_target_modules("Dreamcast").is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_host_build_target_needs_no_module() {
+        assert!(build_target_modules("StandaloneLinux64")
+            .unwrap()
+            .is_empty());
+        assert_eq!(build_target_modules("Win64").unwrap(), &["windows-mono"]);
+    }
+
+    #[test]
+    fn test_detect_uses_project_version_and_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unity_project(
+            temp_dir.path(),
+            "m_EditorVersionWithRevision: 2021.3.55f1 (f87d5274e360)",
+        );
+        fs::write(
+            temp_dir.path().join(".uvm.toml"),
+            "modules = [\"android\"]\nbuild-targets = [\"Android\", \"WebGL\"]\narchitecture = \"arm64\"",
+        )
+        .unwrap();
+
+        let requirements =
+            ProjectRequirements::detect(temp_dir.path(), &DetectOptions::new()).unwrap();
+        assert_eq!(requirements.version_display(), "2021.3.55f1 (f87d5274e360)");
+        assert_eq!(requirements.modules, vec!["android", "webgl"]);
+        assert_eq!(requirements.architecture, Some(InstallArchitecture::Arm64));
+        assert_eq!(
+            requirements.manifest_path,
+            Some(temp_dir.path().join(".uvm.toml"))
+        );
+    }
+
+    #[test]
+    fn test_detect_prefers_manifest_version() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unity_project(temp_dir.path(), "m_EditorVersion: 2021.3.55f1");
+        fs::write(
+            temp_dir.path().join(".uvm.toml"),
+            "version = \"2022.3.10f1\"",
+        )
+        .unwrap();
+
+        let requirements =
+            ProjectRequirements::detect(temp_dir.path(), &DetectOptions::new()).unwrap();
+        assert_eq!(requirements.version.to_string(), "2022.3.10f1");
+        assert_eq!(requirements.revision, None);
+        assert!(requirements.modules.is_empty());
+    }
+}
@@ -0,0 +1,163 @@
+use clap::Args;
+use console::style;
+use std::collections::HashSet;
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::time::Instant;
+use unity_hub::unity::{find_installation, Installation};
+use uvm_detect::DetectOptions;
+use uvm_install::{cached_release_revision, installation_architecture};
+
+use crate::commands::install::{run_install, EulaArgs};
+use crate::commands::requirements::ProjectRequirements;
+use crate::commands::Command;
+
+#[derive(Args, Debug)]
+pub struct SyncCommand {
+    /// Detects a Unity project recursively from current working or <project-path> directory.
+    #[arg(short, long)]
+    pub recursive: bool,
+
+    /// Install also synced modules
+    ///
+    /// Synced modules are optional dependencies of some Unity modules.
+    /// e.g. Android SDK for the android module.
+    #[arg(long = "with-sync")]
+    pub sync: bool,
+
+    /// Install from the local installer cache without network access
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Install even if the cache or destination volume has not enough free space
+    #[arg(long)]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub eula: EulaArgs,
+
+    /// Path to the Unity project (defaults to the current directory)
+    pub project_path: Option<PathBuf>,
+}
+
+impl Command for SyncCommand {
+    fn execute(&self) -> io::Result<i32> {
+        let start_time = Instant::now();
+        let project_path = match &self.project_path {
+            Some(path) => path.to_path_buf(),
+            None => env::current_dir()?,
+        };
+
+        let mut detect_options = DetectOptions::new();
+        detect_options.recursive(self.recursive);
+        let requirements = ProjectRequirements::detect(&project_path, &detect_options)
+            .inspect_err(|e| {
+                eprintln!("{}: {}", style("Error").red().bold(), e);
+            })?;
+
+        eprintln!(
+            "{} {} requires Unity {}",
+            style("Project").bold(),
+            requirements.project_dir.display(),
+            style(requirements.version_display()).cyan()
+        );
+        if let Some(manifest_path) = &requirements.manifest_path {
+            eprintln!("  manifest: {}", style(manifest_path.display()).dim());
+        }
+        if let Some(architecture) = requirements.architecture {
+            eprintln!("  architecture: {}", architecture);
+        }
+        if !requirements.modules.is_empty() {
+            eprintln!("  modules: {}", requirements.modules.join(", "));
+        }
+
+        let mut options = requirements
+            .install_options()
+            .with_install_sync(self.sync)
+            .with_offline(self.offline)
+            .with_force(self.force)
+            .with_eula_acceptance(self.eula.acceptance());
+
+        if let Ok(installation) = find_installation(&requirements.version) {
+            let installed: HashSet<String> = installation
+                .installed_modules()
+                .map_err(|e| io::Error::other(e.to_string()))?
+                .into_iter()
+                .map(|module| module.id().to_string())
+                .collect();
+
+            let missing: Vec<&String> = requirements
+                .modules
+                .iter()
+                .filter(|module| !installed.contains(*module))
+                .collect();
+            let mut additional: Vec<&String> = installed
+                .iter()
+                .filter(|module| !requirements.modules.contains(module))
+                .collect();
+            additional.sort();
+
+            if !additional.is_empty() {
+                eprintln!(
+                    "  {}",
+                    style(format!(
+                        "not required by the project (kept): {}",
+                        additional
+                            .iter()
+                            .map(|m| m.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                    .dim()
+                );
+            }
+
+            let architecture_mismatch = requirements
+                .architecture
+                .zip(installation_architecture(&installation))
+                .filter(|(required, installed)| required != installed);
+            if let Some((required, installed)) = architecture_mismatch {
+                eprintln!(
+                    "  {}",
+                    style(format!(
+                        "installed architecture {} doesn't match {}",
+                        installed, required
+                    ))
+                    .yellow()
+                );
+            }
+            let revision_mismatch = requirements.revision.as_ref().and_then(|revision| {
+                cached_release_revision(
+                    &requirements.version,
+                    requirements.architecture.unwrap_or_default(),
+                )
+                .filter(|installed| installed != revision.as_str())
+            });
+            if let Some(installed) = &revision_mismatch {
+                eprintln!(
+                    "  {}",
+                    style(format!("installed revision {} doesn't match", installed)).yellow()
+                );
+            }
+
+            if missing.is_empty()
+                && !self.sync
+                && architecture_mismatch.is_none()
+                && revision_mismatch.is_none()
+            {
+                eprintln!(
+                    "\n{} Unity {} → {}",
+                    style("Up to date").green().bold(),
+                    installation.version(),
+                    installation.path().display()
+                );
+                return Ok(0);
+            }
+
+            options = options.with_destination(installation.path());
+        }
+
+        run_install(options, start_time)
+    }
+}
@@ -251,23 +251,7 @@ impl UninstallArgs {
     }
 
     fn get_module_install_path(&self, module: &Module, installation: &UnityInstallation) -> Option<PathBuf> {
-        if let Some(destination) = module.base.destination() {
-            if destination.is_empty() {
-                return None;
-            }
-            
-            // Replace {UNITY_PATH} placeholder with the actual installation path
-            let resolved_destination = if destination.contains("{UNITY_PATH}") {
-                destination.replace("{UNITY_PATH}", installation.path().to_string_lossy().as_ref())
-            } else {
-                // If no placeholder, treat destination as relative to installation path
-                return Some(installation.path().join(destination));
-            };
-            
-            Some(PathBuf::from(resolved_destination))
-        } else {
-            None
-        }
+        module.install_path(installation.path())
     }
 
 }
@@ -0,0 +1,204 @@
+use clap::Args;
+use console::style;
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use std::fs::remove_dir_all;
+use std::io;
+use std::time::Instant;
+use unity_hub::unity::doctor::diagnose;
+use unity_hub::unity::hub::default_editor::{default_editor, set_default_editor};
+use unity_hub::unity::{
+    find_installation, list_all_installations, Installation, UnityInstallation,
+};
+use unity_version::Version;
+use uvm_install::{installation_architecture, InstallOptions};
+
+use crate::commands::install::{run_install, EulaArgs};
+use crate::commands::releases::{fetch_minor_line_releases, newest_upgrade};
+use crate::commands::Command;
+
+#[derive(Args, Debug)]
+pub struct UpdateCommand {
+    /// Uninstall the old editor once the new one is installed and verified
+    ///
+    /// The Unity Hub default editor moves to the new editor.
+    #[arg(long)]
+    pub remove_old: bool,
+
+    /// Report the updates without installing or removing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Install also synced modules
+    ///
+    /// Synced modules are optional dependencies of some Unity modules.
+    /// e.g. Android SDK for the android module.
+    #[arg(long = "with-sync")]
+    pub sync: bool,
+
+    /// Install even if the cache or destination volume has not enough free space
+    #[arg(long)]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub eula: EulaArgs,
+
+    /// The installed Unity version to update
+    ///
+    /// Updates the newest installed editor of every minor line if omitted.
+    pub version: Option<Version>,
+}
+
+impl Command for UpdateCommand {
+    fn execute(&self) -> io::Result<i32> {
+        let installations = self.installations_to_update().inspect_err(|e| {
+            eprintln!("{}: {}", style("Error").red().bold(), e);
+        })?;
+
+        let mut failed = 0;
+        for installation in installations {
+            if let Err(e) = self.update(&installation) {
+                eprintln!(
+                    "{}: failed to update Unity {}: {}",
+                    style("Error").red().bold(),
+                    installation.version(),
+                    e
+                );
+                failed += 1;
+            }
+        }
+        Ok(if failed == 0 { 0 } else { 1 })
+    }
+}
+
+impl UpdateCommand {
+    fn installations_to_update(&self) -> io::Result<Vec<UnityInstallation>> {
+        if let Some(version) = &self.version {
+            let installation = find_installation(version).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Unity {} is not installed", version),
+                )
+            })?;
+            return Ok(vec![installation]);
+        }
+
+        // Only the newest editor of a minor line is updated, older ones would update to the
+        // same release.
+        let mut newest: HashMap<(u64, u64), UnityInstallation> = HashMap::new();
+        for installation in list_all_installations().map_err(|e| io::Error::other(e.to_string()))? {
+            let version = installation.version();
+            let line = (version.major(), version.minor());
+            match newest.get(&line) {
+                Some(other) if other.version() >= version => {
+                    debug!("skip Unity {}, {} is newer", version, other.version())
+                }
+                _ => {
+                    newest.insert(line, installation);
+                }
+            }
+        }
+        let mut installations: Vec<UnityInstallation> = newest.into_values().collect();
+        installations.sort_by(|a, b| a.version().cmp(b.version()));
+        Ok(installations)
+    }
+
+    fn update(&self, installation: &UnityInstallation) -> io::Result<()> {
+        let start_time = Instant::now();
+        let current = installation.version();
+        let candidates = fetch_minor_line_releases(current)?;
+        let target = match newest_upgrade(current, candidates) {
+            Some(target) => target,
+            None => {
+                eprintln!(
+                    "{} Unity {} → {}",
+                    style("Up to date").green().bold(),
+                    current,
+                    installation.path().display()
+                );
+                return Ok(());
+            }
+        };
+
+        let modules: Vec<String> = installation
+            .installed_modules()
+            .map_err(|e| io::Error::other(e.to_string()))?
+            .into_iter()
+            .map(|module| module.id().to_string())
+            .collect();
+
+        eprintln!(
+            "{} Unity {} → {}",
+            style("Update").bold(),
+            current,
+            style(target.version()).cyan()
+        );
+        if !modules.is_empty() {
+            eprintln!("  modules: {}", modules.join(", "));
+        }
+        if self.remove_old {
+            eprintln!("  remove: {}", installation.path().display());
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let options = InstallOptions::new(target.version().to_owned())
+            .with_revision(target.revision().to_owned())
+            .with_requested_modules(modules)
+            .with_architecture(installation_architecture(installation).unwrap_or_default())
+            .with_install_sync(self.sync)
+            .with_force(self.force)
+            .with_eula_acceptance(self.eula.acceptance());
+        run_install(options, start_time)?;
+
+        if self.remove_old {
+            let updated =
+                find_installation(target.version()).map_err(|e| io::Error::other(e.to_string()))?;
+            self.remove_old_installation(installation, &updated)?;
+        }
+        Ok(())
+    }
+
+    fn remove_old_installation(
+        &self,
+        old: &UnityInstallation,
+        updated: &UnityInstallation,
+    ) -> io::Result<()> {
+        let diagnosis = diagnose(updated);
+        if !diagnosis.is_healthy() {
+            for issue in &diagnosis.issues {
+                warn!("Unity {}: {}", updated.version(), issue);
+            }
+            eprintln!(
+                "{} Unity {} failed verification, kept Unity {} (see `uvm doctor {}`)",
+                style("Warning").yellow().bold(),
+                updated.version(),
+                old.version(),
+                updated.version()
+            );
+            return Ok(());
+        }
+
+        if default_editor().ok().flatten().as_ref() == Some(old.version()) {
+            info!("move default editor to Unity {}", updated.version());
+            set_default_editor(updated.version()).map_err(|e| io::Error::other(e.to_string()))?;
+        }
+
+        debug!("Removing Unity editor at {}", old.path().display());
+        remove_dir_all(old.path()).map_err(|e| {
+            io::Error::other(format!(
+                "Failed to remove Unity installation at {}: {}",
+                old.path().display(),
+                e
+            ))
+        })?;
+        eprintln!(
+            "{} Unity {} → {}",
+            style("Removed").green().bold(),
+            old.version(),
+            old.path().display()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use clap::Args;
+use console::style;
+use std::io;
+use unity_hub::unity::hub::default_editor::{default_editor, set_default_editor};
+use unity_hub::unity::find_installation;
+use unity_version::Version;
+
+use crate::commands::Command;
+
+#[derive(Args, Debug)]
+pub struct UseCommand {
+    /// The installed Unity version to use as default editor. Prints the current default if omitted.
+    pub version: Option<Version>,
+}
+
+impl Command for UseCommand {
+    fn execute(&self) -> io::Result<i32> {
+        let version = match &self.version {
+            Some(version) => version,
+            None => return self.print_default_editor(),
+        };
+
+        let installation = match find_installation(version) {
+            Ok(installation) => installation,
+            Err(_) => {
+                eprintln!(
+                    "{}: Unity {} is not installed, install it with `uvm install {}`",
+                    style("Error").red().bold(),
+                    version,
+                    version
+                );
+                return Ok(1);
+            }
+        };
+
+        set_default_editor(installation.version())
+            .map_err(|e| io::Error::other(e.to_string()))
+            .inspect_err(|e| {
+                eprintln!("{}: {}", style("Error").red().bold(), e);
+            })?;
+
+        eprintln!(
+            "{} Unity {} → {}",
+            style("Using").green().bold(),
+            installation.version(),
+            installation.path().display()
+        );
+        Ok(0)
+    }
+}
+
+impl UseCommand {
+    fn print_default_editor(&self) -> io::Result<i32> {
+        match default_editor().map_err(|e| io::Error::other(e.to_string()))? {
+            Some(version) => {
+                println!("{}", version);
+                Ok(0)
+            }
+            None => {
+                eprintln!("No default editor set");
+                Ok(1)
+            }
+        }
+    }
+}
@@ -4,14 +4,15 @@ use console::style;
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
 use log::{debug, info};
-use semver::VersionReq;
+use serde::Serialize;
 use std::io;
 use std::str::FromStr;
 use std::time::Duration;
-use unity_version::Version;
+use unity_version::{Version, VersionReq};
 use uvm_live_platform::UnityReleaseDownloadArchitecture;
 use uvm_live_platform::UnityReleaseDownloadPlatform;
 use uvm_live_platform::{UnityReleaseEntitlement, UnityReleaseStream};
+use crate::commands::presentation::{output_format, print_json, OutputFormat};
 use crate::commands::Command as CommandTrait;
 #[derive(Args, Debug)]
 pub struct VersionCommand {
@@ -25,8 +26,9 @@ enum Command {
     Matching {
         /// The version requirement string
         ///
-        /// The version requirement string will be converted to `semver::VersionReq`
-        /// See https://docs.rs/semver/1.0.2/semver/struct.VersionReq.html for usage.
+        /// A comma separated list of comparators which understand Unity release types and
+        /// revisions, e.g. `>=2022.3.10f1, <2023`, `2022.3.*f` or `>=2023.1, final-only`.
+        /// Alpha and beta releases only match if the requirement names a pre-release type.
         version_req: VersionReq,
 
         #[command(flatten)]
@@ -109,7 +111,7 @@ impl CommandTrait for VersionCommand {
                     )
                 })?;
                 pb.finish_with_message("latest version");
-                println!("{}", style(version).green().bold());
+                Self::print_version(&version)?;
             }
             Command::Matching {
                 version_req,
@@ -137,13 +139,18 @@ impl CommandTrait for VersionCommand {
                     let versions = Self::fetch_matching_versions(versions, version_req.clone());
                     pb.finish_with_message("all matching versions");
                     info!("all matching versions:");
-                    for version in versions {
-                        println!("{}", style(version).green().bold());
+                    if output_format() == OutputFormat::Json {
+                        let versions: Vec<String> = versions.map(|v| v.to_string()).collect();
+                        print_json(&VersionsView { versions })?;
+                    } else {
+                        for version in versions {
+                            println!("{}", style(version).green().bold());
+                        }
                     }
                 } else {
                     let version = Self::fetch_matching_version(versions, version_req.clone())?;
                     pb.finish_with_message("highest matching version");
-                    println!("{}", style(version).green().bold());
+                    Self::print_version(&version)?;
                 }
             }
         }
@@ -152,21 +159,34 @@ impl CommandTrait for VersionCommand {
     }
 }
 
+#[derive(Serialize)]
+struct VersionView {
+    version: String,
+}
+
+#[derive(Serialize)]
+struct VersionsView {
+    versions: Vec<String>,
+}
+
 impl VersionCommand {
+    fn print_version<V: std::fmt::Display>(version: &V) -> io::Result<()> {
+        if output_format() == OutputFormat::Json {
+            print_json(&VersionView {
+                version: version.to_string(),
+            })
+        } else {
+            println!("{}", style(version).green().bold());
+            Ok(())
+        }
+    }
+
     fn fetch_matching_versions<I: Iterator<Item = Version>>(
         versions: I,
         version_req: VersionReq,
     ) -> impl Iterator<Item = Version> {
         versions.filter(move |version| {
-            let b = version.base().clone();
-            debug!(
-                "use base semver version {} of {} for comparison",
-                b, version
-            );
-            let semver_version = b;
-
-            let is_match = &version_req.matches(&semver_version);
-            if *is_match {
+            if version_req.matches(version) {
                 info!("version {} is a match", version);
                 true
             } else {
@@ -1,6 +1,9 @@
 mod commands;
 
+use crate::commands::bundle::BundleCommand;
 use crate::commands::detect::DetectCommand;
+use crate::commands::diff::DiffCommand;
+use crate::commands::doctor::DoctorCommand;
 #[cfg(feature = "dev-commands")]
 use crate::commands::download_modules_json::DownloadModulesJsonCommand;
 use crate::commands::external::{exec_command, sub_command_path};
@@ -9,7 +12,14 @@ use crate::commands::install::InstallArgs;
 use crate::commands::launch::LaunchCommand;
 use crate::commands::list::ListCommand;
 use crate::commands::modules::ModulesCommand;
+use crate::commands::presentation::OutputFormat;
+use crate::commands::project::ProjectCommand;
+use crate::commands::release_notes::ReleaseNotesCommand;
+use crate::commands::repair::RepairCommand;
+use crate::commands::sync::SyncCommand;
 use crate::commands::uninstall::UninstallArgs;
+use crate::commands::update::UpdateCommand;
+use crate::commands::use_version::UseCommand;
 use crate::commands::version::VersionCommand;
 use crate::commands::Command;
 use clap::{ArgAction, Args, ColorChoice, Parser, Subcommand};
@@ -46,6 +56,10 @@ pub struct GlobalOptions {
     /// Disable progress bars and spinners
     #[arg(long, conflicts_with = "progress", env = "UVM_NO_PROGRESS")]
     pub no_progress: bool,
+
+    /// Output format of the query commands (list, modules, detect, version, release-notes, diff, install --dry-run)
+    #[arg(long, value_enum, global = true, env = "UVM_OUTPUT", default_value_t = OutputFormat::default())]
+    pub output: OutputFormat,
 }
 
 #[derive(Parser, Debug)]
@@ -64,8 +78,17 @@ pub enum Commands {
     List(ListCommand),
     Launch(LaunchCommand),
     Modules(ModulesCommand),
+    ReleaseNotes(ReleaseNotesCommand),
+    Diff(DiffCommand),
     Install(InstallArgs),
     Uninstall(UninstallArgs),
+    Update(UpdateCommand),
+    Use(UseCommand),
+    Bundle(BundleCommand),
+    Sync(SyncCommand),
+    Project(ProjectCommand),
+    Doctor(DoctorCommand),
+    Repair(RepairCommand),
     Version(VersionCommand),
     GC(GcCommand),
     #[cfg(feature = "dev-commands")]
@@ -81,8 +104,17 @@ impl Commands {
             Commands::List(list) => list.execute(),
             Commands::Launch(launch) => launch.execute(),
             Commands::Modules(modules) => modules.execute(),
+            Commands::ReleaseNotes(release_notes) => release_notes.execute(),
+            Commands::Diff(diff) => diff.execute(),
             Commands::Install(install) => with_garbage_collection(install),
             Commands::Uninstall(uninstall) => with_garbage_collection(uninstall),
+            Commands::Update(update) => with_garbage_collection(update),
+            Commands::Use(use_version) => use_version.execute(),
+            Commands::Bundle(bundle) => with_garbage_collection(bundle),
+            Commands::Sync(sync) => with_garbage_collection(sync),
+            Commands::Project(project) => with_garbage_collection(project),
+            Commands::Doctor(doctor) => doctor.execute(),
+            Commands::Repair(repair) => repair.execute(),
             Commands::Version(version) => with_garbage_collection(version),
             Commands::GC(gc) => gc.execute(),
             #[cfg(feature = "dev-commands")]
@@ -189,6 +221,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Set global progress mode based on flags
     commands::progress::set_progress_mode(cli.global.progress, cli.global.no_progress);
+    commands::presentation::set_output_format(cli.global.output);
 
     let verbose_level = cli
         .global
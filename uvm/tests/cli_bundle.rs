@@ -0,0 +1,32 @@
+use std::process::Command;
+
+#[test]
+fn test_uvm_bundle_help() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .arg("bundle")
+        .arg("--help")
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Usage: uvm bundle [OPTIONS] <EDITOR_VERSION> <DESTINATION>"));
+    assert!(stdout.contains("--module"));
+    assert!(stdout.contains("--platform"));
+    assert!(stdout.contains("--architecture"));
+}
+
+#[test]
+fn test_uvm_bundle_requires_destination() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .arg("bundle")
+        .arg("2022.3.0f1")
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("<DESTINATION>"));
+}
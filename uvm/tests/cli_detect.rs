@@ -1,3 +1,6 @@
+mod common;
+
+use common::create_unity_project;
 use std::fs;
 use std::process::Command;
 use tempfile::tempdir;
@@ -75,4 +78,30 @@ fn fails_on_missing_project_version() {
         .expect("failed to run uvm");
 
     assert!(!output.status.success());
-}
\ No newline at end of file
+}
+
+#[test]
+fn detects_project_version_as_json() {
+    let temp = tempdir().unwrap();
+    create_unity_project(
+        temp.path(),
+        "m_EditorVersion: 2021.3.2f1\nm_EditorVersionWithRevision: 2021.3.2f1 (d6360bedb9a0)",
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .arg("detect")
+        .arg("--output")
+        .arg("json")
+        .arg(temp.path())
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["version"], "2021.3.2f1");
+    assert_eq!(json["revision"], "d6360bedb9a0");
+    assert_eq!(
+        json["project_path"],
+        temp.path().display().to_string().as_str()
+    );
+}
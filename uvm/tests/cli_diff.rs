@@ -0,0 +1,30 @@
+use std::process::Command;
+
+#[test]
+fn test_uvm_diff_requires_two_versions() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .args(["diff", "2022.3.10f1"])
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("<TO>"), "unexpected output: {}", stderr);
+}
+
+#[test]
+fn test_uvm_diff_rejects_older_target_version() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("UVM_GC_ENABLED", "false")
+        .args(["--color", "never", "diff", "2022.3.20f1", "2022.3.10f1"])
+        .output()
+        .expect("failed to run uvm");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("2022.3.20f1 must be older than 2022.3.10f1"),
+        "unexpected output: {}",
+        stderr
+    );
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::{create_hub_installation, uvm};
+use tempfile::tempdir;
+
+const WEBGL_MODULE: &str = r#"[{
+    "id": "webgl",
+    "name": "WebGL Build Support",
+    "description": "",
+    "category": "Platforms",
+    "downloadSize": 1000,
+    "installedSize": 2000,
+    "url": "https://example.com/webgl.tar.xz",
+    "destination": "{UNITY_PATH}/Editor/Data/PlaybackEngines/WebGLSupport",
+    "isInstalled": true
+}]"#;
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_doctor_reports_and_fixes_missing_module() {
+    let home = tempdir().unwrap();
+    let path = create_hub_installation(home.path(), "2022.3.10f1", WEBGL_MODULE);
+
+    let output = uvm(home.path(), &["doctor"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "unexpected output: {}", stderr);
+    assert!(
+        stderr.contains("module webgl is marked as installed") && stderr.contains("(fixable)"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("run `uvm doctor --fix`"),
+        "unexpected output: {}",
+        stderr
+    );
+
+    let output = uvm(home.path(), &["doctor", "--fix", "2022.3.10f1"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "unexpected output: {}", stderr);
+    let modules = std::fs::read_to_string(path.join("modules.json")).unwrap();
+    assert!(modules.contains("\"isInstalled\": false"), "{}", modules);
+
+    let output = uvm(home.path(), &["doctor"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "unexpected output: {}", stderr);
+    assert!(stderr.contains("no issues found"), "unexpected output: {}", stderr);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_doctor_reports_unreadable_modules_json() {
+    let home = tempdir().unwrap();
+    create_hub_installation(home.path(), "2022.3.10f1", "{");
+
+    let output = uvm(home.path(), &["doctor", "--fix"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "unexpected output: {}", stderr);
+    assert!(
+        stderr.contains("modules.json is unreadable"),
+        "unexpected output: {}",
+        stderr
+    );
+}
@@ -0,0 +1,321 @@
+mod common;
+
+use common::create_unity_project;
+#[cfg(target_os = "linux")]
+use common::cache_release;
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+fn test_uvm_install_help_lists_offline_flag() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .arg("install")
+        .arg("--help")
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--offline"));
+    assert!(stdout.contains("--max-parallel-downloads"));
+    assert!(stdout.contains("--project"));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_install_offline_fails_without_cached_release() {
+    let home = tempdir().unwrap();
+    let destination = home.path().join("Unity");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("UVM_GC_ENABLED", "false")
+        .arg("install")
+        .arg("--offline")
+        .arg("2022.3.0f1")
+        .arg(&destination)
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("No cached release manifest for Unity 2022.3.0f1"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(!destination.exists());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_install_from_bundle_fails_for_invalid_bundle() {
+    let home = tempdir().unwrap();
+    let bundle = home.path().join("bundle");
+    std::fs::create_dir_all(&bundle).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("UVM_GC_ENABLED", "false")
+        .arg("install")
+        .arg("--from-bundle")
+        .arg(&bundle)
+        .arg("2022.3.0f1")
+        .arg(home.path().join("Unity"))
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid install bundle"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_install_project_uses_pinned_version() {
+    let home = tempdir().unwrap();
+    let project = home.path().join("project");
+    create_unity_project(
+        &project,
+        "m_EditorVersion: 2021.3.55f1\nm_EditorVersionWithRevision: 2021.3.55f1 (f87d5274e360)\n",
+    );
+    std::fs::write(project.join(".uvm.toml"), "modules = [\"android\"]\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .current_dir(&project)
+        .env("HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("UVM_GC_ENABLED", "false")
+        .arg("install")
+        .arg("--offline")
+        .arg("--project")
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("pins Unity 2021.3.55f1 (f87d5274e360)"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("No cached release manifest for Unity 2021.3.55f1"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_uvm_install_project_requires_revision() {
+    let home = tempdir().unwrap();
+    create_unity_project(home.path(), "m_EditorVersion: 2021.3.55f1\n");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("UVM_GC_ENABLED", "false")
+        .arg("install")
+        .arg("--project")
+        .arg(home.path())
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unable to read the pinned editor version"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_uvm_install_project_conflicts_with_version() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .arg("install")
+        .arg("--project")
+        .arg(".")
+        .arg("2022.3.0f1")
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_uvm_install_offline_requires_exact_version() {
+    let home = tempdir().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("UVM_GC_ENABLED", "false")
+        .arg("install")
+        .arg("--offline")
+        .arg("2022.3")
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Offline installs require an exact version, got =2022.3"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_uvm_install_rejects_invalid_version_requirement() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .arg("install")
+        .arg("latest-nightly")
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unknown release stream 'nightly'"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_install_dry_run_prints_plan_as_json() {
+    let home = tempdir().unwrap();
+    let destination = home.path().join("Unity");
+    cache_release(home.path());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("UVM_GC_ENABLED", "false")
+        .args(["--output", "json", "install", "--offline", "--dry-run"])
+        .args(["-m", "android", "2022.3.0f1"])
+        .arg(&destination)
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan["version"], "2022.3.0f1");
+    assert_eq!(plan["revision"], "abc123");
+    let components = plan["components"].as_array().unwrap();
+    let ids: Vec<&str> = components
+        .iter()
+        .map(|component| component["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["Unity", "android"]);
+    assert!(components.iter().all(|c| c["status"] == "missing"));
+    assert_eq!(
+        components[1]["eulas"][0]["label"],
+        "Android SDK and NDK License Terms"
+    );
+    assert!(
+        !destination.exists(),
+        "dry run must not create the destination"
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_install_dry_run_prints_plan_as_text() {
+    let home = tempdir().unwrap();
+    cache_release(home.path());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("UVM_GC_ENABLED", "false")
+        .args(["--color", "never", "install", "--offline", "--dry-run"])
+        .args(["-m", "android", "2022.3.0f1"])
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Unity 2022.3.0f1 (abc123, x86_64)"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("  + android"), "{}", stdout);
+    assert!(stdout.contains("EULAs to accept:"), "{}", stdout);
+    assert!(!home.path().join("Unity/Hub/Editor/2022.3.0f1").exists());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_install_requires_eula_acceptance_without_terminal() {
+    let home = tempdir().unwrap();
+    let destination = home.path().join("Unity");
+    cache_release(home.path());
+
+    let install = |accept_eula: Option<&str>| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_uvm"));
+        command
+            .env("HOME", home.path())
+            .env("XDG_CACHE_HOME", home.path().join("cache"))
+            .env("XDG_CONFIG_HOME", home.path().join("config"))
+            .env("UVM_GC_ENABLED", "false")
+            .args(["--color", "never", "install", "--offline"]);
+        if let Some(accept_eula) = accept_eula {
+            command.arg(accept_eula);
+        }
+        command
+            .args(["-m", "android", "2022.3.0f1"])
+            .arg(&destination)
+            .output()
+            .expect("failed to run uvm")
+    };
+
+    let output = install(None);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("EULA not accepted for: android"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(stderr.contains("--accept-eula"), "unexpected output: {}", stderr);
+    assert!(
+        !destination.exists(),
+        "nothing must be installed without accepting the EULA"
+    );
+
+    let output = install(Some("--accept-eula=android"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("EULA not accepted"),
+        "unexpected output: {}",
+        stderr
+    );
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{create_hub_installation, uvm};
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_list_prints_installations_as_json() {
+    let home = tempdir().unwrap();
+    create_hub_installation(
+        home.path(),
+        "2022.3.10f1",
+        r#"[{
+            "id": "webgl",
+            "name": "WebGL Build Support",
+            "description": "Allows building your Unity projects for the WebGL platform",
+            "category": "Platforms",
+            "downloadSize": 1000,
+            "installedSize": 2000,
+            "url": "https://example.com/webgl.tar.xz",
+            "isInstalled": true
+        }]"#,
+    );
+
+    let output = uvm(home.path(), &["list", "--output", "json"]);
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let installation = &json[0];
+    assert_eq!(installation["version"], "2022.3.10f1");
+    assert!(installation["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("Unity/Hub/Editor/2022.3.10f1"));
+    assert_eq!(installation["architecture"], serde_json::Value::Null);
+
+    let module = &installation["modules"][0];
+    assert_eq!(module["id"], "webgl");
+    assert_eq!(module["category"], "Platforms");
+    assert_eq!(module["download_size"], 1000);
+    assert_eq!(module["installed_size"], 2000);
+    assert_eq!(module["installed"], true);
+}
+
+#[test]
+fn test_uvm_list_rejects_unknown_output_format() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .arg("list")
+        .arg("--output")
+        .arg("yaml")
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+}
@@ -0,0 +1,219 @@
+//! End-to-end tests against a local stand-in for the live platform API serving the fake
+//! releases and installers in `tests/fixtures/live_platform`.
+mod common;
+
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::tempdir;
+use uvm_live_platform::http_client::{HTTP_CONFIG_ENV, HTTP_NO_PROXY_ENV, HTTP_PROXY_ENV};
+use uvm_live_platform::test_support::FixtureServer;
+use uvm_live_platform::{CASSETTE_ENV, CASSETTE_MODE_ENV, ENDPOINT_ENV};
+
+fn fixture_server() -> FixtureServer {
+    FixtureServer::start(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/live_platform"))
+        .expect("failed to start fixture server")
+}
+
+fn uvm_command(home: &Path, endpoint: &str, args: &[&str]) -> Command {
+    let mut command = common::uvm_command(home);
+    command
+        .env(ENDPOINT_ENV, endpoint)
+        .args(["--color", "never", "--no-progress"])
+        .args(args);
+    command
+}
+
+fn uvm(home: &Path, server: &FixtureServer, args: &[&str]) -> Output {
+    uvm_command(home, &server.endpoint(), args)
+        .output()
+        .expect("failed to run uvm")
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_modules_lists_fixture_release() {
+    let home = tempdir().unwrap();
+    let server = fixture_server();
+
+    let output = uvm(
+        home.path(),
+        &server,
+        &["--output", "json", "modules", "2022.3.0f1"],
+    );
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let modules: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let ids: Vec<&str> = modules
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|module| module["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["webgl"]);
+    assert!(server
+        .requests()
+        .iter()
+        .any(|request| request.operation.as_deref() == Some("FetchReleaseQuery")));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_install_downloads_fixture_installers() {
+    let home = tempdir().unwrap();
+    let destination = home.path().join("Unity-2022.3.0f1");
+    let server = fixture_server();
+
+    let output = uvm(
+        home.path(),
+        &server,
+        &[
+            "install",
+            "2022.3.0f1",
+            "-m",
+            "webgl",
+            destination.to_str().unwrap(),
+        ],
+    );
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(destination.join("Editor/Unity").is_file());
+    assert!(destination
+        .join("Editor/Data/PlaybackEngines/WebGLSupport/BuildTools/README.txt")
+        .is_file());
+    let downloads: Vec<String> = server
+        .requests()
+        .into_iter()
+        .filter(|request| request.method == "GET")
+        .map(|request| request.path)
+        .collect();
+    assert!(downloads.contains(&"/files/Unity-2022.3.0f1.zip".to_string()));
+    assert!(downloads
+        .contains(&"/files/UnitySetup-WebGL-Support-for-Editor-2022.3.0f1.zip".to_string()));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_release_notes_renders_fixture_notes() {
+    let home = tempdir().unwrap();
+    let server = fixture_server();
+
+    let output = uvm(home.path(), &server, &["release-notes", "2022.3.0f1"]);
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("• WebGL: Fixed a crash when building a fixture project."),
+        "unexpected output: {}",
+        stdout
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_replays_recorded_cassette_without_network() {
+    let home = tempdir().unwrap();
+    let cassette = home.path().join("cassette");
+    let args = ["--output", "json", "modules", "2022.3.0f1"];
+
+    let server = fixture_server();
+    let endpoint = server.endpoint();
+    let recorded = uvm_command(&home.path().join("record"), &endpoint, &args)
+        .env(CASSETTE_ENV, &cassette)
+        .env(CASSETTE_MODE_ENV, "record")
+        .output()
+        .expect("failed to run uvm");
+    assert!(
+        recorded.status.success(),
+        "{}",
+        String::from_utf8_lossy(&recorded.stderr)
+    );
+    drop(server);
+
+    let replayed = uvm_command(&home.path().join("replay"), &endpoint, &args)
+        .env(CASSETTE_ENV, &cassette)
+        .env(CASSETTE_MODE_ENV, "replay")
+        .output()
+        .expect("failed to run uvm");
+    assert!(
+        replayed.status.success(),
+        "{}",
+        String::from_utf8_lossy(&replayed.stderr)
+    );
+    assert_eq!(replayed.stdout, recorded.stdout);
+
+    let unknown = uvm_command(
+        &home.path().join("replay"),
+        &endpoint,
+        &["modules", "2023.1.0f1"],
+    )
+    .env(CASSETTE_ENV, &cassette)
+    .output()
+    .expect("failed to run uvm");
+    assert!(!unknown.status.success());
+    let stderr = String::from_utf8_lossy(&unknown.stderr);
+    assert!(
+        stderr.contains("no recorded fetch_release request"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_sends_requests_through_configured_proxy() {
+    let home = tempdir().unwrap();
+    let server = fixture_server();
+    // nothing listens on the proxy port, so only requests bypassing the proxy succeed
+    let proxy = "http://127.0.0.1:9";
+    let args = ["modules", "2022.3.0f1"];
+
+    let proxied = uvm_command(home.path(), &server.endpoint(), &args)
+        .env(HTTP_PROXY_ENV, proxy)
+        .output()
+        .expect("failed to run uvm");
+    assert!(!proxied.status.success());
+    assert!(server.requests().is_empty());
+
+    let bypassed = uvm_command(home.path(), &server.endpoint(), &args)
+        .env(HTTP_PROXY_ENV, proxy)
+        .env(HTTP_NO_PROXY_ENV, "127.0.0.1")
+        .output()
+        .expect("failed to run uvm");
+    assert!(
+        bypassed.status.success(),
+        "{}",
+        String::from_utf8_lossy(&bypassed.stderr)
+    );
+    assert!(!server.requests().is_empty());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_reports_invalid_http_config() {
+    let home = tempdir().unwrap();
+    let server = fixture_server();
+    let config = home.path().join("http.json");
+    std::fs::write(&config, r#"{ "read_timeout": "forever" }"#).unwrap();
+
+    let output = uvm_command(home.path(), &server.endpoint(), &["modules", "2022.3.0f1"])
+        .env(HTTP_CONFIG_ENV, &config)
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid HTTP client config"), "{}", stderr);
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,23 @@
+mod common;
+
+use common::uvm;
+use tempfile::tempdir;
+
+#[test]
+fn test_uvm_project_upgrade_requires_unity_project() {
+    let home = tempdir().unwrap();
+    let project = home.path().join("not-a-project");
+    std::fs::create_dir_all(&project).unwrap();
+
+    let output = uvm(
+        home.path(),
+        &["project", "upgrade", "--dry-run", project.to_str().unwrap()],
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unable to find a Unity project"),
+        "unexpected output: {}",
+        stderr
+    );
+}
@@ -0,0 +1,33 @@
+use std::process::Command;
+
+#[test]
+fn test_uvm_release_notes_help_lists_filter_options() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .args(["release-notes", "--help"])
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--since"), "unexpected help: {}", stdout);
+    assert!(stdout.contains("--grep"), "unexpected help: {}", stdout);
+    assert!(stdout.contains("--markdown"), "unexpected help: {}", stdout);
+}
+
+#[test]
+fn test_uvm_release_notes_rejects_since_newer_than_version() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("UVM_GC_ENABLED", "false")
+        .args(["--color", "never", "release-notes", "2022.3.10f1"])
+        .args(["--since", "2022.3.20f1"])
+        .output()
+        .expect("failed to run uvm");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--since 2022.3.20f1 must be older than 2022.3.10f1"),
+        "unexpected output: {}",
+        stderr
+    );
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{create_hub_installation, uvm};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_repair_rolls_back_interrupted_module() {
+    let home = tempdir().unwrap();
+    let base_dir = create_hub_installation(
+        home.path(),
+        "2022.3.10f1",
+        r#"[{"id": "android", "name": "Android Build Support", "description": "android",
+            "category": "Platforms", "downloadSize": 100, "installedSize": 200,
+            "required": false, "hidden": false, "url": "https://example.com/android.pkg",
+            "isInstalled": true}]"#,
+    );
+    let android = base_dir.join("Editor/Data/PlaybackEngines/AndroidPlayer");
+    fs::create_dir_all(&android).unwrap();
+    fs::write(
+        base_dir.join("uvm-install.journal"),
+        format!(
+            "{{\"component\":\"android\",\"state\":\"extracting\",\"destination\":\"{}\",\"time\":0}}\n",
+            android.display()
+        ),
+    )
+    .unwrap();
+
+    let output = uvm(home.path(), &["repair"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(
+        stderr.contains("rolled back: android"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(!android.exists());
+    assert!(!base_dir.join("uvm-install.journal").exists());
+    let modules = fs::read_to_string(base_dir.join("modules.json")).unwrap();
+    assert!(modules.contains("\"isInstalled\": false"), "{}", modules);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_repair_without_journals_does_nothing() {
+    let home = tempdir().unwrap();
+
+    let output = uvm(home.path(), &["repair"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(
+        stderr.contains("No interrupted installations found"),
+        "unexpected output: {}",
+        stderr
+    );
+}
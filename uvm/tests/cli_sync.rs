@@ -0,0 +1,148 @@
+mod common;
+
+use common::create_unity_project;
+#[cfg(target_os = "linux")]
+use common::{cache_release, create_hub_installation, uvm};
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_sync_installs_project_requirements() {
+    let home = tempdir().unwrap();
+    let project = home.path().join("project");
+    create_unity_project(
+        &project,
+        "m_EditorVersionWithRevision: 2021.3.55f1 (f87d5274e360)\n",
+    );
+    std::fs::write(
+        home.path().join(".uvm.toml"),
+        "modules = [\"android\"]\nbuild-targets = [\"iOS\"]\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("HOME", home.path())
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("UVM_GC_ENABLED", "false")
+        .arg("sync")
+        .arg("--offline")
+        .arg(&project)
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("requires Unity 2021.3.55f1 (f87d5274e360)"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("modules: android, ios"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("No cached release manifest for Unity 2021.3.55f1"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_uvm_sync_rejects_unknown_build_target() {
+    let home = tempdir().unwrap();
+    create_unity_project(
+        home.path(),
+        "m_EditorVersionWithRevision: 2021.3.55f1 (f87d5274e360)\n",
+    );
+    std::fs::write(home.path().join(".uvm.toml"), "build-targets = [\"Dreamcast\"]\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .env("UVM_GC_ENABLED", "false")
+        .arg("sync")
+        .arg(home.path())
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unknown build target 'Dreamcast'"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_sync_reinstalls_editor_with_other_architecture() {
+    let home = tempdir().unwrap();
+    let project = home.path().join("project");
+    create_unity_project(
+        &project,
+        "m_EditorVersionWithRevision: 2022.3.10f1 (f87d5274e360)\n",
+    );
+    std::fs::write(project.join(".uvm.toml"), "architecture = \"x86_64\"\n").unwrap();
+    let editor = create_hub_installation(home.path(), "2022.3.10f1", "[]").join("Editor/Unity");
+
+    let write_elf_header = |machine: u16| {
+        let mut header = vec![0u8; 64];
+        header[..4].copy_from_slice(b"\x7fELF");
+        header[5] = 1;
+        header[18..20].copy_from_slice(&machine.to_le_bytes());
+        std::fs::write(&editor, header).unwrap();
+    };
+
+    write_elf_header(0x3e);
+    let output = uvm(home.path(), &["sync", "--offline", project.to_str().unwrap()]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "unexpected output: {}", stderr);
+    assert!(stderr.contains("Up to date"), "unexpected output: {}", stderr);
+
+    write_elf_header(0xb7);
+    let output = uvm(home.path(), &["sync", "--offline", project.to_str().unwrap()]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("installed architecture arm64 doesn't match x86_64"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("No cached release manifest for Unity 2022.3.10f1"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_sync_checks_pinned_revision_of_installation() {
+    let home = tempdir().unwrap();
+    let project = home.path().join("project");
+    create_unity_project(
+        &project,
+        "m_EditorVersionWithRevision: 2022.3.0f1 (f87d5274e360)\n",
+    );
+    create_hub_installation(home.path(), "2022.3.0f1", "[]");
+    cache_release(home.path());
+
+    let output = uvm(home.path(), &["sync", "--offline", project.to_str().unwrap()]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("installed revision abc123 doesn't match"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("requires revision f87d5274e360 but the release has revision abc123"),
+        "unexpected output: {}",
+        stderr
+    );
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::uvm;
+use tempfile::tempdir;
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_update_rejects_missing_installation() {
+    let home = tempdir().unwrap();
+
+    let output = uvm(home.path(), &["update", "--dry-run", "2019.4.1f1"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unity 2019.4.1f1 is not installed"),
+        "unexpected output: {}",
+        stderr
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_update_without_installations_does_nothing() {
+    let home = tempdir().unwrap();
+
+    let output = uvm(home.path(), &["update", "--dry-run"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
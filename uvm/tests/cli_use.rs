@@ -0,0 +1,147 @@
+mod common;
+
+use common::{create_hub_installation, create_unity_project, uvm};
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_use_sets_hub_default_editor() {
+    let home = tempdir().unwrap();
+    create_hub_installation(home.path(), "2022.3.10f1", "[]");
+
+    let output = uvm(home.path(), &["use"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No default editor set"));
+
+    let output = uvm(home.path(), &["use", "2022.3.10f1"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = home.path().join("config/UnityHub/defaultEditor.json");
+    assert_eq!(std::fs::read_to_string(config).unwrap(), "\"2022.3.10f1\"");
+
+    let output = uvm(home.path(), &["use"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2022.3.10f1");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_use_rejects_missing_installation() {
+    let home = tempdir().unwrap();
+
+    let output = uvm(home.path(), &["use", "2019.4.1f1"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unity 2019.4.1f1 is not installed"),
+        "unexpected output: {}",
+        stderr
+    );
+    assert!(!home.path().join("config/UnityHub/defaultEditor.json").exists());
+}
+
+/// Creates installations of `versions` whose editor writes its version to the returned marker.
+#[cfg(target_os = "linux")]
+fn create_launchable_installations(home: &Path, versions: &[&str]) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let marker = home.join("launched");
+    for version in versions {
+        let exec_path = create_hub_installation(home, version, "[]").join("Editor/Unity");
+        std::fs::write(
+            &exec_path,
+            format!("#!/bin/sh\necho {} > {}\n", version, marker.display()),
+        )
+        .unwrap();
+        std::fs::set_permissions(&exec_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    marker
+}
+
+#[cfg(target_os = "linux")]
+fn assert_launched(marker: &Path, version: &str) {
+    for _ in 0..50 {
+        if let Ok(launched) = std::fs::read_to_string(marker) {
+            if !launched.is_empty() {
+                assert_eq!(launched.trim(), version);
+                return;
+            }
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    panic!("editor was not launched");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_launch_uses_default_editor() {
+    let home = tempdir().unwrap();
+    let marker = create_launchable_installations(home.path(), &["2021.3.16f1", "2022.3.10f1", "2023.2.1f1"]);
+    let project = home.path().join("project");
+    create_unity_project(&project, "m_EditorVersion: 2021.3.16f1");
+
+    assert!(uvm(home.path(), &["use", "2022.3.10f1"]).status.success());
+    let output = uvm(home.path(), &["launch", project.to_str().unwrap()]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_launched(&marker, "2022.3.10f1");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_launch_resolves_version_requirement_against_installations() {
+    let home = tempdir().unwrap();
+    let marker = create_launchable_installations(home.path(), &["2021.3.16f1", "2022.3.10f1", "2022.3.20f1", "2023.2.1f1"]);
+    let project = home.path().join("project");
+    create_unity_project(&project, "m_EditorVersion: 2021.3.16f1");
+
+    let output = uvm(
+        home.path(),
+        &["launch", "--unity-version", "2024.1", project.to_str().unwrap()],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No installed Unity version matches"));
+
+    let output = uvm(
+        home.path(),
+        &["launch", "--unity-version", "2022.3", project.to_str().unwrap()],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_launched(&marker, "2022.3.20f1");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_uvm_launch_ignores_stale_default_editor() {
+    let home = tempdir().unwrap();
+    let marker = create_launchable_installations(home.path(), &["2022.3.10f1"]);
+    let project = home.path().join("project");
+    create_unity_project(&project, "m_EditorVersion: 2021.3.16f1");
+
+    // Hub keeps the default of an editor which has been uninstalled since
+    let config = home.path().join("config/UnityHub");
+    std::fs::create_dir_all(&config).unwrap();
+    std::fs::write(config.join("defaultEditor.json"), "\"2019.4.1f1\"").unwrap();
+
+    let output = uvm(home.path(), &["launch", project.to_str().unwrap()]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_launched(&marker, "2022.3.10f1");
+}
@@ -0,0 +1,17 @@
+use std::process::Command;
+
+#[test]
+fn test_uvm_version_matching_rejects_invalid_requirement() {
+    let output = Command::new(env!("CARGO_BIN_EXE_uvm"))
+        .args(["version", "matching", "latest"])
+        .output()
+        .expect("failed to run uvm");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Failed to parse version requirement 'latest'"),
+        "unexpected output: {}",
+        stderr
+    );
+}
@@ -0,0 +1,85 @@
+//! Helpers shared by the cli integration tests.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+pub fn create_unity_project(dir: &Path, version_content: &str) {
+    let project_settings = dir.join("ProjectSettings");
+    std::fs::create_dir_all(&project_settings).unwrap();
+    std::fs::write(project_settings.join("ProjectVersion.txt"), version_content).unwrap();
+}
+
+/// Creates a uvm command with `home` as home, cache and config directory.
+pub fn uvm_command(home: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_uvm"));
+    command
+        .env("HOME", home)
+        .env("XDG_CACHE_HOME", home.join("cache"))
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env("UVM_GC_ENABLED", "false");
+    command
+}
+
+/// Runs uvm with `home` as home, cache and config directory.
+pub fn uvm(home: &Path, args: &[&str]) -> Output {
+    uvm_command(home)
+        .args(args)
+        .output()
+        .expect("failed to run uvm")
+}
+
+/// Creates a fake Unity Hub installation of `version` below `home`.
+pub fn create_hub_installation(home: &Path, version: &str, modules_json: &str) -> PathBuf {
+    let path = home.join("Unity/Hub/Editor").join(version);
+    std::fs::create_dir_all(path.join("Editor")).unwrap();
+    std::fs::write(path.join("Editor/Unity"), "").unwrap();
+    std::fs::write(path.join("modules.json"), modules_json).unwrap();
+    path
+}
+
+/// Caches the release manifest of Unity 2022.3.0f1 (abc123) with an android module.
+pub fn cache_release(home: &Path) {
+    let release_dir =
+        home.join("cache/com.github.larusso.unity-version-manager/installer/2022.3.0f1-abc123");
+    std::fs::create_dir_all(&release_dir).unwrap();
+    std::fs::write(
+        release_dir.join("release-x86_64.json"),
+        r#"{
+            "version": "2022.3.0f1",
+            "productName": "Unity",
+            "releaseDate": "2023-01-01",
+            "releaseNotes": { "url": "https://example.com/notes" },
+            "stream": "LTS",
+            "skuFamily": "CLASSIC",
+            "recommended": true,
+            "unityHubDeepLink": "unityhub://2022.3.0f1",
+            "shortRevision": "abc123",
+            "downloads": [{
+                "url": "https://example.com/unity.tar.xz",
+                "platform": "LINUX",
+                "architecture": "X86_64",
+                "downloadSize": 1000000,
+                "installedSize": 2000000,
+                "modules": [{
+                    "id": "android",
+                    "name": "Android Build Support",
+                    "description": "Android Build Support",
+                    "category": "Platforms",
+                    "url": "https://example.com/android.tar.xz",
+                    "downloadSize": 500000,
+                    "installedSize": 1000000,
+                    "required": false,
+                    "hidden": false,
+                    "preSelected": false,
+                    "eula": [{
+                        "url": "https://example.com/android-eula.html",
+                        "label": "Android SDK and NDK License Terms",
+                        "message": "Accept the Android terms"
+                    }]
+                }]
+            }]
+        }"#,
+    )
+    .unwrap();
+}
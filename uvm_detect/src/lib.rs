@@ -9,6 +9,9 @@ use unity_version::RevisionHash;
 use unity_version::Version;
 use unity_version::CompleteVersion;
 
+mod project_manifest;
+pub use project_manifest::{ProjectArchitecture, ProjectManifest, PROJECT_MANIFEST_FILE_NAME};
+
 
 /// Configuration options for Unity project detection.
 ///
@@ -323,6 +326,41 @@ impl DetectOptions {
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))
     }
 
+    /// Detects a Unity project and loads its `.uvm.toml` project manifest.
+    ///
+    /// The project is located with the configured options. The manifest is optional and
+    /// searched in the project root and its parent directories up to the repository root
+    /// (see [`ProjectManifest::find`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory path to start searching from
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(ProjectManifest))` - If the project has a valid manifest
+    /// * `Ok(None)` - If the project has no manifest
+    /// * `Err(io::Error)` - If the project is not found or the manifest cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use uvm_detect::DetectOptions;
+    ///
+    /// let modules = DetectOptions::new()
+    ///     .detect_project_manifest(Path::new("./project"))?
+    ///     .map(|manifest| manifest.modules)
+    ///     .unwrap_or_default();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn detect_project_manifest(&self, dir: &Path) -> io::Result<Option<ProjectManifest>> {
+        let project_dir = self.detect_unity_project_dir(dir)?;
+        ProjectManifest::find(&project_dir)
+            .map(|path| ProjectManifest::load(&path))
+            .transpose()
+    }
+
     /// Detects a Unity project directory by searching for Unity project markers.
     ///
     /// This function searches for Unity project directories using the configured options. It looks 
@@ -585,6 +623,69 @@ pub fn detect_project_complete_version(project_path: &Path) -> io::Result<Comple
     DetectOptions::new().detect_project_complete_version(project_path)
 }
 
+/// Detects a Unity project and loads its `.uvm.toml` project manifest.
+/// 
+/// Convenience function using default detection options.
+pub fn detect_project_manifest(project_path: &Path) -> io::Result<Option<ProjectManifest>> {
+    DetectOptions::new().detect_project_manifest(project_path)
+}
+
+/// Pins the Unity project in `project_dir` to `version`.
+///
+/// Rewrites the `m_EditorVersion` and `m_EditorVersionWithRevision` entries of
+/// `ProjectSettings/ProjectVersion.txt`. All other lines are kept as they are, missing
+/// entries are appended. Windows line endings are preserved.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use std::str::FromStr;
+/// use unity_version::CompleteVersion;
+/// use uvm_detect::write_project_version;
+///
+/// let version = CompleteVersion::from_str("2022.3.20f1 (61c2feb0970d)").unwrap();
+/// write_project_version(Path::new("./my-unity-project"), &version)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_project_version(project_dir: &Path, version: &CompleteVersion) -> io::Result<()> {
+    let path = try_get_project_version(project_dir).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "ProjectVersion.txt not found")
+    })?;
+    let content = fs::read_to_string(&path)?;
+    let line_ending = if content.contains("\r\n") { "\r\n" } else { "\n" };
+
+    let editor_version = format!("m_EditorVersion: {}", version.version());
+    let editor_version_with_revision = format!("m_EditorVersionWithRevision: {}", version);
+    let mut has_editor_version = false;
+    let mut has_editor_version_with_revision = false;
+
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if line.starts_with("m_EditorVersion: ") {
+                has_editor_version = true;
+                editor_version.clone()
+            } else if line.starts_with("m_EditorVersionWithRevision: ") {
+                has_editor_version_with_revision = true;
+                editor_version_with_revision.clone()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if !has_editor_version {
+        lines.push(editor_version);
+    }
+    if !has_editor_version_with_revision {
+        lines.push(editor_version_with_revision);
+    }
+
+    let mut content = lines.join(line_ending);
+    content.push_str(line_ending);
+    fs::write(path, content)
+}
+
 /// Attempts to get the path to the Unity ProjectVersion.txt file if it exists.
 /// 
 /// Convenience function using default detection options.
@@ -735,6 +836,25 @@ mod tests {
         assert_eq!(version.to_string(), "2019.4.31f1");
     }
 
+    #[test]
+    fn test_detect_project_version_unity_6_china_and_experimental_versions() {
+        for version_string in ["6000.0.23f1", "2022.3.10f1c1", "2020.3.48c1", "2023.1.0x1"] {
+            let temp_dir = TempDir::new().unwrap();
+            let version_content = format!(
+                "m_EditorVersion: {}\nm_EditorVersionWithRevision: {} (4016570cf34f)",
+                version_string, version_string
+            );
+            create_unity_project(temp_dir.path(), &version_content).unwrap();
+
+            let version = detect_project_version(temp_dir.path()).unwrap();
+            assert_eq!(version.to_string(), version_string);
+
+            let complete_version = detect_project_complete_version(temp_dir.path()).unwrap();
+            assert_eq!(complete_version.version().to_string(), version_string);
+            assert_eq!(complete_version.revision().as_str(), "4016570cf34f");
+        }
+    }
+
     #[test]
     fn test_detect_project_version_no_version_info() {
         let temp_dir = TempDir::new().unwrap();
@@ -1175,4 +1295,82 @@ mod tests {
         assert_eq!(complete_version.version().to_string(), "2021.3.55f1");
         assert_eq!(complete_version.revision().as_str(), "f87d5274e360");
     }
+
+    #[test]
+    fn test_write_project_version() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unity_project(
+            temp_dir.path(),
+            "m_EditorVersion: 2021.3.16f1\nm_EditorVersionWithRevision: 2021.3.16f1 (4016570cf34f)\n",
+        )
+        .unwrap();
+
+        let version = CompleteVersion::from_str("2021.3.45f1 (0da89fac8e79)").unwrap();
+        write_project_version(temp_dir.path(), &version).unwrap();
+
+        let content =
+            fs::read_to_string(temp_dir.path().join("ProjectSettings/ProjectVersion.txt")).unwrap();
+        assert_eq!(
+            content,
+            "m_EditorVersion: 2021.3.45f1\nm_EditorVersionWithRevision: 2021.3.45f1 (0da89fac8e79)\n"
+        );
+        assert_eq!(detect_project_complete_version(temp_dir.path()).unwrap(), version);
+    }
+
+    #[test]
+    fn test_write_project_version_preserves_crlf() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unity_project(
+            temp_dir.path(),
+            "m_EditorVersion: 2021.3.16f1\r\nm_EditorVersionWithRevision: 2021.3.16f1 (4016570cf34f)\r\n",
+        )
+        .unwrap();
+
+        let version = CompleteVersion::from_str("2021.3.45f1 (0da89fac8e79)").unwrap();
+        write_project_version(temp_dir.path(), &version).unwrap();
+
+        let content =
+            fs::read_to_string(temp_dir.path().join("ProjectSettings/ProjectVersion.txt")).unwrap();
+        assert_eq!(
+            content,
+            "m_EditorVersion: 2021.3.45f1\r\nm_EditorVersionWithRevision: 2021.3.45f1 (0da89fac8e79)\r\n"
+        );
+    }
+
+    #[test]
+    fn test_write_project_version_adds_missing_revision() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unity_project(temp_dir.path(), "m_EditorVersion: 2021.3.16f1").unwrap();
+
+        let version = CompleteVersion::from_str("2021.3.45f1 (0da89fac8e79)").unwrap();
+        write_project_version(temp_dir.path(), &version).unwrap();
+        assert_eq!(
+            detect_project_version_revision_hash(temp_dir.path()).unwrap().as_str(),
+            "0da89fac8e79"
+        );
+        assert!(write_project_version(&temp_dir.path().join("missing"), &version).is_err());
+    }
+
+    #[test]
+    fn test_detect_project_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unity_project(temp_dir.path(), "m_EditorVersion: 2021.3.16f1").unwrap();
+
+        assert_eq!(detect_project_manifest(temp_dir.path()).unwrap(), None);
+
+        fs::write(
+            temp_dir.path().join(PROJECT_MANIFEST_FILE_NAME),
+            "modules = [\"android\"]",
+        )
+        .unwrap();
+        let manifest = detect_project_manifest(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(manifest.modules, vec!["android"]);
+    }
+
+    #[test]
+    fn test_detect_project_manifest_without_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = detect_project_manifest(temp_dir.path());
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+    }
 }
@@ -0,0 +1,247 @@
+use serde::Deserialize;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use unity_version::{CompleteVersion, RevisionHash, Version};
+
+/// The file name of the uvm project manifest.
+pub const PROJECT_MANIFEST_FILE_NAME: &str = ".uvm.toml";
+
+/// The editor architecture a project prefers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum ProjectArchitecture {
+    #[serde(rename = "x86_64", alias = "x86-64")]
+    X86_64,
+    #[serde(rename = "arm64", alias = "aarch64")]
+    Arm64,
+}
+
+impl fmt::Display for ProjectArchitecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectArchitecture::X86_64 => write!(f, "x86_64"),
+            ProjectArchitecture::Arm64 => write!(f, "arm64"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct RawProjectManifest {
+    version: Option<String>,
+    architecture: Option<ProjectArchitecture>,
+    modules: Vec<String>,
+    build_targets: Vec<String>,
+}
+
+/// Per-project tooling requirements declared in a `.uvm.toml` file.
+///
+/// The manifest usually sits next to `Assets/` and `ProjectSettings/`, but may also live in a
+/// parent directory of the project, e.g. the root of a repository containing several projects.
+///
+/// # File Format
+///
+/// ```toml
+/// # optional, overrides the editor version pinned in ProjectVersion.txt
+/// version = "2022.3.10f1 (ff3792e53c62)"
+/// # optional, x86_64 or arm64
+/// architecture = "arm64"
+/// # modules to install alongside the editor
+/// modules = ["android-sdk-ndk-tools"]
+/// # build targets the project is built for (e.g. Android, iOS, WebGL)
+/// build-targets = ["Android", "iOS"]
+/// ```
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use uvm_detect::ProjectManifest;
+///
+/// let manifest = ProjectManifest::load(Path::new("./my-unity-project/.uvm.toml"))?;
+/// for module in &manifest.modules {
+///     println!("requires module {}", module);
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectManifest {
+    /// Editor version overriding `ProjectVersion.txt`
+    pub version: Option<Version>,
+    /// Revision hash of the overriding editor version, if given
+    pub revision: Option<RevisionHash>,
+    /// Preferred editor architecture
+    pub architecture: Option<ProjectArchitecture>,
+    /// Module ids to install in addition to the editor (e.g. `android`, `ios`)
+    pub modules: Vec<String>,
+    /// Build targets the project needs support for (e.g. `Android`, `iOS`)
+    pub build_targets: Vec<String>,
+}
+
+impl ProjectManifest {
+    /// Reads and parses the manifest file at `path`.
+    ///
+    /// # Error Types
+    ///
+    /// - `NotFound` - The manifest file doesn't exist
+    /// - `InvalidData` - The manifest is not valid TOML or contains invalid values
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        content
+            .parse()
+            .map_err(|e: io::Error| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    /// Returns the path of the manifest in `dir`.
+    pub fn path_in<P: AsRef<Path>>(dir: P) -> PathBuf {
+        dir.as_ref().join(PROJECT_MANIFEST_FILE_NAME)
+    }
+
+    /// Searches for a manifest in `project_dir` and its parent directories.
+    ///
+    /// The search stops at the first manifest found or after the first directory which
+    /// contains a `.git` entry, so manifests outside of the repository are ignored.
+    pub fn find<P: AsRef<Path>>(project_dir: P) -> Option<PathBuf> {
+        for dir in project_dir.as_ref().ancestors() {
+            let path = Self::path_in(dir);
+            if path.is_file() {
+                return Some(path);
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        None
+    }
+}
+
+fn invalid_data<E: fmt::Display>(e: E) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid project manifest: {}", e),
+    )
+}
+
+impl FromStr for ProjectManifest {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw: RawProjectManifest = toml::from_str(s).map_err(|e| invalid_data(e.message()))?;
+
+        let (version, revision) = match raw.version.as_deref().map(str::trim) {
+            Some(version) if version.contains('(') => {
+                let complete_version = CompleteVersion::from_str(version).map_err(invalid_data)?;
+                (
+                    Some(complete_version.version().to_owned()),
+                    Some(complete_version.revision().to_owned()),
+                )
+            }
+            Some(version) => (
+                Some(Version::from_str(version).map_err(invalid_data)?),
+                None,
+            ),
+            None => (None, None),
+        };
+
+        Ok(ProjectManifest {
+            version,
+            revision,
+            architecture: raw.architecture,
+            modules: raw.modules,
+            build_targets: raw.build_targets,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_modules() {
+        let manifest: ProjectManifest = "modules = [\"android\", \"ios\"]".parse().unwrap();
+        assert_eq!(manifest.modules, vec!["android", "ios"]);
+    }
+
+    #[test]
+    fn test_parse_empty_manifest() {
+        let manifest: ProjectManifest = "".parse().unwrap();
+        assert_eq!(manifest, ProjectManifest::default());
+    }
+
+    #[test]
+    fn test_parse_full_manifest() {
+        let manifest: ProjectManifest = r#"
+            version = "2022.3.10f1 (ff3792e53c62)"
+            architecture = "arm64"
+            modules = ["android-sdk-ndk-tools"]
+            build-targets = ["Android", "iOS"]
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(manifest.version.unwrap().to_string(), "2022.3.10f1");
+        assert_eq!(manifest.revision.unwrap().as_str(), "ff3792e53c62");
+        assert_eq!(manifest.architecture, Some(ProjectArchitecture::Arm64));
+        assert_eq!(manifest.modules, vec!["android-sdk-ndk-tools"]);
+        assert_eq!(manifest.build_targets, vec!["Android", "iOS"]);
+    }
+
+    #[test]
+    fn test_parse_version_without_revision() {
+        let manifest: ProjectManifest = "version = \"2021.3.16f1\"".parse().unwrap();
+        assert_eq!(manifest.version.unwrap().to_string(), "2021.3.16f1");
+        assert_eq!(manifest.revision, None);
+    }
+
+    #[test]
+    fn test_parse_invalid_manifest() {
+        for content in [
+            "modules = \"android\"",
+            "version = \"not a version\"",
+            "architecture = \"mips\"",
+            "module = [\"android\"]",
+        ] {
+            let err = content.parse::<ProjectManifest>().unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", content);
+        }
+    }
+
+    #[test]
+    fn test_load_reports_path_on_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = ProjectManifest::path_in(temp_dir.path());
+        fs::write(&path, "modules = [").unwrap();
+
+        let err = ProjectManifest::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains(PROJECT_MANIFEST_FILE_NAME));
+    }
+
+    #[test]
+    fn test_find_searches_parent_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path().join("projects").join("game");
+        fs::create_dir_all(&project_dir).unwrap();
+        assert_eq!(ProjectManifest::find(&project_dir), None);
+
+        let manifest_path = ProjectManifest::path_in(temp_dir.path());
+        fs::write(&manifest_path, "").unwrap();
+        assert_eq!(ProjectManifest::find(&project_dir), Some(manifest_path));
+    }
+
+    #[test]
+    fn test_find_stops_at_repository_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let repository = temp_dir.path().join("repository");
+        let project_dir = repository.join("game");
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::create_dir(repository.join(".git")).unwrap();
+        fs::write(ProjectManifest::path_in(temp_dir.path()), "").unwrap();
+
+        assert_eq!(ProjectManifest::find(&project_dir), None);
+    }
+}
@@ -0,0 +1,473 @@
+//! Portable offline install bundles.
+//!
+//! A bundle is a directory containing everything needed to install a Unity version on a
+//! machine without network access:
+//!
+//! ```text
+//! <bundle>/bundle.json    the bundle manifest listing all installers and their integrity
+//! <bundle>/release.json   the serialized release fetched from the live platform api
+//! <bundle>/installers/    the installer files
+//! ```
+//!
+//! Bundles are created with [`BundleOptions::create`] and consumed with
+//! [`InstallOptions::with_bundle`](crate::InstallOptions::with_bundle).
+
+use crate::error::InstallError::{InvalidBundle, LoadingInstallerFailed};
+use crate::install::{
+    installer_cache, DownloadMirror, InstallManifest, Loader, ProgressHandler, RetryPolicy,
+};
+use crate::{resolve_components, InstallArchitecture, InstallError, Result, UnityComponent2};
+use log::{debug, info, trace};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use unity_version::Version;
+use uvm_install_graph::{InstallGraph, UnityComponent, Walker};
+use uvm_live_platform::error::{ErrorRepr, LivePlatformError};
+use uvm_live_platform::{
+    FetchRelease, Release, UnityReleaseDownloadArchitecture, UnityReleaseDownloadPlatform,
+};
+
+const RELEASE_FILE_NAME: &str = "release.json";
+const INSTALLERS_DIR_NAME: &str = "installers";
+
+/// A single installer contained in a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleInstaller {
+    /// The component id, `Unity` for the editor.
+    pub id: String,
+    /// The original download url of the installer.
+    pub url: String,
+    /// The file name of the installer inside the `installers` directory.
+    pub file_name: String,
+    /// The SRI integrity string of the installer if the release provides one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<String>,
+}
+
+/// The manifest describing the content of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    pub version: String,
+    pub short_revision: String,
+    pub platform: UnityReleaseDownloadPlatform,
+    pub architecture: UnityReleaseDownloadArchitecture,
+    pub installers: Vec<BundleInstaller>,
+}
+
+impl BundleManifest {
+    pub const FILE_NAME: &'static str = "bundle.json";
+
+    /// Loads the manifest of the bundle at `bundle_dir`.
+    pub fn load<P: AsRef<Path>>(bundle_dir: P) -> Result<Self> {
+        let manifest_path = bundle_dir.as_ref().join(Self::FILE_NAME);
+        let content = fs::read_to_string(&manifest_path).map_err(|err| {
+            InvalidBundle(format!("unable to read {}: {}", manifest_path.display(), err))
+        })?;
+        let manifest: Self = serde_json::from_str(&content).map_err(|err| {
+            InvalidBundle(format!("unable to parse {}: {}", manifest_path.display(), err))
+        })?;
+
+        // installers are imported by file name, which must not escape the installer directories
+        if let Some(installer) = manifest
+            .installers
+            .iter()
+            .find(|installer| !is_plain_file_name(&installer.file_name))
+        {
+            return Err(InvalidBundle(format!(
+                "invalid installer file name {:?} for {}",
+                installer.file_name, installer.id
+            )));
+        }
+        Ok(manifest)
+    }
+
+    pub(crate) fn load_release<P: AsRef<Path>>(bundle_dir: P) -> Result<Release> {
+        let release_path = bundle_dir.as_ref().join(RELEASE_FILE_NAME);
+        let content = fs::read_to_string(&release_path).map_err(|err| {
+            InvalidBundle(format!("unable to read {}: {}", release_path.display(), err))
+        })?;
+        serde_json::from_str(&content).map_err(|err| {
+            InvalidBundle(format!("unable to parse {}: {}", release_path.display(), err))
+        })
+    }
+
+    /// The location of the given installer inside the bundle at `bundle_dir`.
+    pub fn installer_path<P: AsRef<Path>>(&self, bundle_dir: P, installer: &BundleInstaller) -> PathBuf {
+        bundle_dir
+            .as_ref()
+            .join(INSTALLERS_DIR_NAME)
+            .join(&installer.file_name)
+    }
+}
+
+pub struct BundleOptions {
+    version: Version,
+    requested_modules: HashSet<String>,
+    install_sync: bool,
+    platform: UnityReleaseDownloadPlatform,
+    architecture: InstallArchitecture,
+    download_mirror: Option<DownloadMirror>,
+    retry_policy: Option<RetryPolicy>,
+    progress_handler: Option<Box<dyn ProgressHandler>>,
+}
+
+impl BundleOptions {
+    pub fn new<V: Into<Version>>(version: V) -> Self {
+        Self {
+            version: version.into(),
+            requested_modules: HashSet::new(),
+            install_sync: false,
+            platform: UnityReleaseDownloadPlatform::default(),
+            architecture: InstallArchitecture::default(),
+            download_mirror: None,
+            retry_policy: None,
+            progress_handler: None,
+        }
+    }
+
+    pub fn with_requested_modules<S: Into<String>, I: IntoIterator<Item = S>>(
+        mut self,
+        requested_modules: I,
+    ) -> Self {
+        self.requested_modules = requested_modules.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
+    pub fn with_install_sync(mut self, install_sync: bool) -> Self {
+        self.install_sync = install_sync;
+        self
+    }
+
+    /// The platform the bundle is created for. Defaults to the current platform.
+    pub fn with_platform(mut self, platform: UnityReleaseDownloadPlatform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    pub fn with_architecture(mut self, architecture: InstallArchitecture) -> Self {
+        self.architecture = architecture;
+        self
+    }
+
+    pub fn with_download_mirror(mut self, mirror: DownloadMirror) -> Self {
+        self.download_mirror = Some(mirror);
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    pub fn with_progress_handler<P: ProgressHandler + 'static>(mut self, handler: P) -> Self {
+        self.progress_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Downloads the editor and all requested modules into `destination`.
+    pub fn create<P: AsRef<Path>>(&self, destination: P) -> Result<BundleManifest> {
+        let destination = destination.as_ref();
+        if destination.exists() && !destination.is_dir() {
+            return Err(InvalidBundle(format!(
+                "{} is not a directory",
+                destination.display()
+            )));
+        }
+
+        let architecture: UnityReleaseDownloadArchitecture = self.architecture.into();
+
+        if let Some(ref handler) = self.progress_handler {
+            handler.set_message("Fetching Unity version metadata...");
+        }
+
+        let release = FetchRelease::builder(self.version.to_owned())
+            .with_platform(self.platform)
+            .with_extended_lts()
+            .with_u7_alpha()
+            .with_architecture(architecture)
+            .fetch()
+            .map_err(|e| {
+                let e = ErrorRepr::FetchReleaseError(e);
+                LivePlatformError::new("Failed to fetch release", e)
+            })?;
+
+        let mut graph = InstallGraph::from(&release);
+        graph.mark_all_missing();
+        let components = resolve_components(
+            &graph,
+            &self.requested_modules,
+            self.install_sync,
+            &self.version,
+        )?;
+        graph.keep(&components);
+
+        let mut ids: Vec<String> = Vec::new();
+        for node in graph.topo().iter(graph.context()) {
+            let id = match graph.component(node).unwrap() {
+                UnityComponent::Editor(_) => "Unity".to_string(),
+                UnityComponent::Module(m) => m.id().to_string(),
+            };
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        if let Some(ref handler) = self.progress_handler {
+            let component_list: Vec<(String, String)> = ids
+                .iter()
+                .map(|id| {
+                    let component_type = if id == "Unity" { "Editor" } else { "Module" };
+                    (id.clone(), component_type.to_string())
+                })
+                .collect();
+            handler.initialize_components(&component_list);
+        }
+
+        let installers_dir = destination.join(INSTALLERS_DIR_NAME);
+        fs::DirBuilder::new().recursive(true).create(&installers_dir)?;
+
+        let download_mirror = self
+            .download_mirror
+            .clone()
+            .unwrap_or_else(DownloadMirror::from_env);
+        let retry_policy = self.retry_policy.unwrap_or_else(RetryPolicy::from_env);
+
+        let mut installers = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let node = graph.get_node_id(id).ok_or_else(|| {
+                InstallError::UnsupportedModule(id.to_string(), self.version.to_string())
+            })?;
+            let component = UnityComponent2(graph.component(node).unwrap());
+            let component_handler = self
+                .progress_handler
+                .as_ref()
+                .and_then(|handler| handler.get_component_handler(id));
+
+            info!("download installer for {}", id);
+            let mut loader = Loader::new(&release.version, &release.short_revision, &component);
+            if !download_mirror.is_empty() {
+                loader.set_download_mirror(&download_mirror);
+            }
+            loader.set_retry_policy(retry_policy);
+            if let Some(ref handler) = component_handler {
+                handler.set_message("Downloading...");
+                loader.set_progress_handle(&**handler);
+            }
+            let cached_installer = loader.download().map_err(LoadingInstallerFailed)?;
+
+            let file_name = cached_installer
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .ok_or_else(|| InvalidBundle(format!("invalid installer path {}", cached_installer.display())))?;
+            let bundled_installer = installers_dir.join(&file_name);
+            link_or_copy(&cached_installer, &bundled_installer)?;
+
+            if let Some(ref handler) = self.progress_handler {
+                if let Some(ref component_handler) = component_handler {
+                    component_handler.set_message("✓ Bundled");
+                    component_handler.finish();
+                }
+                handler.mark_component_complete();
+            }
+
+            installers.push(BundleInstaller {
+                id: id.to_string(),
+                url: component.download_url().to_string(),
+                file_name,
+                integrity: component.integrity().map(|i| i.to_string()),
+            });
+        }
+
+        let manifest = BundleManifest {
+            version: release.version.clone(),
+            short_revision: release.short_revision.clone(),
+            platform: self.platform,
+            architecture,
+            installers,
+        };
+
+        fs::write(
+            destination.join(RELEASE_FILE_NAME),
+            serde_json::to_string_pretty(&release).map_err(std::io::Error::from)?,
+        )?;
+        fs::write(
+            destination.join(BundleManifest::FILE_NAME),
+            serde_json::to_string_pretty(&manifest).map_err(std::io::Error::from)?,
+        )?;
+
+        Ok(manifest)
+    }
+}
+
+fn is_plain_file_name(file_name: &str) -> bool {
+    let mut components = Path::new(file_name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    )
+}
+
+fn link_or_copy(from: &Path, to: &Path) -> std::io::Result<()> {
+    if to.exists() {
+        fs::remove_file(to)?;
+    }
+    fs::hard_link(from, to).or_else(|err| {
+        trace!("unable to hard link installer ({}), copy instead", err);
+        fs::copy(from, to).map(|_| ())
+    })
+}
+
+/// Imports the installers of the bundle at `bundle_dir` into the installer cache so that
+/// the release can be installed offline.
+pub(crate) fn import_bundle<P: AsRef<Path>>(
+    bundle_dir: P,
+    version: &Version,
+    architecture: UnityReleaseDownloadArchitecture,
+) -> Result<()> {
+    let bundle_dir = bundle_dir.as_ref();
+    let manifest = BundleManifest::load(bundle_dir)?;
+
+    if manifest.version != version.to_string() {
+        return Err(InvalidBundle(format!(
+            "bundle contains Unity {} but {} was requested",
+            manifest.version, version
+        )));
+    }
+    if manifest.platform != UnityReleaseDownloadPlatform::default() {
+        return Err(InvalidBundle(format!(
+            "bundle was created for {} but this is {}",
+            manifest.platform,
+            UnityReleaseDownloadPlatform::default()
+        )));
+    }
+    if manifest.architecture != architecture {
+        return Err(InvalidBundle(format!(
+            "bundle was created for architecture {} but {} was requested",
+            manifest.architecture, architecture
+        )));
+    }
+
+    let release = BundleManifest::load_release(bundle_dir)?;
+    let installer_dir = installer_cache::installer_dir(&manifest.version, &manifest.short_revision)?;
+    fs::DirBuilder::new().recursive(true).create(&installer_dir)?;
+
+    for installer in &manifest.installers {
+        let source = manifest.installer_path(bundle_dir, installer);
+        if !source.exists() {
+            return Err(InvalidBundle(format!(
+                "installer for {} is missing at {}",
+                installer.id,
+                source.display()
+            )));
+        }
+
+        let destination = installer_dir.join(&installer.file_name);
+        debug!("import {} to {}", source.display(), destination.display());
+        link_or_copy(&source, &destination)?;
+
+        if let Ok(url) = Url::parse(&installer.url) {
+            installer_cache::record_file_name(&installer_dir, &url, &installer.file_name)?;
+        }
+    }
+
+    installer_cache::save_release(&release, architecture)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn manifest() -> BundleManifest {
+        BundleManifest {
+            version: "2022.3.0f1".to_string(),
+            short_revision: "fb119bb0b476".to_string(),
+            platform: UnityReleaseDownloadPlatform::default(),
+            architecture: UnityReleaseDownloadArchitecture::default(),
+            installers: vec![BundleInstaller {
+                id: "Unity".to_string(),
+                url: "https://download.unity3d.com/download_unity/fb119bb0b476/Unity.tar.xz"
+                    .to_string(),
+                file_name: "Unity.tar.xz".to_string(),
+                integrity: Some("sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=".to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn manifest_round_trips_through_bundle_dir() {
+        let bundle_dir = tempfile::tempdir().unwrap();
+        let manifest = manifest();
+        fs::write(
+            bundle_dir.path().join(BundleManifest::FILE_NAME),
+            serde_json::to_string_pretty(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(BundleManifest::load(bundle_dir.path()).unwrap(), manifest);
+        assert_eq!(
+            manifest.installer_path(bundle_dir.path(), &manifest.installers[0]),
+            bundle_dir.path().join("installers").join("Unity.tar.xz")
+        );
+    }
+
+    #[test]
+    fn import_rejects_version_mismatch() {
+        let bundle_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            bundle_dir.path().join(BundleManifest::FILE_NAME),
+            serde_json::to_string_pretty(&manifest()).unwrap(),
+        )
+        .unwrap();
+
+        let result = import_bundle(
+            bundle_dir.path(),
+            &Version::from_str("2022.3.1f1").unwrap(),
+            UnityReleaseDownloadArchitecture::default(),
+        );
+        assert!(matches!(result, Err(InvalidBundle(_))));
+    }
+
+    #[test]
+    fn load_rejects_installer_file_names_outside_the_bundle() {
+        let bundle_dir = tempfile::tempdir().unwrap();
+        for file_name in [
+            "../../Unity.tar.xz",
+            "/tmp/Unity.tar.xz",
+            "installers/Unity.tar.xz",
+            "..",
+        ] {
+            let mut manifest = manifest();
+            manifest.installers[0].file_name = file_name.to_string();
+            fs::write(
+                bundle_dir.path().join(BundleManifest::FILE_NAME),
+                serde_json::to_string_pretty(&manifest).unwrap(),
+            )
+            .unwrap();
+
+            assert!(
+                matches!(
+                    BundleManifest::load(bundle_dir.path()),
+                    Err(InvalidBundle(_))
+                ),
+                "{} must be rejected",
+                file_name
+            );
+        }
+    }
+
+    #[test]
+    fn load_fails_for_missing_manifest() {
+        let bundle_dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            BundleManifest::load(bundle_dir.path()),
+            Err(InvalidBundle(_))
+        ));
+    }
+}
@@ -34,11 +34,44 @@ pub enum InstallError {
     #[error("{}", MultipleInstallFailures::format_errors(.0))]
     MultipleInstallFailures(Vec<InstallError>),
 
+    #[error("No cached release manifest for Unity {0} ({1}). Install it once with network access to populate the cache.")]
+    OfflineReleaseNotCached(String, String),
+
+    #[error("{}", OfflineInstallersMissing::format_errors(.0))]
+    OfflineInstallersMissing(Vec<InstallError>),
+
+    #[error("No Unity version found matching {0}")]
+    NoMatchingVersion(String),
+
+    #[error("Offline installs require an exact version, got {0}")]
+    OfflineVersionRequirement(String),
+
+    #[error("Unity {0} requires revision {1} but the release has revision {2}")]
+    RevisionMismatch(String, String, String),
+
+    #[error("Invalid install bundle: {0}")]
+    InvalidBundle(String),
+
     #[error("Unity Editor installation failed: {0}")]
     EditorInstallationFailed(Box<InstallError>),
 
     #[error("{}", ModuleInstallationsFailed::format_errors(.0))]
     ModuleInstallationsFailed(Vec<InstallError>),
+
+    #[error(
+        "Not enough disk space at {}: {} required, {} available",
+        path.display(),
+        crate::space::format_bytes(*required),
+        crate::space::format_bytes(*available)
+    )]
+    InsufficientDiskSpace {
+        path: std::path::PathBuf,
+        required: u64,
+        available: u64,
+    },
+
+    #[error("EULA not accepted for: {}", .0.join(", "))]
+    EulaNotAccepted(Vec<String>),
 }
 
 /// Helper struct for formatting multiple errors
@@ -79,4 +112,20 @@ impl ModuleInstallationsFailed {
     }
 }
 
+/// Helper struct for formatting missing offline installers
+pub struct OfflineInstallersMissing;
+
+impl OfflineInstallersMissing {
+    fn format_errors(errors: &[InstallError]) -> String {
+        let mut msg = format!(
+            "{} installer(s) required for the offline installation are missing from the cache:\n",
+            errors.len()
+        );
+        for (i, err) in errors.iter().enumerate() {
+            msg.push_str(&format!("  {}. {}\n", i + 1, err));
+        }
+        msg
+    }
+}
+
 // impl_context!(InstallError(InstallError));
\ No newline at end of file
@@ -0,0 +1,162 @@
+//! Acceptance of the license agreements some modules require, e.g. the Android SDK and NDK.
+//!
+//! Accepted EULAs are recorded in `uvm-eula.json` next to `modules.json` of the installation,
+//! so modules installed again later don't ask for the same agreement twice.
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const EULA_FILE_NAME: &str = "uvm-eula.json";
+
+/// Which EULAs are accepted for an installation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EulaAcceptance {
+    /// No EULA is accepted. Installing a component with an EULA fails.
+    #[default]
+    None,
+    /// All EULAs are accepted.
+    All,
+    /// The EULAs of the components with these ids are accepted.
+    Components(HashSet<String>),
+}
+
+impl EulaAcceptance {
+    pub fn accepts(&self, component: &str) -> bool {
+        match self {
+            EulaAcceptance::None => false,
+            EulaAcceptance::All => true,
+            EulaAcceptance::Components(components) => components.contains(component),
+        }
+    }
+
+    /// Additionally accepts the EULAs of `component`.
+    pub fn accept<S: Into<String>>(self, component: S) -> Self {
+        match self {
+            EulaAcceptance::All => EulaAcceptance::All,
+            EulaAcceptance::None => EulaAcceptance::Components(HashSet::from([component.into()])),
+            EulaAcceptance::Components(mut components) => {
+                components.insert(component.into());
+                EulaAcceptance::Components(components)
+            }
+        }
+    }
+}
+
+/// An EULA accepted for a component of an installation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcceptedEula {
+    pub component: String,
+    pub label: String,
+    pub url: String,
+    /// Seconds since the unix epoch.
+    pub accepted_at: u64,
+}
+
+impl AcceptedEula {
+    pub fn new<S: Into<String>>(component: S, label: S, url: S) -> Self {
+        Self {
+            component: component.into(),
+            label: label.into(),
+            url: url.into(),
+            accepted_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The EULAs accepted for the installation at `base_dir`.
+pub fn accepted_eulas<P: AsRef<Path>>(base_dir: P) -> io::Result<Vec<AcceptedEula>> {
+    let path = base_dir.as_ref().join(EULA_FILE_NAME);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Adds `eulas` to the accepted EULAs of the installation at `base_dir`.
+///
+/// An EULA already accepted for the same component and url is kept with its original date.
+pub fn record_accepted_eulas<P: AsRef<Path>>(
+    base_dir: P,
+    eulas: impl IntoIterator<Item = AcceptedEula>,
+) -> io::Result<()> {
+    let base_dir = base_dir.as_ref();
+    let mut accepted = accepted_eulas(base_dir)?;
+    let count = accepted.len();
+    for eula in eulas {
+        if !is_accepted(&accepted, &eula.component, &eula.url) {
+            accepted.push(eula);
+        }
+    }
+    if accepted.len() == count {
+        return Ok(());
+    }
+    let content = serde_json::to_string_pretty(&accepted)?;
+    fs::write(base_dir.join(EULA_FILE_NAME), content)
+}
+
+pub(crate) fn is_accepted(accepted: &[AcceptedEula], component: &str, url: &str) -> bool {
+    accepted
+        .iter()
+        .any(|eula| eula.component == component && eula.url == url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn acceptance_of_components() {
+        assert!(!EulaAcceptance::None.accepts("android"));
+        assert!(EulaAcceptance::All.accepts("android"));
+
+        let acceptance = EulaAcceptance::None.accept("android");
+        assert!(acceptance.accepts("android"));
+        assert!(!acceptance.accepts("android-sdk-ndk-tools"));
+        assert!(acceptance
+            .accept("android-sdk-ndk-tools")
+            .accepts("android-sdk-ndk-tools"));
+        assert_eq!(EulaAcceptance::All.accept("android"), EulaAcceptance::All);
+    }
+
+    #[test]
+    fn records_accepted_eulas_once() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(accepted_eulas(temp_dir.path()).unwrap().is_empty());
+
+        let eula = AcceptedEula::new(
+            "android-sdk-ndk-tools",
+            "Android SDK and NDK License Terms",
+            "https://example.com/android-eula.html",
+        );
+        record_accepted_eulas(temp_dir.path(), [eula.clone()]).unwrap();
+        record_accepted_eulas(
+            temp_dir.path(),
+            [AcceptedEula {
+                accepted_at: eula.accepted_at + 10,
+                ..eula.clone()
+            }],
+        )
+        .unwrap();
+
+        let accepted = accepted_eulas(temp_dir.path()).unwrap();
+        assert_eq!(accepted, vec![eula]);
+        assert!(is_accepted(
+            &accepted,
+            "android-sdk-ndk-tools",
+            "https://example.com/android-eula.html"
+        ));
+        assert!(!is_accepted(
+            &accepted,
+            "android-sdk-ndk-tools",
+            "https://example.com/new-eula.html"
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::error::InstallError::LoadingInstallerFailed;
+use crate::install::error::InstallerError;
+use crate::Result;
+use log::{debug, trace};
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+#[derive(Default)]
+struct QueueState {
+    pending: VecDeque<String>,
+    finished: HashMap<String, Result<PathBuf>>,
+    running_workers: usize,
+}
+
+/// A bounded pool of download workers.
+///
+/// Components are downloaded in the order they were queued by at most `max_workers` threads.
+/// Consumers block in [`DownloadQueue::wait_for`] until the download of a specific component
+/// finished, which allows installing components in dependency order while later downloads
+/// are still in flight.
+pub(crate) struct DownloadQueue {
+    state: Mutex<QueueState>,
+    ready: Condvar,
+    cancelled: AtomicBool,
+    max_workers: usize,
+}
+
+impl DownloadQueue {
+    pub fn new<I: IntoIterator<Item = String>>(components: I, max_workers: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                pending: components.into_iter().collect(),
+                ..Default::default()
+            }),
+            ready: Condvar::new(),
+            cancelled: AtomicBool::new(false),
+            max_workers: max_workers.max(1),
+        }
+    }
+
+    /// Spawns the download workers into the given scope.
+    ///
+    /// Each worker pulls the next pending component and calls `download` for it until the
+    /// queue is drained or [`DownloadQueue::cancel`] was called.
+    pub fn spawn<'scope, 'env, F>(&'env self, scope: &'scope thread::Scope<'scope, 'env>, download: &'env F)
+    where
+        F: Fn(&str) -> Result<PathBuf> + Sync,
+    {
+        let workers = {
+            let mut state = self.state.lock().unwrap();
+            let workers = self.max_workers.min(state.pending.len());
+            state.running_workers = workers;
+            workers
+        };
+
+        debug!("start {} download workers", workers);
+        for _ in 0..workers {
+            scope.spawn(move || self.work(download));
+        }
+    }
+
+    fn work<F>(&self, download: &F)
+    where
+        F: Fn(&str) -> Result<PathBuf>,
+    {
+        let mut worker = WorkerGuard {
+            queue: self,
+            current: None,
+        };
+        loop {
+            let next = if self.cancelled.load(Ordering::SeqCst) {
+                None
+            } else {
+                self.lock_state().pending.pop_front()
+            };
+
+            let Some(component) = next else {
+                break;
+            };
+
+            trace!("download worker picked up {}", component);
+            worker.current = Some(component.clone());
+            let result = download(&component);
+            worker.current = None;
+            self.lock_state().finished.insert(component, result);
+            self.ready.notify_all();
+        }
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Blocks until the download for `component` finished and returns its result.
+    ///
+    /// Returns `None` when the component was never queued or the queue was cancelled
+    /// before it got picked up.
+    pub fn wait_for(&self, component: &str) -> Option<Result<PathBuf>> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(result) = state.finished.remove(component) {
+                return Some(result);
+            }
+
+            let queued = state.pending.iter().any(|c| c == component);
+            if state.running_workers == 0 && (!queued || self.cancelled.load(Ordering::SeqCst)) {
+                return None;
+            }
+            state = self.ready.wait(state).unwrap();
+        }
+    }
+
+    /// Stops workers from picking up new downloads. Downloads already in flight still finish.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.state.lock().unwrap().pending.clear();
+        self.ready.notify_all();
+    }
+}
+
+/// Signs off a download worker, even if `download` panicked.
+///
+/// Without it a panicking worker would never be counted as finished and
+/// [`DownloadQueue::wait_for`] would block forever.
+struct WorkerGuard<'a> {
+    queue: &'a DownloadQueue,
+    current: Option<String>,
+}
+
+impl Drop for WorkerGuard<'_> {
+    fn drop(&mut self) {
+        let mut state = self.queue.lock_state();
+        if let Some(component) = self.current.take() {
+            debug!("download worker panicked while downloading {}", component);
+            let error = io::Error::other(format!("download of {} panicked", component));
+            state.finished.insert(
+                component,
+                Err(LoadingInstallerFailed(InstallerError::from(error))),
+            );
+        }
+        state.running_workers -= 1;
+        if state.running_workers == 0 {
+            // no worker is left to pick up the remaining downloads, e.g. after a panic
+            state.pending.clear();
+        }
+        drop(state);
+        self.queue.ready.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn wait_for_returns_error_when_download_panics() {
+        let queue = DownloadQueue::new(["android".to_string(), "ios".to_string()], 1);
+        let download = |component: &str| -> Result<PathBuf> {
+            if component == "android" {
+                panic!("download of {} failed", component);
+            }
+            Ok(PathBuf::from(component))
+        };
+        let results = Mutex::new(Vec::new());
+
+        let scope_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            thread::scope(|scope| {
+                queue.spawn(scope, &download);
+                for component in ["android", "ios"] {
+                    let result = queue.wait_for(component);
+                    results.lock().unwrap().push(result.map(|r| r.is_ok()));
+                }
+            })
+        }));
+
+        assert!(scope_result.is_err(), "the worker panic is propagated");
+        // the only worker died with android, so ios is never picked up
+        assert_eq!(*results.lock().unwrap(), vec![Some(false), None]);
+    }
+}
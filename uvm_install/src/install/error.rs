@@ -15,6 +15,9 @@ pub enum InstallerErrorInner {
     #[error("missing destination {0}")]
     MissingDestination(String),
 
+    #[error("installer for {0} is not cached ({1})")]
+    MissingCachedInstaller(String, String),
+
     #[error("missing command {0}")]
     MissingCommand(String),
 
@@ -0,0 +1,144 @@
+use log::{debug, trace};
+use reqwest::Url;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use unity_hub::unity::hub::paths;
+use uvm_live_platform::{Release, UnityReleaseDownloadArchitecture};
+
+const FILE_NAME_SIDECAR_EXTENSION: &str = "url";
+
+fn cache_sub_dir(sub_dir: &str) -> io::Result<PathBuf> {
+    paths::cache_dir()
+        .map(|c| c.join(sub_dir))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "Unable to fetch cache installer directory",
+            )
+        })
+}
+
+/// The directory containing the cached installers of a release.
+pub fn installer_dir(version: &str, short_revision: &str) -> io::Result<PathBuf> {
+    cache_sub_dir(&format!("installer/{}-{}", version, short_revision))
+}
+
+/// The directory containing partial downloads of a release.
+pub fn temp_dir(version: &str, short_revision: &str) -> io::Result<PathBuf> {
+    cache_sub_dir(&format!("tmp/{}-{}", version, short_revision))
+}
+
+fn release_manifest_name(architecture: UnityReleaseDownloadArchitecture) -> String {
+    format!("release-{}.json", architecture)
+}
+
+/// Stores the release next to its installers so it can be installed without network later.
+pub fn save_release(
+    release: &Release,
+    architecture: UnityReleaseDownloadArchitecture,
+) -> io::Result<PathBuf> {
+    let dir = installer_dir(&release.version, &release.short_revision)?;
+    fs::DirBuilder::new().recursive(true).create(&dir)?;
+    let manifest_path = dir.join(release_manifest_name(architecture));
+    trace!("write release manifest to {}", manifest_path.display());
+    let content = serde_json::to_string_pretty(release)?;
+    fs::write(&manifest_path, content)?;
+    Ok(manifest_path)
+}
+
+/// Loads the most recently cached release manifest for `version` and `architecture`.
+pub fn load_release(
+    version: &str,
+    architecture: UnityReleaseDownloadArchitecture,
+) -> io::Result<Option<Release>> {
+    let installer_root = cache_sub_dir("installer")?;
+    if !installer_root.exists() {
+        return Ok(None);
+    }
+
+    let prefix = format!("{}-", version);
+    let manifest_name = release_manifest_name(architecture);
+    let newest_manifest = fs::read_dir(&installer_root)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|entry| entry.path().join(&manifest_name))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path);
+
+    match newest_manifest {
+        Some(path) => {
+            debug!("load cached release manifest {}", path.display());
+            let content = fs::read_to_string(&path)?;
+            let release = serde_json::from_str(&content)?;
+            Ok(Some(release))
+        }
+        None => Ok(None),
+    }
+}
+
+fn file_name_from_url_path(url: &Url) -> Option<String> {
+    if Path::new(url.as_str()).extension().is_some() {
+        url.as_str().rsplit('/').next().map(|s| s.to_string())
+    } else {
+        None
+    }
+}
+
+/// Remembers the resolved file name for urls which don't contain one.
+pub fn record_file_name<P: AsRef<Path>>(installer_dir: P, url: &Url, file_name: &str) -> io::Result<()> {
+    if file_name_from_url_path(url).is_some() {
+        return Ok(());
+    }
+    let sidecar = installer_dir
+        .as_ref()
+        .join(format!("{}.{}", file_name, FILE_NAME_SIDECAR_EXTENSION));
+    fs::write(sidecar, url.as_str())
+}
+
+/// Resolves the installer file name for `url` without network access.
+pub fn cached_file_name<P: AsRef<Path>>(installer_dir: P, url: &Url) -> Option<String> {
+    file_name_from_url_path(url).or_else(|| {
+        fs::read_dir(installer_dir.as_ref())
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == FILE_NAME_SIDECAR_EXTENSION)
+            })
+            .find(|path| {
+                fs::read_to_string(path).is_ok_and(|content| content.trim() == url.as_str())
+            })
+            .and_then(|path| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_file_name_uses_url_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = Url::parse("https://download.unity3d.com/download_unity/abc/Unity.pkg").unwrap();
+        assert_eq!(cached_file_name(dir.path(), &url), Some("Unity.pkg".to_string()));
+    }
+
+    #[test]
+    fn cached_file_name_uses_recorded_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = Url::parse("https://go.microsoft.com/fwlink/?linkid=2086937").unwrap();
+        assert_eq!(cached_file_name(dir.path(), &url), None);
+
+        record_file_name(dir.path(), &url, "visualstudioformac.dmg").unwrap();
+        assert_eq!(
+            cached_file_name(dir.path(), &url),
+            Some("visualstudioformac.dmg".to_string())
+        );
+    }
+}
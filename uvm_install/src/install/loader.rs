@@ -1,18 +1,21 @@
-use crate::install::error::InstallerResult;
+use crate::install::error::{InstallerError, InstallerErrorInner, InstallerResult};
+use crate::install::installer_cache;
+use crate::install::{DownloadMirror, RetryPolicy};
 use crate::utils;
 use crate::utils::lock_process;
 use crate::utils::UrlUtils;
 use log::*;
-use reqwest::header::{RANGE, USER_AGENT};
+use reqwest::header::RANGE;
 use reqwest::{StatusCode, Url};
-use ssri::Integrity;
+use ssri::{Integrity, IntegrityChecker};
 use std::fs;
 use std::io;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 use std::path::PathBuf;
+use std::thread;
 use std::time::Instant;
-use unity_hub::unity::hub::paths;
+use uvm_live_platform::http_client;
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 enum CheckSumResult {
@@ -44,7 +47,9 @@ enum CheckSumResult {
 /// // ... perform installation ...
 /// handler.finish();
 /// ```
-pub trait ProgressHandler {
+///
+/// Components may be downloaded concurrently, so handlers need to be shareable across threads.
+pub trait ProgressHandler: Send + Sync {
     /// Mark the operation as finished.
     fn finish(&self);
 
@@ -129,29 +134,96 @@ pub trait InstallManifest {
     fn install_destination<P: AsRef<Path>>(&self, base_path: P) -> Option<PathBuf>;
 }
 
-struct DownloadProgress<'a, R, P> {
+/// Size of the buffer used to hash installers already on disk.
+const CHECKSUM_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Feeds the content of the file at `path` into `checker` without loading it into memory.
+fn hash_file<P: AsRef<Path>>(path: P, checker: &mut IntegrityChecker) -> io::Result<()> {
+    let mut file = fs::File::open(path)?;
+    let mut buffer = vec![0; CHECKSUM_BUFFER_SIZE];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            return Ok(());
+        }
+        checker.input(&buffer[..n]);
+    }
+}
+
+/// Streams `source` into `dest` like `io::copy`.
+///
+/// Read errors come from the connection and can be retried. Write errors such as a full disk or
+/// missing permissions will fail again on the next attempt and are fatal.
+fn copy_download<R: Read, W: Write>(source: &mut R, dest: &mut W) -> Result<u64, DownloadFailure> {
+    let mut buffer = vec![0; CHECKSUM_BUFFER_SIZE];
+    let mut written = 0;
+    loop {
+        let n = match source.read(&mut buffer) {
+            Ok(0) => return Ok(written),
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(DownloadFailure::Transient(err.into())),
+        };
+        dest.write_all(&buffer[..n])
+            .map_err(|err| DownloadFailure::Fatal(err.into()))?;
+        written += n as u64;
+    }
+}
+
+/// A failed download attempt.
+enum DownloadFailure {
+    /// The connection broke or the server failed temporarily. The attempt can be retried.
+    Transient(InstallerError),
+    Fatal(InstallerError),
+}
+
+impl From<io::Error> for DownloadFailure {
+    fn from(err: io::Error) -> Self {
+        DownloadFailure::Fatal(err.into())
+    }
+}
+
+impl From<reqwest::Error> for DownloadFailure {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_builder() {
+            DownloadFailure::Fatal(err.into())
+        } else {
+            DownloadFailure::Transient(err.into())
+        }
+    }
+}
+
+fn checksum_result(checker: Option<IntegrityChecker>) -> CheckSumResult {
+    match checker.map(|c| c.result()) {
+        Some(Ok(_)) => CheckSumResult::Equal,
+        Some(Err(_)) => CheckSumResult::NotEqual,
+        None => CheckSumResult::NoCheckSum,
+    }
+}
+
+/// Read adapter that reports download progress and hashes the streamed bytes.
+struct DownloadProgress<'a, R> {
     pub inner: R,
-    pub progress_handle: &'a P,
+    pub progress_handle: Option<&'a dyn ProgressHandler>,
+    pub checker: Option<IntegrityChecker>,
     pub start_time: Instant,
     pub bytes_downloaded: u64,
     pub last_update: Instant,
 }
 
-// impl<'a, R: Read, P: 'a + ProgressHandler + ?Sized> Read for DownloadProgress<'a, R, &P> {
-//     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-//         self.inner.read(buf).map(|n| {
-//             self.progress_handle.inc(n as u64);
-//             n
-//         })
-//     }
-// }
-
-impl<'a, R: Read, P: 'a + ProgressHandler + ?Sized> Read for DownloadProgress<'a, R, Box<&P>> {
+impl<'a, R: Read> Read for DownloadProgress<'a, R> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf).map(|n| {
-            self.progress_handle.inc(n as u64);
+            if let Some(checker) = self.checker.as_mut() {
+                checker.input(&buf[..n]);
+            }
             self.bytes_downloaded += n as u64;
 
+            let Some(progress_handle) = self.progress_handle else {
+                return n;
+            };
+            progress_handle.inc(n as u64);
+
             // Update speed message periodically (every second)
             let now = Instant::now();
             if now.duration_since(self.last_update).as_secs() >= 1 {
@@ -165,7 +237,7 @@ impl<'a, R: Read, P: 'a + ProgressHandler + ?Sized> Read for DownloadProgress<'a
                     } else {
                         format!("{:.0} B/s", speed)
                     };
-                    self.progress_handle.set_message(&speed_msg);
+                    progress_handle.set_message(&speed_msg);
                 }
                 self.last_update = now;
             }
@@ -180,6 +252,9 @@ pub struct Loader<'a, M> {
     short_revision: &'a str,
     manifest: &'a M,
     verify: bool,
+    mirror: Option<&'a DownloadMirror>,
+    retry_policy: RetryPolicy,
+    offline: bool,
     progress_handle: Option<Box<&'a dyn ProgressHandler>>,
 }
 
@@ -193,6 +268,9 @@ where
             short_revision,
             manifest,
             verify: true,
+            mirror: None,
+            retry_policy: RetryPolicy::default(),
+            offline: false,
             progress_handle: None,
         }
     }
@@ -202,6 +280,19 @@ where
         self.verify = verify;
     }
 
+    pub fn set_download_mirror(&mut self, mirror: &'a DownloadMirror) {
+        self.mirror = Some(mirror);
+    }
+
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Only use installers from the installer cache and never touch the network.
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
     #[allow(dead_code)]
     pub fn set_progress_handle(&mut self, progress_handle: &'a dyn ProgressHandler) {
         self.progress_handle = Some(Box::new(progress_handle));
@@ -215,6 +306,10 @@ where
             self.version
         );
 
+        if self.offline {
+            return self.cached_installer();
+        }
+
         let module_url = Url::parse(self.manifest.download_url())?;
 
         // set total size in progress
@@ -222,26 +317,10 @@ where
             p.set_length(manifest.install_size());
         }
 
-        let version_string = format!("{}-{}", self.version, self.short_revision);
-        let installer_dir = paths::cache_dir()
-            .map(|c| c.join(&format!("installer/{}", version_string)))
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::Other,
-                    "Unable to fetch cache installer directory",
-                )
-            })?;
-
-        let temp_dir = paths::cache_dir()
-            .map(|c| c.join(&format!("tmp/{}", version_string)))
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::Other,
-                    "Unable to fetch cache installer directory",
-                )
-            })?;
+        let installer_dir = installer_cache::installer_dir(self.version, self.short_revision)?;
+        let temp_dir = installer_cache::temp_dir(self.version, self.short_revision)?;
 
-        let file_name = UrlUtils::get_file_name_from_url(&module_url)?;
+        let file_name = UrlUtils::get_file_name_from_url_with_mirror(&module_url, self.mirror)?;
 
         let temp_file_name = format!("{}.part", file_name);
 
@@ -254,6 +333,7 @@ where
             .create(&installer_dir)?;
 
         lock_process!(temp_dir.join(format!("{}.lock", file_name)));
+        installer_cache::record_file_name(&installer_dir, &module_url, &file_name)?;
 
         let installer_path = installer_dir.join(file_name);
         trace!("installer_path: {}", installer_path.display());
@@ -275,91 +355,230 @@ where
         }
 
         let temp_file = temp_dir.join(temp_file_name);
+        let download_start = Instant::now();
+        let mut retry = 0;
+        let check_sum_result = loop {
+            match self.download_attempt(&module_url, &temp_file) {
+                Ok(result) => break result,
+                Err(DownloadFailure::Transient(err)) if retry < self.retry_policy.max_retries() => {
+                    let delay = self.retry_policy.backoff(retry);
+                    retry += 1;
+                    warn!(
+                        "download of {} failed: {}. Retry {}/{} in {:?}",
+                        module_url,
+                        err,
+                        retry,
+                        self.retry_policy.max_retries(),
+                        delay
+                    );
+                    if let Some(ref p) = self.progress_handle {
+                        p.set_message(&format!(
+                            "Retrying ({}/{})...",
+                            retry,
+                            self.retry_policy.max_retries()
+                        ));
+                    }
+                    thread::sleep(delay);
+                }
+                Err(DownloadFailure::Transient(err)) | Err(DownloadFailure::Fatal(err)) => {
+                    return Err(err)
+                }
+            }
+        };
+
+        if let Some(ref p) = self.progress_handle {
+            // Set final completion message with time taken
+            let elapsed = download_start.elapsed();
+            let elapsed_msg = if elapsed.as_secs() >= 60 {
+                format!("{}m {}s", elapsed.as_secs() / 60, elapsed.as_secs() % 60)
+            } else {
+                format!("{}s", elapsed.as_secs())
+            };
+            p.set_message(&format!("Downloaded in {}", elapsed_msg));
+            p.finish();
+        }
+
+        trace!("checksum result {:?}", check_sum_result);
+        if check_sum_result == CheckSumResult::NotEqual {
+            // don't resume from a corrupt partial download on the next attempt
+            fs::remove_file(&temp_file)?;
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("Checksum verify failed for {}", installer_path.display()),
+            )
+            .into());
+        }
 
+        fs::rename(&temp_file, &installer_path)?;
+        Ok(installer_path)
+    }
+
+    /// Downloads the installer into `temp_file`, resuming from the bytes already on disk.
+    ///
+    /// Returns the checksum result of the complete file.
+    fn download_attempt(
+        &self,
+        module_url: &Url,
+        temp_file: &Path,
+    ) -> Result<CheckSumResult, DownloadFailure> {
         debug!("create tempfile for installer at {}", temp_file.display());
         //check if tempfile exists and get its size
         let start_range = if temp_file.exists() {
-            let metadata = fs::metadata(&temp_file)?;
+            let metadata = fs::metadata(temp_file)?;
             metadata.len()
         } else {
             0
         };
 
-        // set total size in progress
         if let Some(ref p) = self.progress_handle {
-            p.inc(start_range);
+            p.set_position(start_range);
         }
 
         debug!("request installer with offset {}", start_range);
 
-        let client = reqwest::blocking::Client::new();
-        let response = client
-            .get(module_url.as_str())
-            .header(USER_AGENT, "uvm")
-            .header(RANGE, format!("bytes={}-", start_range))
-            .send()?;
+        let client = http_client::client()
+            .map_err(|err| DownloadFailure::Fatal(io::Error::other(err).into()))?;
+        let request = |url: &Url| {
+            let request = client.get(url.as_str());
+            if start_range > 0 {
+                request.header(RANGE, format!("bytes={}-", start_range))
+            } else {
+                request
+            }
+        };
+        let response = match self.mirror {
+            Some(mirror) => mirror.send(module_url, request)?,
+            None => request(module_url).send()?,
+        };
         let status = response.status();
+        debug!("server responds with code {}", status);
+
+        if status == StatusCode::RANGE_NOT_SATISFIABLE && start_range > 0 {
+            // the partial download doesn't match the file on the server, start over
+            fs::remove_file(temp_file)?;
+            return Err(DownloadFailure::Transient(
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!(
+                        "Server rejected resume of {} at offset {}",
+                        module_url, start_range
+                    ),
+                )
+                .into(),
+            ));
+        }
+
         if status.is_client_error() || status.is_server_error() {
-            return Err(io::Error::new(
+            let err = io::Error::new(
                 io::ErrorKind::Other,
-                format!(
-                    "Download failed for {} with status {}",
-                    installer_path.display(),
-                    status
-                ),
+                format!("Download failed for {} with status {}", module_url, status),
             )
-            .into());
+            .into();
+            return Err(if status.is_server_error() {
+                DownloadFailure::Transient(err)
+            } else {
+                DownloadFailure::Fatal(err)
+            });
         }
 
-        debug!("server responds with code {}", status);
         let append = status == StatusCode::PARTIAL_CONTENT;
         debug!("server supports partial respond {}", append);
+        if !append && start_range > 0 {
+            debug!("server ignored range request, restart download from the beginning");
+            if let Some(ref p) = self.progress_handle {
+                p.set_position(0);
+            }
+        }
 
         let mut dest = fs::OpenOptions::new()
             .append(append)
+            .truncate(!append)
             .create(true)
             .write(true)
-            .open(&temp_file)?;
+            .open(temp_file)?;
+
+        // Hash while streaming. A resumed download needs the bytes already on disk first.
+        let mut checker = self
+            .manifest
+            .integrity()
+            .filter(|_| self.verify)
+            .map(IntegrityChecker::new);
+        if append {
+            if let Some(checker) = checker.as_mut() {
+                hash_file(temp_file, checker)?;
+            }
+        }
 
+        let expected_len = response.content_length();
         let download_start = Instant::now();
+        let mut source = DownloadProgress {
+            progress_handle: self.progress_handle.as_deref().copied(),
+            checker,
+            inner: response,
+            start_time: download_start,
+            bytes_downloaded: if append { start_range } else { 0 },
+            last_update: download_start,
+        };
 
-        if let Some(ref p) = self.progress_handle {
-            let mut source = DownloadProgress {
-                progress_handle: p,
-                inner: response,
-                start_time: download_start,
-                bytes_downloaded: start_range,
-                last_update: download_start,
-            };
-
-            let _ = io::copy(&mut source, &mut dest)?;
+        // the bytes written so far stay in the temp file and are resumed on the next attempt
+        let received = copy_download(&mut source, &mut dest)?;
+        if let Some(expected_len) = expected_len.filter(|len| received < *len) {
+            return Err(DownloadFailure::Transient(
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "Download of {} ended after {} of {} bytes",
+                        module_url, received, expected_len
+                    ),
+                )
+                .into(),
+            ));
+        }
 
-            // Set final completion message with time taken
-            let elapsed = download_start.elapsed();
-            let elapsed_msg = if elapsed.as_secs() >= 60 {
-                format!("{}m {}s", elapsed.as_secs() / 60, elapsed.as_secs() % 60)
-            } else {
-                format!("{}s", elapsed.as_secs())
-            };
-            p.set_message(&format!("Downloaded in {}", elapsed_msg));
-            p.finish();
+        Ok(if self.verify {
+            checksum_result(source.checker.take())
         } else {
-            let mut source = response;
-            let _ = io::copy(&mut source, &mut dest)?;
-        }
+            CheckSumResult::Skipped
+        })
+    }
 
-        fs::rename(&temp_file, &installer_path)?;
+    /// Returns the path of the installer in the installer cache without verifying its checksum.
+    ///
+    /// Fails when the installer was never downloaded.
+    pub fn find_cached_installer(&self) -> InstallerResult<PathBuf> {
+        let module_url = Url::parse(self.manifest.download_url())?;
+        let installer_dir = installer_cache::installer_dir(self.version, self.short_revision)?;
+        let installer_path = installer_cache::cached_file_name(&installer_dir, &module_url)
+            .map(|file_name| installer_dir.join(file_name))
+            .filter(|path| path.exists())
+            .ok_or_else(|| {
+                InstallerErrorInner::MissingCachedInstaller(
+                    self.manifest.id().to_string(),
+                    module_url.to_string(),
+                )
+            })?;
+        Ok(installer_path)
+    }
 
+    /// Returns the installer from the installer cache without touching the network.
+    ///
+    /// Fails when the installer was never downloaded or the cached file is corrupt.
+    pub fn cached_installer(&self) -> InstallerResult<PathBuf> {
+        let installer_path = self.find_cached_installer()?;
+        debug!("found cached installer at {}", installer_path.display());
         match self.verify_checksum(&installer_path, self.manifest.integrity())? {
             CheckSumResult::NotEqual => Err(io::Error::new(
                 io::ErrorKind::Other,
                 format!("Checksum verify failed for {}", installer_path.display()),
             )
             .into()),
-            CheckSumResult::NoFile => {
-                Err(io::Error::new(io::ErrorKind::Other, "Failed to download installer").into())
+            _ => {
+                if let Some(ref p) = self.progress_handle {
+                    p.set_length(self.manifest.install_size());
+                    p.finish();
+                }
+                Ok(installer_path)
             }
-            _ => Ok(installer_path),
         }
     }
 
@@ -379,13 +598,9 @@ where
             debug!("check installer checksum");
 
             if let Some(i) = check_sum {
-                let mut installer = fs::File::open(&path)?;
-                let mut installer_bytes = Vec::new();
-                installer.read_to_end(&mut installer_bytes)?;
-                return match i.check(&installer_bytes) {
-                    Ok(_) => Ok(CheckSumResult::Equal),
-                    _ => Ok(CheckSumResult::NotEqual),
-                };
+                let mut checker = IntegrityChecker::new(i);
+                hash_file(path, &mut checker)?;
+                return Ok(checksum_result(Some(checker)));
             } else {
                 return Ok(CheckSumResult::NoCheckSum);
             }
@@ -393,3 +608,274 @@ where
         Ok(CheckSumResult::NoFile)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn download_progress<R: Read>(
+        inner: R,
+        checker: Option<IntegrityChecker>,
+    ) -> DownloadProgress<'static, R> {
+        let now = Instant::now();
+        DownloadProgress {
+            inner,
+            progress_handle: None,
+            checker,
+            start_time: now,
+            bytes_downloaded: 0,
+            last_update: now,
+        }
+    }
+
+    #[test]
+    fn hash_file_matches_integrity_of_content() {
+        let content = vec![42u8; CHECKSUM_BUFFER_SIZE * 2 + 17];
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), &content).unwrap();
+
+        let mut checker = IntegrityChecker::new(Integrity::from(&content));
+        hash_file(file.path(), &mut checker).unwrap();
+        assert_eq!(checksum_result(Some(checker)), CheckSumResult::Equal);
+
+        let mut checker = IntegrityChecker::new(Integrity::from(b"other content"));
+        hash_file(file.path(), &mut checker).unwrap();
+        assert_eq!(checksum_result(Some(checker)), CheckSumResult::NotEqual);
+    }
+
+    #[test]
+    fn download_progress_hashes_streamed_bytes() {
+        let content = b"installer bytes".repeat(1024);
+        let checker = IntegrityChecker::new(Integrity::from(&content));
+        let mut source = download_progress(Cursor::new(content.clone()), Some(checker));
+
+        let mut dest = Vec::new();
+        io::copy(&mut source, &mut dest).unwrap();
+
+        assert_eq!(dest, content);
+        assert_eq!(source.bytes_downloaded, content.len() as u64);
+        assert_eq!(
+            checksum_result(source.checker.take()),
+            CheckSumResult::Equal
+        );
+    }
+
+    #[test]
+    fn download_progress_continues_hash_of_partial_file() {
+        let content = b"installer bytes".repeat(1024);
+        let (head, tail) = content.split_at(1000);
+        let partial = tempfile::NamedTempFile::new().unwrap();
+        fs::write(partial.path(), head).unwrap();
+
+        let mut checker = IntegrityChecker::new(Integrity::from(&content));
+        hash_file(partial.path(), &mut checker).unwrap();
+        let mut source = download_progress(Cursor::new(tail.to_vec()), Some(checker));
+        io::copy(&mut source, &mut io::sink()).unwrap();
+
+        assert_eq!(
+            checksum_result(source.checker.take()),
+            CheckSumResult::Equal
+        );
+    }
+
+    struct FailingIo(io::ErrorKind);
+
+    impl Read for FailingIo {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(self.0.into())
+        }
+    }
+
+    impl Write for FailingIo {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(self.0.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn copy_download_classifies_read_and_write_errors() {
+        let mut dest = Vec::new();
+        assert_eq!(
+            copy_download(&mut Cursor::new(b"installer".to_vec()), &mut dest).ok(),
+            Some(9)
+        );
+        assert_eq!(dest, b"installer");
+
+        assert!(matches!(
+            copy_download(
+                &mut FailingIo(io::ErrorKind::ConnectionReset),
+                &mut io::sink()
+            ),
+            Err(DownloadFailure::Transient(_))
+        ));
+        assert!(matches!(
+            copy_download(
+                &mut Cursor::new(b"installer".to_vec()),
+                &mut FailingIo(io::ErrorKind::PermissionDenied)
+            ),
+            Err(DownloadFailure::Fatal(_))
+        ));
+    }
+
+    #[test]
+    fn checksum_result_without_integrity() {
+        assert_eq!(checksum_result(None), CheckSumResult::NoCheckSum);
+    }
+
+    struct TestManifest {
+        url: String,
+        integrity: Integrity,
+    }
+
+    impl InstallManifest for TestManifest {
+        fn is_editor(&self) -> bool {
+            false
+        }
+
+        fn id(&self) -> &str {
+            "test"
+        }
+
+        fn install_size(&self) -> u64 {
+            0
+        }
+
+        fn download_url(&self) -> &str {
+            &self.url
+        }
+
+        fn integrity(&self) -> Option<Integrity> {
+            Some(self.integrity.clone())
+        }
+
+        fn install_rename_from_to<P: AsRef<Path>>(&self, _: P) -> Option<(PathBuf, PathBuf)> {
+            None
+        }
+
+        fn install_destination<P: AsRef<Path>>(&self, _: P) -> Option<PathBuf> {
+            None
+        }
+    }
+
+    /// Serves one canned response per connection and returns the received request heads.
+    fn serve(responses: Vec<Vec<u8>>) -> (Url, thread::JoinHandle<Vec<String>>) {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!(
+            "http://{}/installer.pkg",
+            listener.local_addr().unwrap()
+        ))
+        .unwrap();
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|response| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut head = String::new();
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        head.push_str(&line.to_lowercase());
+                    }
+                    stream.write_all(&response).unwrap();
+                    head
+                })
+                .collect()
+        });
+        (url, handle)
+    }
+
+    fn response(status: &str, content_length: usize, body: &[u8]) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            status, content_length
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    #[test]
+    fn download_attempt_resumes_truncated_body() {
+        let content = b"installer bytes".repeat(100);
+        let (head, tail) = content.split_at(400);
+        let (url, server) = serve(vec![
+            response("200 OK", content.len(), head),
+            response("206 Partial Content", tail.len(), tail),
+        ]);
+        let manifest = TestManifest {
+            url: url.to_string(),
+            integrity: Integrity::from(&content),
+        };
+        let loader = Loader::new("2022.1.0f1", "abc", &manifest);
+        let dir = tempfile::tempdir().unwrap();
+        let temp_file = dir.path().join("installer.pkg.part");
+
+        let first = loader.download_attempt(&url, &temp_file);
+        assert!(matches!(first, Err(DownloadFailure::Transient(_))));
+        assert_eq!(fs::read(&temp_file).unwrap(), head);
+
+        let second = loader.download_attempt(&url, &temp_file);
+        assert!(matches!(second, Ok(CheckSumResult::Equal)));
+        assert_eq!(fs::read(&temp_file).unwrap(), content);
+
+        let requests = server.join().unwrap();
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains("range: bytes=400-"));
+    }
+
+    #[test]
+    fn download_attempt_restarts_when_server_ignores_range() {
+        let content = b"installer bytes".repeat(100);
+        let (url, server) = serve(vec![response("200 OK", content.len(), &content)]);
+        let manifest = TestManifest {
+            url: url.to_string(),
+            integrity: Integrity::from(&content),
+        };
+        let loader = Loader::new("2022.1.0f1", "abc", &manifest);
+        let dir = tempfile::tempdir().unwrap();
+        let temp_file = dir.path().join("installer.pkg.part");
+        fs::write(&temp_file, b"stale partial download").unwrap();
+
+        let result = loader.download_attempt(&url, &temp_file);
+        assert!(matches!(result, Ok(CheckSumResult::Equal)));
+        assert_eq!(fs::read(&temp_file).unwrap(), content);
+        assert!(server.join().unwrap()[0].contains("range: bytes=22-"));
+    }
+
+    #[test]
+    fn download_attempt_classifies_status_codes() {
+        let (url, server) = serve(vec![
+            response("503 Service Unavailable", 0, b""),
+            response("404 Not Found", 0, b""),
+        ]);
+        let manifest = TestManifest {
+            url: url.to_string(),
+            integrity: Integrity::from(b"content"),
+        };
+        let loader = Loader::new("2022.1.0f1", "abc", &manifest);
+        let dir = tempfile::tempdir().unwrap();
+        let temp_file = dir.path().join("installer.pkg.part");
+
+        assert!(matches!(
+            loader.download_attempt(&url, &temp_file),
+            Err(DownloadFailure::Transient(_))
+        ));
+        assert!(matches!(
+            loader.download_attempt(&url, &temp_file),
+            Err(DownloadFailure::Fatal(_))
+        ));
+        server.join().unwrap();
+    }
+}
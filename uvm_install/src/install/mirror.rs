@@ -0,0 +1,323 @@
+use log::{debug, trace, warn};
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::{StatusCode, Url};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use unity_hub::unity::hub::paths;
+
+pub const DOWNLOAD_MIRROR_ENV: &str = "UVM_DOWNLOAD_MIRROR";
+pub const DOWNLOAD_MIRROR_CONFIG_ENV: &str = "UVM_DOWNLOAD_MIRROR_CONFIG";
+pub const DOWNLOAD_MIRROR_CONFIG_FILE: &str = "mirrors.json";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MirrorRule {
+    /// The url prefix to replace. `None` matches every url and replaces its origin.
+    from: Option<String>,
+    to: String,
+}
+
+/// Rewrites download urls so that installers are fetched through a mirror.
+///
+/// A mirror consists of rules mapping url prefixes to mirror prefixes. The rule with the
+/// longest matching prefix wins. A rule without a prefix replaces the scheme and host of
+/// every url that is not matched by another rule.
+///
+/// Requests sent through [`DownloadMirror::send`] fall back to the original url when the
+/// mirror responds with `404 Not Found`.
+///
+/// Besides editor and module installers, the links of the EULAs shown before installing are
+/// mirrored as well. Third-party notices of a release are neither downloaded nor shown by uvm.
+///
+/// # Configuration
+///
+/// [`DownloadMirror::from_env`] reads the rules from `UVM_DOWNLOAD_MIRROR`. The value is either
+/// a single mirror base url or a comma separated list of `prefix=mirror-prefix` pairs:
+///
+/// ```text
+/// UVM_DOWNLOAD_MIRROR=https://proxy.example.com
+/// UVM_DOWNLOAD_MIRROR=https://download.unity3d.com/=https://proxy.example.com/unity/
+/// ```
+///
+/// When the variable is not set, the rules are loaded from a json file mapping prefixes to
+/// mirror prefixes. The file is located at `UVM_DOWNLOAD_MIRROR_CONFIG` or `mirrors.json` in the
+/// uvm config directory:
+///
+/// ```json
+/// {
+///     "https://download.unity3d.com/": "https://proxy.example.com/unity/",
+///     "https://go.microsoft.com/": "https://proxy.example.com/microsoft/"
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DownloadMirror {
+    rules: Vec<MirrorRule>,
+}
+
+impl DownloadMirror {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule which replaces the url prefix `from` with `to`.
+    pub fn with_rule<F: Into<String>, T: Into<String>>(mut self, from: F, to: T) -> Self {
+        self.rules.push(MirrorRule {
+            from: Some(from.into()),
+            to: to.into(),
+        });
+        self
+    }
+
+    /// Adds a catch-all rule which replaces the scheme and host of a url with `base`.
+    pub fn with_base<T: Into<String>>(mut self, base: T) -> Self {
+        self.rules.push(MirrorRule {
+            from: None,
+            to: base.into(),
+        });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Loads the mirror configuration from the environment or the mirror config file.
+    ///
+    /// Invalid configuration is logged and ignored.
+    pub fn from_env() -> Self {
+        if let Ok(value) = std::env::var(DOWNLOAD_MIRROR_ENV) {
+            trace!("use download mirror from {}: {}", DOWNLOAD_MIRROR_ENV, value);
+            return Self::parse(&value);
+        }
+
+        let config_path = std::env::var(DOWNLOAD_MIRROR_CONFIG_ENV)
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| paths::uvm_config_dir().map(|p| p.join(DOWNLOAD_MIRROR_CONFIG_FILE)));
+
+        match config_path {
+            Some(path) if path.exists() => {
+                trace!("load download mirror config from {}", path.display());
+                fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|content| Self::from_json(&content).map_err(|err| err.to_string()))
+                    .unwrap_or_else(|err| {
+                        warn!(
+                            "Invalid download mirror config {}: {}",
+                            path.display(),
+                            err
+                        );
+                        Self::default()
+                    })
+            }
+            _ => Self::default(),
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .fold(Self::default(), |mirror, entry| {
+                // both urls may contain `=` in their query strings, so split at the first `=`
+                // which is followed by a url
+                let rule = entry
+                    .match_indices('=')
+                    .map(|(index, _)| (&entry[..index], &entry[index + 1..]))
+                    .find(|(_, to)| Url::parse(to).is_ok());
+                match rule {
+                    Some((from, to)) => mirror.with_rule(from, to),
+                    None => mirror.with_base(entry),
+                }
+            })
+    }
+
+    fn from_json(content: &str) -> serde_json::Result<Self> {
+        let rules: HashMap<String, String> = serde_json::from_str(content)?;
+        Ok(rules
+            .into_iter()
+            .fold(Self::default(), |mirror, (from, to)| mirror.with_rule(from, to)))
+    }
+
+    /// Returns the mirrored url for `url` or `None` if no rule matches.
+    pub fn rewrite(&self, url: &Url) -> Option<Url> {
+        let url_str = url.as_str();
+        let prefix_rule = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.from.as_deref().map(|from| (from, &rule.to)))
+            .filter(|(from, _)| url_str.starts_with(from))
+            .max_by_key(|(from, _)| from.len());
+
+        let rewritten = match prefix_rule {
+            Some((from, to)) => format!("{}{}", to, &url_str[from.len()..]),
+            None => {
+                let base = self.rules.iter().find(|rule| rule.from.is_none())?;
+                let origin_end = url_str.len() - url[url::Position::BeforePath..].len();
+                format!("{}{}", base.to.trim_end_matches('/'), &url_str[origin_end..])
+            }
+        };
+
+        match Url::parse(&rewritten) {
+            Ok(mirrored) => Some(mirrored),
+            Err(err) => {
+                warn!("Invalid mirror url {}: {}", rewritten, err);
+                None
+            }
+        }
+    }
+
+    /// Returns `url` rewritten through the mirror, or `url` itself if no rule matches.
+    pub fn mirror_url(&self, url: &str) -> String {
+        Url::parse(url)
+            .ok()
+            .and_then(|parsed| self.rewrite(&parsed))
+            .map(String::from)
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    /// Sends the request created by `request` to the mirrored url.
+    ///
+    /// Falls back to the original url if no mirror rule matches or the mirror responds
+    /// with `404 Not Found`.
+    pub fn send<F>(&self, url: &Url, request: F) -> reqwest::Result<Response>
+    where
+        F: Fn(&Url) -> RequestBuilder,
+    {
+        if let Some(mirrored) = self.rewrite(url) {
+            debug!("request {} through mirror {}", url, mirrored);
+            let response = request(&mirrored).send()?;
+            if response.status() != StatusCode::NOT_FOUND {
+                return Ok(response);
+            }
+            warn!(
+                "Mirror responded with {} for {}, fall back to {}",
+                response.status(),
+                mirrored,
+                url
+            );
+        }
+        request(url).send()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn empty_mirror_does_not_rewrite() {
+        let mirror = DownloadMirror::new();
+        assert!(mirror
+            .rewrite(&url("https://download.unity3d.com/download_unity/abc/Unity.pkg"))
+            .is_none());
+    }
+
+    #[test]
+    fn base_mirror_replaces_origin() {
+        let mirror = DownloadMirror::new().with_base("https://proxy.example.com/unity/");
+        assert_eq!(
+            mirror.rewrite(&url(
+                "https://download.unity3d.com/download_unity/abc/Unity.pkg?x=1"
+            )),
+            Some(url(
+                "https://proxy.example.com/unity/download_unity/abc/Unity.pkg?x=1"
+            ))
+        );
+    }
+
+    #[test]
+    fn longest_prefix_rule_wins() {
+        let mirror = DownloadMirror::new()
+            .with_base("https://fallback.example.com")
+            .with_rule("https://download.unity3d.com/", "https://proxy.example.com/all/")
+            .with_rule(
+                "https://download.unity3d.com/download_unity/",
+                "https://proxy.example.com/editors/",
+            );
+
+        assert_eq!(
+            mirror.rewrite(&url("https://download.unity3d.com/download_unity/abc/Unity.pkg")),
+            Some(url("https://proxy.example.com/editors/abc/Unity.pkg"))
+        );
+        assert_eq!(
+            mirror.rewrite(&url("https://download.unity3d.com/other/Unity.pkg")),
+            Some(url("https://proxy.example.com/all/other/Unity.pkg"))
+        );
+        assert_eq!(
+            mirror.rewrite(&url("https://go.microsoft.com/fwlink/?linkid=2086937")),
+            Some(url("https://fallback.example.com/fwlink/?linkid=2086937"))
+        );
+    }
+
+    #[test]
+    fn mirror_url_keeps_unmatched_urls() {
+        let mirror = DownloadMirror::new()
+            .with_rule("https://download.unity3d.com/", "https://proxy.example.com/");
+        assert_eq!(
+            mirror.mirror_url("https://download.unity3d.com/eula/android.html"),
+            "https://proxy.example.com/eula/android.html"
+        );
+        assert_eq!(
+            mirror.mirror_url("https://developer.android.com/studio/terms"),
+            "https://developer.android.com/studio/terms"
+        );
+    }
+
+    #[test]
+    fn prefix_rules_without_base_only_rewrite_matches() {
+        let mirror = DownloadMirror::new()
+            .with_rule("https://download.unity3d.com/", "https://proxy.example.com/");
+        assert!(mirror
+            .rewrite(&url("https://go.microsoft.com/fwlink/?linkid=2086937"))
+            .is_none());
+    }
+
+    #[rstest]
+    #[case("https://proxy.example.com", DownloadMirror::new().with_base("https://proxy.example.com"))]
+    #[case(
+        "https://download.unity3d.com/=https://proxy.example.com/unity/",
+        DownloadMirror::new().with_rule("https://download.unity3d.com/", "https://proxy.example.com/unity/")
+    )]
+    #[case(
+        "https://a.com/=https://proxy.example.com/a/, https://proxy.example.com",
+        DownloadMirror::new()
+            .with_rule("https://a.com/", "https://proxy.example.com/a/")
+            .with_base("https://proxy.example.com")
+    )]
+    #[case(
+        "https://download.unity3d.com=https://proxy.example.com/unity?token=abc",
+        DownloadMirror::new().with_rule("https://download.unity3d.com", "https://proxy.example.com/unity?token=abc")
+    )]
+    #[case(
+        "https://a.com/?x=1=https://proxy.example.com/a/",
+        DownloadMirror::new().with_rule("https://a.com/?x=1", "https://proxy.example.com/a/")
+    )]
+    #[case(
+        "https://proxy.example.com/?token=abc",
+        DownloadMirror::new().with_base("https://proxy.example.com/?token=abc")
+    )]
+    #[case("", DownloadMirror::new())]
+    fn parse_mirror_value(#[case] value: &str, #[case] expected: DownloadMirror) {
+        assert_eq!(DownloadMirror::parse(value), expected);
+    }
+
+    #[test]
+    fn parse_mirror_config_file() {
+        let mirror = DownloadMirror::from_json(
+            r#"{"https://download.unity3d.com/": "https://proxy.example.com/unity/"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            mirror,
+            DownloadMirror::new()
+                .with_rule("https://download.unity3d.com/", "https://proxy.example.com/unity/")
+        );
+    }
+}
@@ -1,9 +1,21 @@
+mod download_queue;
 pub mod error;
 pub mod installer;
+pub(crate) mod installer_cache;
 mod loader;
+mod mirror;
+mod retry;
 pub mod utils;
 
+pub(crate) use self::download_queue::DownloadQueue;
 pub use self::loader::{InstallManifest, Loader, ProgressHandler};
+pub use self::mirror::{
+    DownloadMirror, DOWNLOAD_MIRROR_CONFIG_ENV, DOWNLOAD_MIRROR_CONFIG_FILE, DOWNLOAD_MIRROR_ENV,
+};
+pub use self::retry::{
+    RetryPolicy, DEFAULT_DOWNLOAD_RETRIES, DEFAULT_DOWNLOAD_RETRY_BACKOFF,
+    DEFAULT_DOWNLOAD_RETRY_MAX_BACKOFF, DOWNLOAD_RETRIES_ENV, DOWNLOAD_RETRY_BACKOFF_ENV,
+};
 use error::InstallerError;
 use log::error;
 use std::path::Path;
@@ -0,0 +1,157 @@
+use log::{trace, warn};
+use std::env;
+use std::time::Duration;
+
+pub const DOWNLOAD_RETRIES_ENV: &str = "UVM_DOWNLOAD_RETRIES";
+pub const DOWNLOAD_RETRY_BACKOFF_ENV: &str = "UVM_DOWNLOAD_RETRY_BACKOFF";
+pub const DEFAULT_DOWNLOAD_RETRIES: u32 = 3;
+pub const DEFAULT_DOWNLOAD_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+pub const DEFAULT_DOWNLOAD_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Controls how often a failed installer download is retried and how long to wait in between.
+///
+/// Only transient failures are retried: connection errors, `5xx` responses and bodies that end
+/// before all bytes arrived. Each retry resumes from the bytes already on disk. The delay starts
+/// at the initial backoff and doubles with every retry up to the maximum backoff.
+///
+/// # Configuration
+///
+/// [`RetryPolicy::from_env`] reads the retry count from `UVM_DOWNLOAD_RETRIES` and the initial
+/// backoff from `UVM_DOWNLOAD_RETRY_BACKOFF` (a human readable duration like `500ms` or `2s`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_DOWNLOAD_RETRIES,
+            initial_backoff: DEFAULT_DOWNLOAD_RETRY_BACKOFF,
+            max_backoff: DEFAULT_DOWNLOAD_RETRY_MAX_BACKOFF,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            ..Self::default()
+        }
+    }
+
+    /// A policy which gives up after the first failure.
+    pub fn no_retry() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+
+    /// Caps the delay between two retries.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the delay before the retry with the zero based index `retry`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        2u32.checked_pow(retry)
+            .and_then(|factor| self.initial_backoff.checked_mul(factor))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    /// Loads the retry policy from the environment.
+    ///
+    /// Invalid values are logged and replaced with the defaults.
+    pub fn from_env() -> Self {
+        let max_retries = env::var(DOWNLOAD_RETRIES_ENV)
+            .ok()
+            .and_then(|value| {
+                trace!(
+                    "use download retries from {}: {}",
+                    DOWNLOAD_RETRIES_ENV,
+                    value
+                );
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| warn!("Invalid download retry count: {}", value))
+                    .ok()
+            })
+            .unwrap_or(DEFAULT_DOWNLOAD_RETRIES);
+
+        let initial_backoff = env::var(DOWNLOAD_RETRY_BACKOFF_ENV)
+            .ok()
+            .and_then(|value| {
+                trace!(
+                    "use download retry backoff from {}: {}",
+                    DOWNLOAD_RETRY_BACKOFF_ENV,
+                    value
+                );
+                humantime::parse_duration(value.trim())
+                    .map_err(|_| warn!("Invalid download retry backoff: {}", value))
+                    .ok()
+            })
+            .unwrap_or(DEFAULT_DOWNLOAD_RETRY_BACKOFF);
+
+        Self::new(max_retries, initial_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use serial_test::serial;
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 2)]
+    #[case(2, 4)]
+    #[case(5, 32)]
+    #[case(6, 60)]
+    #[case(40, 60)]
+    fn backoff_doubles_up_to_max(#[case] retry: u32, #[case] expected_secs: u64) {
+        let policy = RetryPolicy::new(3, Duration::from_secs(1));
+        assert_eq!(policy.backoff(retry), Duration::from_secs(expected_secs));
+    }
+
+    #[test]
+    fn backoff_respects_custom_max() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(300))
+            .with_max_backoff(Duration::from_millis(500));
+        assert_eq!(policy.backoff(0), Duration::from_millis(300));
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+    }
+
+    #[test]
+    #[serial]
+    fn from_env_reads_retries_and_backoff() {
+        env::set_var(DOWNLOAD_RETRIES_ENV, "5");
+        env::set_var(DOWNLOAD_RETRY_BACKOFF_ENV, "250ms");
+        let policy = RetryPolicy::from_env();
+        env::remove_var(DOWNLOAD_RETRIES_ENV);
+        env::remove_var(DOWNLOAD_RETRY_BACKOFF_ENV);
+
+        assert_eq!(policy, RetryPolicy::new(5, Duration::from_millis(250)));
+    }
+
+    #[test]
+    #[serial]
+    fn from_env_falls_back_to_defaults_for_invalid_values() {
+        env::set_var(DOWNLOAD_RETRIES_ENV, "many");
+        env::set_var(DOWNLOAD_RETRY_BACKOFF_ENV, "soon");
+        let policy = RetryPolicy::from_env();
+        env::remove_var(DOWNLOAD_RETRIES_ENV);
+        env::remove_var(DOWNLOAD_RETRY_BACKOFF_ENV);
+
+        assert_eq!(policy, RetryPolicy::default());
+    }
+}
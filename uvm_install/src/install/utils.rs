@@ -1,5 +1,5 @@
 use cluFlock::{ExclusiveFlock, FlockLock};
-use reqwest::blocking::Client;
+use crate::install::DownloadMirror;
 use reqwest::header::{USER_AGENT, CONTENT_DISPOSITION};
 use reqwest::Url;
 use std::fs::File;
@@ -8,6 +8,7 @@ use std::path::Path;
 #[cfg(windows)]
 use std::path::{Component, Prefix, PathBuf};
 use log::{debug, error, trace};
+use uvm_live_platform::http_client;
 
 pub fn lock_process_or_wait(lock_file: &File) -> io::Result<FlockLock<&File>> {
     match ExclusiveFlock::try_lock(lock_file) {
@@ -74,16 +75,22 @@ pub fn prepend_long_path_support<P:AsRef<Path>>(path:P) -> PathBuf {
 
 pub struct UrlUtils {}
 
+/// Sent instead of the configured user agent when resolving installer file names,
+/// as the download servers only answer browsers with a `Content-Disposition` header.
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_13_6) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/69.0.3497.100 Safari/537.36";
+
 impl UrlUtils {
-    fn get_final_file_name_from_url(url: &Url) -> io::Result<String> {
-        let client = Client::new();
-        let response = client
-            .head(url.clone())
-            .header(USER_AGENT, "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_13_6) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/69.0.3497.100 Safari/537.36")
-            .send()
-            .map_err(|err| {
-                io::Error::new(io::ErrorKind::Other, err)
-            })?;
+    fn get_final_file_name_from_url(url: &Url, mirror: Option<&DownloadMirror>) -> io::Result<String> {
+        let client = http_client::client()
+            .map_err(io::Error::other)?;
+        let request = |url: &Url| client.head(url.clone()).header(USER_AGENT, BROWSER_USER_AGENT);
+        let response = match mirror {
+            Some(mirror) => mirror.send(url, request),
+            None => request(url).send(),
+        }
+        .map_err(|err| {
+            io::Error::new(io::ErrorKind::Other, err)
+        })?;
 
         response
             .headers()
@@ -131,7 +138,9 @@ impl UrlUtils {
             })
     }
 
-    pub fn get_file_name_from_url(url: &Url) -> io::Result<String> {
+    /// Returns the installer file name of `url`. Urls without a file name are resolved with a
+    /// request, sent through the given mirror.
+    pub fn get_file_name_from_url_with_mirror(url: &Url, mirror: Option<&DownloadMirror>) -> io::Result<String> {
         let test_path = Path::new(url.as_ref());
         if test_path.extension().is_some() {
             url.as_str()
@@ -145,7 +154,7 @@ impl UrlUtils {
                     )
                 })
         } else {
-            Self::get_final_file_name_from_url(url)
+            Self::get_final_file_name_from_url(url, mirror)
         }
     }
 }
@@ -158,25 +167,25 @@ mod tests {
     #[test]
     fn parse_file_name_from_url_with_file_name_part() {
         let url = Url::parse("https://beta.unity3d.com/download/8ea4afdbfa47/MacEditorTargetInstaller/UnitySetup-Android-Support-for-Editor-2019.3.0a8.pkg").unwrap();
-        assert_eq!(UrlUtils::get_file_name_from_url(&url).unwrap(), "UnitySetup-Android-Support-for-Editor-2019.3.0a8.pkg".to_string());
+        assert_eq!(UrlUtils::get_file_name_from_url_with_mirror(&url, None).unwrap(), "UnitySetup-Android-Support-for-Editor-2019.3.0a8.pkg".to_string());
     }
 
     #[test]
     fn parse_file_name_from_url_without_file_name_part_and_content_disposition() {
         let url = Url::parse("https://go.microsoft.com/fwlink/?linkid=2086937").unwrap();
-        assert!(UrlUtils::get_file_name_from_url(&url).unwrap().starts_with("visualstudioformac-"));
+        assert!(UrlUtils::get_file_name_from_url_with_mirror(&url, None).unwrap().starts_with("visualstudioformac-"));
     }
 
     #[test]
     fn parse_file_name_from_url_without_file_name_part_and_content_disposition2() {
         let url = Url::parse("https://go.microsoft.com/fwlink/?linkid=2087047").unwrap();
-        assert!(UrlUtils::get_file_name_from_url(&url).unwrap().starts_with("monoframework-mdk-"));
+        assert!(UrlUtils::get_file_name_from_url_with_mirror(&url, None).unwrap().starts_with("monoframework-mdk-"));
     }
 
     #[test]
     fn parse_file_name_from_url_without_file_name_part_and_content_disposition3() {
         let url = Url::parse("https://new-translate.unity3d.jp/v1/live/54/2019.3/zh-hant").unwrap();
-        assert_eq!(UrlUtils::get_file_name_from_url(&url).unwrap(), "zh-hant.po".to_string());
+        assert_eq!(UrlUtils::get_file_name_from_url_with_mirror(&url, None).unwrap(), "zh-hant.po".to_string());
     }
 
     #[cfg(windows)]
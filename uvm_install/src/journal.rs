@@ -0,0 +1,504 @@
+//! A per-installation journal of component state transitions.
+//!
+//! The journal is written next to `modules.json` while components are installed. Every
+//! transition is appended as one JSON line, so a journal of an interrupted installation
+//! tells which components were only partially extracted. [`rollback`] removes these
+//! components again and corrects `modules.json`, after which an installation can resume.
+use crate::error::Result;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use unity_hub::unity::hub::module::Module;
+
+pub const JOURNAL_FILE_NAME: &str = "uvm-install.journal";
+
+/// The editor component id used in the journal.
+const EDITOR_COMPONENT: &str = "Unity";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentState {
+    Downloaded,
+    Extracting,
+    Installed,
+    Failed,
+}
+
+impl ComponentState {
+    /// Returns `true` if a component in this state may have left partial files behind.
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, ComponentState::Extracting | ComponentState::Failed)
+    }
+}
+
+/// One state transition of a component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub component: String,
+    pub state: ComponentState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<PathBuf>,
+    /// Seconds since the unix epoch.
+    pub time: u64,
+}
+
+#[derive(Debug)]
+pub struct InstallJournal {
+    path: PathBuf,
+    records: Vec<JournalRecord>,
+}
+
+impl InstallJournal {
+    /// Opens the journal of the installation at `base_dir`.
+    ///
+    /// A missing journal is empty. A truncated last line, e.g. from a crash while writing,
+    /// is ignored.
+    pub fn open<P: AsRef<Path>>(base_dir: P) -> io::Result<Self> {
+        let path = base_dir.as_ref().join(JOURNAL_FILE_NAME);
+        let mut records = Vec::new();
+        if path.exists() {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                match serde_json::from_str::<JournalRecord>(&line) {
+                    Ok(record) => records.push(record),
+                    Err(e) => debug!("skip invalid journal line '{}': {}", line, e),
+                }
+            }
+        }
+        Ok(Self { path, records })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn records(&self) -> &[JournalRecord] {
+        &self.records
+    }
+
+    /// The latest state of `component`.
+    pub fn state(&self, component: &str) -> Option<ComponentState> {
+        self.latest(component).map(|record| record.state)
+    }
+
+    /// The latest record of every component which didn't finish its installation.
+    pub fn incomplete(&self) -> Vec<&JournalRecord> {
+        let mut incomplete: Vec<&JournalRecord> = Vec::new();
+        for record in &self.records {
+            if incomplete.iter().any(|r| r.component == record.component) {
+                continue;
+            }
+            if let Some(latest) = self.latest(&record.component) {
+                if latest.state.is_incomplete() {
+                    incomplete.push(latest);
+                }
+            }
+        }
+        incomplete
+    }
+
+    /// Appends a state transition of `component` and syncs it to disk.
+    pub fn record(
+        &mut self,
+        component: &str,
+        state: ComponentState,
+        destination: Option<&Path>,
+    ) -> io::Result<()> {
+        let record = JournalRecord {
+            component: component.to_string(),
+            state,
+            destination: destination.map(Path::to_path_buf),
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+        };
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        file.sync_data()?;
+        self.records.push(record);
+        Ok(())
+    }
+
+    /// Deletes the journal once the installation is consistent.
+    pub fn remove(self) -> io::Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    fn latest(&self, component: &str) -> Option<&JournalRecord> {
+        self.records
+            .iter()
+            .rev()
+            .find(|record| record.component == component)
+    }
+}
+
+/// A component removed by [`rollback`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolledBackComponent {
+    pub component: String,
+    pub state: ComponentState,
+    /// The removed directory, if the component had files on disk.
+    pub removed: Option<PathBuf>,
+}
+
+/// Rolls back every incomplete component recorded in the journal of the installation at
+/// `base_dir`.
+///
+/// Partially extracted module directories are removed and the modules are flagged as not
+/// installed in `modules.json`. An incomplete editor removes the whole installation. The
+/// journal is deleted afterwards.
+pub fn rollback<P: AsRef<Path>>(base_dir: P) -> Result<Vec<RolledBackComponent>> {
+    let base_dir = base_dir.as_ref();
+    let journal = InstallJournal::open(base_dir)?;
+    let incomplete: Vec<RolledBackComponent> = journal
+        .incomplete()
+        .into_iter()
+        .map(|record| RolledBackComponent {
+            component: record.component.clone(),
+            state: record.state,
+            removed: None,
+        })
+        .collect();
+    if incomplete.is_empty() {
+        return Ok(incomplete);
+    }
+
+    if let Some(editor) = incomplete
+        .iter()
+        .find(|component| component.component == EDITOR_COMPONENT)
+    {
+        info!(
+            "editor installation at {} is incomplete, remove it",
+            base_dir.display()
+        );
+        fs::remove_dir_all(base_dir)?;
+        return Ok(vec![RolledBackComponent {
+            removed: Some(base_dir.to_path_buf()),
+            ..editor.clone()
+        }]);
+    }
+
+    let modules_json = base_dir.join("modules.json");
+    let mut modules: Option<Vec<Module>> = if modules_json.exists() {
+        Some(serde_json::from_str(&fs::read_to_string(&modules_json)?).map_err(io::Error::from)?)
+    } else {
+        None
+    };
+
+    // Destinations of the installed components, including modules of earlier runs which are
+    // only flagged installed in modules.json
+    let is_rolled_back = |id: &str| incomplete.iter().any(|c| c.component == id);
+    let installed: Vec<PathBuf> = journal
+        .records()
+        .iter()
+        .filter(|record| record.state == ComponentState::Installed)
+        .filter(|record| !is_rolled_back(&record.component))
+        .filter_map(|record| record.destination.clone())
+        .chain(
+            modules
+                .iter()
+                .flatten()
+                .filter(|module| module.is_installed && !is_rolled_back(module.id()))
+                .filter_map(|module| module.install_path(base_dir)),
+        )
+        .collect();
+
+    let mut rolled_back = Vec::new();
+    for mut component in incomplete {
+        let destination = journal
+            .latest(&component.component)
+            .and_then(|record| record.destination.as_deref());
+        if let Some(destination) = destination.filter(|d| is_removable(d, base_dir, &installed)) {
+            if destination.exists() {
+                info!(
+                    "remove partially installed {} at {}",
+                    component.component,
+                    destination.display()
+                );
+                if destination.is_dir() {
+                    fs::remove_dir_all(destination)?;
+                } else {
+                    fs::remove_file(destination)?;
+                }
+                component.removed = Some(destination.to_path_buf());
+            }
+        } else if let Some(destination) = destination {
+            warn!(
+                "keep {} of {}, it is shared or outside of the installation",
+                destination.display(),
+                component.component
+            );
+        }
+        rolled_back.push(component);
+    }
+
+    if let Some(modules) = modules.as_mut() {
+        for module in modules.iter_mut() {
+            if rolled_back.iter().any(|c| c.component == module.id()) {
+                module.is_installed = false;
+            }
+        }
+        crate::write_modules_json(base_dir, modules);
+    }
+
+    journal.remove()?;
+    Ok(rolled_back)
+}
+
+/// Editor directories some modules extract into, e.g. the documentation.
+const SHARED_EDITOR_DIRS: [&str; 2] = ["Editor/Data", "Unity.app/Contents"];
+
+/// Only directories inside the installation are removed. Directories shared with the editor
+/// or holding an `installed` component, e.g. the common localization directory of all language
+/// packs, are kept.
+fn is_removable(destination: &Path, base_dir: &Path, installed: &[PathBuf]) -> bool {
+    destination.starts_with(base_dir)
+        && SHARED_EDITOR_DIRS
+            .iter()
+            .all(|dir| !base_dir.join(dir).starts_with(destination))
+        && !installed.iter().any(|other| other.starts_with(destination))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn module_json(id: &str, is_installed: bool) -> String {
+        format!(
+            r#"{{"id": "{id}", "name": "{id}", "description": "{id}", "category": "Platforms",
+            "downloadSize": 100, "installedSize": 200, "required": false, "hidden": false,
+            "url": "https://example.com/{id}.pkg", "isInstalled": {is_installed}}}"#
+        )
+    }
+
+    #[test]
+    fn records_state_transitions() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut journal = InstallJournal::open(temp_dir.path()).unwrap();
+        journal
+            .record("android", ComponentState::Downloaded, None)
+            .unwrap();
+        journal
+            .record("android", ComponentState::Extracting, None)
+            .unwrap();
+        journal
+            .record("ios", ComponentState::Downloaded, None)
+            .unwrap();
+        journal
+            .record("ios", ComponentState::Extracting, None)
+            .unwrap();
+        journal
+            .record("ios", ComponentState::Installed, None)
+            .unwrap();
+
+        let journal = InstallJournal::open(temp_dir.path()).unwrap();
+        assert_eq!(journal.records().len(), 5);
+        assert_eq!(journal.state("android"), Some(ComponentState::Extracting));
+        assert_eq!(journal.state("ios"), Some(ComponentState::Installed));
+        assert_eq!(journal.state("webgl"), None);
+        let incomplete: Vec<&str> = journal
+            .incomplete()
+            .iter()
+            .map(|record| record.component.as_str())
+            .collect();
+        assert_eq!(incomplete, vec!["android"]);
+    }
+
+    #[test]
+    fn ignores_truncated_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut journal = InstallJournal::open(temp_dir.path()).unwrap();
+        journal
+            .record("ios", ComponentState::Installed, None)
+            .unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(journal.path())
+            .unwrap();
+        file.write_all(b"{\"component\":\"andr").unwrap();
+
+        let journal = InstallJournal::open(temp_dir.path()).unwrap();
+        assert_eq!(journal.records().len(), 1);
+        assert!(journal.incomplete().is_empty());
+    }
+
+    #[test]
+    fn rollback_removes_partially_extracted_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = temp_dir.path();
+        let android = base_dir.join("Editor/Data/PlaybackEngines/AndroidPlayer");
+        let ios = base_dir.join("Editor/Data/PlaybackEngines/iOSSupport");
+        fs::create_dir_all(&android).unwrap();
+        fs::create_dir_all(&ios).unwrap();
+        fs::write(
+            base_dir.join("modules.json"),
+            format!(
+                "[{}, {}]",
+                module_json("android", true),
+                module_json("ios", true)
+            ),
+        )
+        .unwrap();
+
+        let mut journal = InstallJournal::open(base_dir).unwrap();
+        journal
+            .record("Unity", ComponentState::Installed, Some(base_dir))
+            .unwrap();
+        journal
+            .record("ios", ComponentState::Installed, Some(&ios))
+            .unwrap();
+        journal
+            .record("android", ComponentState::Extracting, Some(&android))
+            .unwrap();
+
+        let rolled_back = rollback(base_dir).unwrap();
+        assert_eq!(
+            rolled_back,
+            vec![RolledBackComponent {
+                component: "android".to_string(),
+                state: ComponentState::Extracting,
+                removed: Some(android.clone()),
+            }]
+        );
+        assert!(!android.exists());
+        assert!(ios.exists());
+        assert!(!base_dir.join(JOURNAL_FILE_NAME).exists());
+
+        let modules: Vec<Module> =
+            serde_json::from_str(&fs::read_to_string(base_dir.join("modules.json")).unwrap())
+                .unwrap();
+        assert!(!modules[0].is_installed, "android should not be installed");
+        assert!(modules[1].is_installed, "ios should stay installed");
+    }
+
+    #[test]
+    fn rollback_removes_incomplete_editor() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = temp_dir.path().join("2022.3.10f1");
+        fs::create_dir_all(base_dir.join("Editor")).unwrap();
+        let mut journal = InstallJournal::open(&base_dir).unwrap();
+        journal
+            .record("Unity", ComponentState::Extracting, Some(&base_dir))
+            .unwrap();
+
+        let rolled_back = rollback(&base_dir).unwrap();
+        assert_eq!(rolled_back.len(), 1);
+        assert_eq!(rolled_back[0].removed, Some(base_dir.clone()));
+        assert!(!base_dir.exists());
+    }
+
+    #[test]
+    fn rollback_keeps_destinations_outside_of_installation() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = temp_dir.path().join("2022.3.10f1");
+        let outside = temp_dir.path().join("sdk");
+        fs::create_dir_all(&base_dir).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        let mut journal = InstallJournal::open(&base_dir).unwrap();
+        journal
+            .record("android-sdk", ComponentState::Failed, Some(&outside))
+            .unwrap();
+
+        let rolled_back = rollback(&base_dir).unwrap();
+        assert_eq!(rolled_back[0].removed, None);
+        assert!(outside.exists());
+    }
+
+    #[test]
+    fn rollback_keeps_shared_editor_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = temp_dir.path();
+        let data = base_dir.join("Editor/Data");
+        fs::create_dir_all(&data).unwrap();
+        let mut journal = InstallJournal::open(base_dir).unwrap();
+        journal
+            .record("documentation", ComponentState::Extracting, Some(&data))
+            .unwrap();
+
+        let rolled_back = rollback(base_dir).unwrap();
+        assert_eq!(rolled_back[0].removed, None);
+        assert!(data.exists());
+    }
+
+    #[test]
+    fn rollback_keeps_destination_shared_with_installed_components() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_dir = temp_dir.path();
+        let localization = base_dir.join("Unity.app/Contents/Localization");
+        fs::create_dir_all(&localization).unwrap();
+        fs::write(localization.join("ja.po"), "").unwrap();
+        fs::write(localization.join("ko.po"), "").unwrap();
+        fs::write(localization.join("zh-hans.po"), "").unwrap();
+        let language_pack = |id: &str, is_installed: bool| {
+            format!(
+                r#"{{"id": "{id}", "name": "{id}", "description": "{id}", "category": "Language packs",
+                "downloadSize": 100, "installedSize": 200, "required": false, "hidden": false,
+                "url": "https://example.com/{id}.po", "isInstalled": {is_installed},
+                "destination": "{{UNITY_PATH}}/Unity.app/Contents/Localization"}}"#
+            )
+        };
+        // ja was installed by an earlier run, zh-hans by this one
+        fs::write(
+            base_dir.join("modules.json"),
+            format!(
+                "[{}, {}, {}]",
+                language_pack("language-ja", true),
+                language_pack("language-ko", false),
+                language_pack("language-zh-hans", true)
+            ),
+        )
+        .unwrap();
+
+        let mut journal = InstallJournal::open(base_dir).unwrap();
+        journal
+            .record(
+                "language-zh-hans",
+                ComponentState::Installed,
+                Some(&localization),
+            )
+            .unwrap();
+        journal
+            .record("language-ko", ComponentState::Failed, Some(&localization))
+            .unwrap();
+
+        let rolled_back = rollback(base_dir).unwrap();
+        assert_eq!(rolled_back.len(), 1);
+        assert_eq!(rolled_back[0].component, "language-ko");
+        assert_eq!(rolled_back[0].removed, None);
+        assert!(localization.join("ja.po").exists());
+        assert!(localization.join("zh-hans.po").exists());
+
+        // without the journal entry the pack installed by an earlier run still protects it
+        let mut journal = InstallJournal::open(base_dir).unwrap();
+        journal
+            .record(
+                "language-ko",
+                ComponentState::Extracting,
+                Some(&localization),
+            )
+            .unwrap();
+        let rolled_back = rollback(base_dir).unwrap();
+        assert_eq!(rolled_back[0].removed, None);
+        assert!(localization.join("ja.po").exists());
+    }
+
+    #[test]
+    fn rollback_without_journal_does_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(rollback(temp_dir.path()).unwrap().is_empty());
+    }
+}
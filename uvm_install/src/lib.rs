@@ -1,32 +1,58 @@
+mod bundle;
 mod error;
+mod eula;
 mod install;
+mod journal;
+mod plan;
+mod requirement;
+mod space;
 mod sys;
 use crate::error::InstallError::{InstallFailed, InstallerCreatedFailed, LoadingInstallerFailed};
+pub use bundle::{BundleInstaller, BundleManifest, BundleOptions};
 pub use error::*;
+pub use eula::{
+    accepted_eulas, record_accepted_eulas, AcceptedEula, EulaAcceptance, EULA_FILE_NAME,
+};
 use install::utils;
 pub use install::ProgressHandler;
-use install::{InstallManifest, Loader};
+pub use journal::{
+    rollback, ComponentState, InstallJournal, JournalRecord, RolledBackComponent, JOURNAL_FILE_NAME,
+};
+pub use plan::{ComponentStatus, InstallPlan, PlannedComponent, PlannedEula};
+pub use requirement::{VersionRequirement, VersionRequirementError};
+pub use space::SpaceRequirement;
+pub use install::{
+    DownloadMirror, DOWNLOAD_MIRROR_CONFIG_ENV, DOWNLOAD_MIRROR_CONFIG_FILE, DOWNLOAD_MIRROR_ENV,
+};
+pub use install::{
+    RetryPolicy, DEFAULT_DOWNLOAD_RETRIES, DEFAULT_DOWNLOAD_RETRY_BACKOFF,
+    DEFAULT_DOWNLOAD_RETRY_MAX_BACKOFF, DOWNLOAD_RETRIES_ENV, DOWNLOAD_RETRY_BACKOFF_ENV,
+};
+use install::{installer_cache, DownloadQueue, InstallManifest, Loader};
 use lazy_static::lazy_static;
-use log::{debug, info, trace};
+use log::{debug, info, trace, warn};
 use ssri::Integrity;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Display;
 use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
-use std::{fs, io};
+use std::sync::Mutex;
+use std::{fs, io, thread};
 use sys::create_installer;
 use unity_hub::unity::hub;
 use unity_hub::unity::hub::editors::EditorInstallation;
 use unity_hub::unity::hub::module::Module;
-use unity_hub::unity::hub::paths;
 use unity_hub::unity::hub::paths::locks_dir;
 use unity_hub::unity::{Installation, UnityInstallation};
-use unity_version::Version;
+use unity_version::{RevisionHash, Version};
 use uvm_install_graph::{InstallGraph, InstallStatus, UnityComponent, Walker};
 use uvm_live_platform::error::ErrorRepr;
 use uvm_live_platform::error::LivePlatformError;
-use uvm_live_platform::{FetchRelease, UnityReleaseDownloadArchitecture};
+use uvm_live_platform::{
+    FetchRelease, Release, UnityReleaseDownloadArchitecture, UnityReleaseEntitlement,
+    UnityReleaseStream,
+};
 
 lazy_static! {
     static ref UNITY_BASE_PATTERN: &'static Path = Path::new("{UNITY_PATH}");
@@ -158,6 +184,36 @@ pub fn ensure_installation_architecture_is_correct<I: Installation>(
     Ok(true)
 }
 
+/// Returns the architecture of the editor executable of `installation`.
+///
+/// Universal binaries report the architecture of the host if they contain it.
+pub fn installation_architecture<I: Installation>(installation: &I) -> Option<InstallArchitecture> {
+    let architectures = sys::binary_architectures(installation.exec_path())
+        .map_err(|e| debug!("unable to read the editor architecture: {}", e))
+        .ok()?;
+    let host = InstallArchitecture::default();
+    if architectures.contains(&host) {
+        Some(host)
+    } else {
+        architectures.first().copied()
+    }
+}
+
+/// Returns the revision of `version` recorded in the cached release manifest, if any.
+///
+/// Unity installations don't record their revision, but a version is released with a single
+/// revision, so this is the revision of an installation of `version` installed by uvm.
+pub fn cached_release_revision(
+    version: &Version,
+    architecture: InstallArchitecture,
+) -> Option<String> {
+    installer_cache::load_release(&version.to_string(), architecture.into())
+        .map_err(|e| debug!("unable to load the cached release of {}: {}", version, e))
+        .ok()
+        .flatten()
+        .map(|release| release.short_revision)
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum InstallArchitecture {
@@ -196,27 +252,87 @@ impl Display for InstallArchitecture {
     }
 }
 
+/// Decides whether an EULA is accepted, e.g. by asking the user. See [`InstallOptions::with_eula_prompt`].
+pub type EulaPrompt = dyn Fn(&PlannedComponent, &PlannedEula) -> bool;
+
+/// The number of installers downloaded at the same time when not configured otherwise.
+pub const DEFAULT_MAX_PARALLEL_DOWNLOADS: usize = 1;
+
 pub struct InstallOptions {
-    version: Version,
+    requirement: VersionRequirement,
+    release_streams: Vec<UnityReleaseStream>,
+    entitlements: Vec<UnityReleaseEntitlement>,
+    revision: Option<RevisionHash>,
     requested_modules: HashSet<String>,
     install_sync: bool,
     destination: Option<PathBuf>,
     architecture: Option<InstallArchitecture>,
+    max_parallel_downloads: usize,
+    download_mirror: Option<DownloadMirror>,
+    retry_policy: Option<RetryPolicy>,
+    offline: bool,
+    bundle: Option<PathBuf>,
+    force: bool,
+    eula_acceptance: EulaAcceptance,
+    eula_prompt: Option<Box<EulaPrompt>>,
     progress_handler: Option<Box<dyn install::ProgressHandler>>,
 }
 
 impl InstallOptions {
-    pub fn new<V: Into<Version>>(version: V) -> Self {
+    /// Creates options to install `version`, which may also be a [`VersionRequirement`].
+    ///
+    /// Requirements are resolved against the releases of the Unity live platform before
+    /// anything is installed.
+    pub fn new<V: Into<VersionRequirement>>(version: V) -> Self {
         Self {
-            version: version.into(),
+            requirement: version.into(),
+            release_streams: Vec::new(),
+            entitlements: Vec::new(),
+            revision: None,
             requested_modules: HashSet::new(),
             install_sync: false,
             destination: None,
             architecture: None,
+            max_parallel_downloads: DEFAULT_MAX_PARALLEL_DOWNLOADS,
+            download_mirror: None,
+            retry_policy: None,
+            offline: false,
+            bundle: None,
+            force: false,
+            eula_acceptance: EulaAcceptance::None,
+            eula_prompt: None,
             progress_handler: None,
         }
     }
 
+    /// Restricts the resolution of a version requirement to releases of these streams.
+    pub fn with_release_streams<I: IntoIterator<Item = UnityReleaseStream>>(
+        mut self,
+        streams: I,
+    ) -> Self {
+        self.release_streams = streams.into_iter().collect();
+        self
+    }
+
+    /// Restricts the resolution of a version requirement to releases with these entitlements.
+    pub fn with_entitlements<I: IntoIterator<Item = UnityReleaseEntitlement>>(
+        mut self,
+        entitlements: I,
+    ) -> Self {
+        self.entitlements = entitlements.into_iter().collect();
+        self
+    }
+
+    /// Requires the release to be built from `revision`.
+    ///
+    /// The installation fails before anything is downloaded when the release for the
+    /// requested version has a different revision hash, e.g. when a project pins
+    /// `m_EditorVersionWithRevision`.
+    pub fn with_revision(mut self, revision: RevisionHash) -> Self {
+        self.revision = Some(revision);
+        self
+    }
+
     pub fn with_requested_modules<S: Into<String>, I: IntoIterator<Item = S>>(
         mut self,
         requested_modules: I,
@@ -240,6 +356,78 @@ impl InstallOptions {
         self
     }
 
+    /// Sets how many installers may be downloaded at the same time.
+    ///
+    /// Installation itself still happens one component at a time in dependency order.
+    /// A value of `0` is treated as `1`.
+    pub fn with_max_parallel_downloads(mut self, max_parallel_downloads: usize) -> Self {
+        self.max_parallel_downloads = max_parallel_downloads.max(1);
+        self
+    }
+
+    /// Sets the mirror used to rewrite installer download urls.
+    ///
+    /// Defaults to the mirror configured via `UVM_DOWNLOAD_MIRROR` or the mirror config file.
+    pub fn with_download_mirror(mut self, mirror: DownloadMirror) -> Self {
+        self.download_mirror = Some(mirror);
+        self
+    }
+
+    /// Sets how failed installer downloads are retried.
+    ///
+    /// Defaults to the policy configured via `UVM_DOWNLOAD_RETRIES` and `UVM_DOWNLOAD_RETRY_BACKOFF`.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Install from the installer cache without network access.
+    ///
+    /// The release manifest and all required installers must have been cached by a
+    /// previous online installation of the same version and architecture.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Install from a bundle created with [`BundleOptions::create`].
+    ///
+    /// The bundle is imported into the installer cache and installed offline.
+    pub fn with_bundle<P: AsRef<Path>>(mut self, bundle: P) -> Self {
+        self.bundle = Some(bundle.as_ref().to_path_buf());
+        self
+    }
+
+    /// Install even if the cache or destination volume has not enough free space.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Accept the EULAs of the components to install.
+    ///
+    /// Components with an EULA which is not accepted fail the installation before anything
+    /// is downloaded, unless the EULA was accepted by a previous installation.
+    pub fn with_eula_acceptance(mut self, eula_acceptance: EulaAcceptance) -> Self {
+        self.eula_acceptance = eula_acceptance;
+        self
+    }
+
+    pub fn eula_acceptance(&self) -> &EulaAcceptance {
+        &self.eula_acceptance
+    }
+
+    /// Asks `prompt` for every EULA of a component to install which is neither accepted by the
+    /// [`EulaAcceptance`] nor by a previous installation. The first declined EULA fails the
+    /// installation with [`InstallError::EulaNotAccepted`].
+    pub fn with_eula_prompt<F>(mut self, prompt: F) -> Self
+    where
+        F: Fn(&PlannedComponent, &PlannedEula) -> bool + 'static,
+    {
+        self.eula_prompt = Some(Box::new(prompt));
+        self
+    }
+
     pub fn with_progress_handler<P: install::ProgressHandler + 'static>(
         mut self,
         handler: P,
@@ -263,8 +451,126 @@ impl InstallOptions {
             .unwrap_or_default()
     }
 
+    /// Resolves the requested version requirement to the version which will be installed.
+    pub fn resolve_version(&self) -> Result<Version> {
+        if self.offline || self.bundle.is_some() {
+            return self
+                .requirement
+                .exact()
+                .cloned()
+                .ok_or_else(|| InstallError::OfflineVersionRequirement(self.requirement.to_string()));
+        }
+        self.requirement
+            .resolve(&self.release_streams, &self.entitlements)
+    }
+
+    /// Computes what [`InstallOptions::install`] would do without touching the filesystem.
+    ///
+    /// The plan lists every component of the installation with its install status, sizes,
+    /// destination and the EULAs which have to be accepted for missing components.
+    pub fn plan(&self) -> Result<InstallPlan> {
+        let version = &self.resolve_version()?;
+        let architecture = self.release_architecture();
+        let unity_release = match &self.bundle {
+            Some(bundle) => BundleManifest::load_release(bundle)?,
+            None => Self::load_release(version, architecture, self.offline)?,
+        };
+        self.verify_revision(&unity_release)?;
+
+        let mut graph = InstallGraph::from(&unity_release);
+        let base_dir = self.base_dir(version)?;
+        let installation = UnityInstallation::new(&base_dir).ok();
+        let reinstall =
+            mark_installed_components(&mut graph, installation.as_ref(), self.architecture)?;
+
+        let mut all_components =
+            resolve_components(&graph, &self.requested_modules, self.install_sync, version)?;
+        all_components.extend(reinstall.unwrap_or_default());
+        graph.keep(&all_components);
+
+        Ok(InstallPlan::from_graph(
+            &graph,
+            architecture,
+            &base_dir,
+            &self.download_mirror(),
+        ))
+    }
+
+    fn download_mirror(&self) -> DownloadMirror {
+        self.download_mirror
+            .clone()
+            .unwrap_or_else(DownloadMirror::from_env)
+    }
+
+    fn release_architecture(&self) -> UnityReleaseDownloadArchitecture {
+        self.architecture
+            .unwrap_or(InstallArchitecture::X86_64)
+            .into()
+    }
+
+    fn load_release(
+        version: &Version,
+        architecture: UnityReleaseDownloadArchitecture,
+        offline: bool,
+    ) -> Result<Release> {
+        if offline {
+            let version_string = version.to_string();
+            return installer_cache::load_release(&version_string, architecture)?.ok_or_else(
+                || InstallError::OfflineReleaseNotCached(version_string, architecture.to_string()),
+            );
+        }
+        let release = FetchRelease::builder(version.to_owned())
+            .with_current_platform()
+            .with_extended_lts()
+            .with_u7_alpha()
+            .with_architecture(architecture)
+            .fetch()
+            .map_err(|e| {
+                let e = ErrorRepr::FetchReleaseError(e);
+                LivePlatformError::new("Failed to fetch release", e)
+            })?;
+        Ok(release)
+    }
+
+    fn verify_revision(&self, release: &Release) -> Result<()> {
+        match &self.revision {
+            Some(revision) => {
+                verify_release_revision(&release.version, &release.short_revision, revision)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The installation directory, either the requested destination or the Unity Hub
+    /// install path.
+    fn base_dir(&self, version: &Version) -> Result<PathBuf> {
+        if let Some(destination) = &self.destination {
+            if destination.exists() && !destination.is_dir() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Requested destination is not a directory.",
+                )
+                .into());
+            }
+            return Ok(destination.to_path_buf());
+        }
+        Ok(hub::paths::install_path()
+            .map(|path| path.join(format!("{}", version)))
+            .or_else(|| {
+                {
+                    #[cfg(any(target_os = "windows", target_os = "macos"))]
+                    let application_path = dirs_2::application_dir();
+                    #[cfg(target_os = "linux")]
+                    let application_path = dirs_2::executable_dir();
+                    application_path
+                }
+                .map(|path| path.join(format!("Unity-{}", version)))
+            })
+            .expect("default installation directory"))
+    }
+
     pub fn install(&self) -> Result<UnityInstallation> {
-        let version = &self.version;
+        let version = &self.resolve_version()?;
         let version_string = version.to_string();
 
         let locks_dir = locks_dir().ok_or_else(|| {
@@ -276,30 +582,24 @@ impl InstallOptions {
 
         fs::DirBuilder::new().recursive(true).create(&locks_dir)?;
         lock_process!(locks_dir.join(format!("{}.lock", version_string)));
-        let architecture: UnityReleaseDownloadArchitecture = self
-            .architecture
-            .clone()
-            .unwrap_or(InstallArchitecture::X86_64)
-            .into();
+        let architecture = self.release_architecture();
 
         // Show spinner for metadata fetch if progress handler is available
         if let Some(ref handler) = self.progress_handler {
             handler.set_message("Fetching Unity version metadata...");
         }
 
-        let unity_release = FetchRelease::builder(version.to_owned())
-            .with_current_platform()
-            .with_extended_lts()
-            .with_u7_alpha()
-            .with_architecture(architecture)
-            .fetch()
-            .map_err(|e| {
-                let e = ErrorRepr::FetchReleaseError(e);
-                LivePlatformError::new("Failed to fetch release", e)
-            })?;
+        if let Some(bundle) = &self.bundle {
+            info!("import install bundle {}", bundle.display());
+            bundle::import_bundle(bundle, version, architecture)?;
+        }
+        let offline = self.offline || self.bundle.is_some();
+
+        let unity_release = Self::load_release(version, architecture, offline)?;
 
         //let unity_release = fetch_release(version.to_owned())?;
         print_release_info(&unity_release);
+        self.verify_revision(&unity_release)?;
 
         // Show spinner for dependency resolution
         if let Some(ref handler) = self.progress_handler {
@@ -309,131 +609,49 @@ impl InstallOptions {
         let mut graph = InstallGraph::from(&unity_release);
         //
 
-        let mut editor_installation: Option<EditorInstallation> = None;
-        let base_dir = if let Some(destination) = &self.destination {
-            if destination.exists() && !destination.is_dir() {
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    "Requested destination is not a directory.",
-                )
-                .into());
-            }
-
-            editor_installation = Some(EditorInstallation::new(
-                version.to_owned(),
-                destination.to_path_buf(),
-            ));
-            destination.to_path_buf()
-        } else {
-            hub::paths::install_path()
-                .map(|path| path.join(format!("{}", version)))
-                .or_else(|| {
-                    {
-                        #[cfg(any(target_os = "windows", target_os = "macos"))]
-                        let application_path = dirs_2::application_dir();
-                        #[cfg(target_os = "linux")]
-                        let application_path = dirs_2::executable_dir();
-                        application_path
-                    }
-                    .map(|path| path.join(format!("Unity-{}", version)))
-                })
-                .expect("default installation directory")
-        };
+        let base_dir = self.base_dir(version)?;
+        let editor_installation = self
+            .destination
+            .as_ref()
+            .map(|destination| EditorInstallation::new(version.to_owned(), destination.to_path_buf()));
         let mut additional_modules = vec![];
+        // A previous run may have been interrupted while extracting a component. Remove its
+        // leftovers so that the component is installed again.
+        for component in rollback(&base_dir)? {
+            info!(
+                "Rolled back incomplete {} ({:?})",
+                component.component, component.state
+            );
+        }
         let installation = UnityInstallation::new(&base_dir);
-        if let Ok(ref installation) = installation {
-            info!("Installation found at {}", installation.path().display());
-            if ensure_installation_architecture_is_correct(installation)? {
-                let modules = installation.installed_modules()?;
-                let mut module_ids: HashSet<String> =
-                    modules.into_iter().map(|m| m.id().to_string()).collect();
-                module_ids.insert("Unity".to_string());
-                graph.mark_installed(&module_ids);
-            } else {
-                info!("Architecture mismatch, reinstalling");
-                info!("Fetch installed modules:");
-                additional_modules = installation
-                    .installed_modules()?
-                    .into_iter()
-                    .map(|m| m.id().to_string())
-                    .collect();
-                // info!("{}", additional_modules.iter().join("\n"));
-                fs::remove_dir_all(installation.path())?;
-                let version_string =
-                    format!("{}-{}", unity_release.version, unity_release.short_revision);
-                let installer_dir = paths::cache_dir()
-                    .map(|c| c.join(&format!("installer/{}", version_string)))
-                    .ok_or_else(|| {
-                        io::Error::new(
-                            io::ErrorKind::Other,
-                            "Unable to fetch cache installer directory",
-                        )
-                    })?;
-                if installer_dir.exists() {
-                    info!("Delete installer cache: {}", installer_dir.display());
-                    fs::remove_dir_all(installer_dir)?;
-                }
-                info!("Cleanup done");
-                graph.mark_all_missing();
+        let reinstall =
+            mark_installed_components(&mut graph, installation.as_ref().ok(), self.architecture)?;
+        if let (Some(reinstall), Ok(installation)) = (reinstall, &installation) {
+            additional_modules = reinstall;
+            fs::remove_dir_all(installation.path())?;
+            let installer_dir = installer_cache::installer_dir(
+                &unity_release.version,
+                &unity_release.short_revision,
+            )?;
+            // offline installs depend on the cached installers
+            if installer_dir.exists() && !offline {
+                info!("Delete installer cache: {}", installer_dir.display());
+                fs::remove_dir_all(installer_dir)?;
+            }
+            info!("Cleanup done");
+        }
+        // cached after the cleanup above, which deletes the whole installer cache of the release
+        if !offline {
+            if let Err(err) = installer_cache::save_release(&unity_release, architecture) {
+                warn!("Failed to cache release manifest: {}", err);
             }
-        } else {
-            info!("\nFresh install");
-            graph.mark_all_missing();
         }
 
         // info!("All available modules for Unity {}", version);
         // print_graph(&graph);
-        let additional_modules_iterator = additional_modules.into_iter();
-        let base_iterator = ["Unity".to_string()].into_iter();
-        let all_components: HashSet<String> = self
-            .requested_modules
-            .iter()
-            .flat_map(|module| {
-                let node = graph.get_node_id(&module).ok_or_else(|| {
-                    debug!(
-                        "Unsupported module '{}' for selected api version {}",
-                        module, version
-                    );
-                    InstallError::UnsupportedModule(module.to_string(), version.to_string())
-                });
-
-                match node {
-                    Ok(node) => {
-                        let mut out = vec![Ok(module.to_string())];
-                        out.append(
-                            &mut graph
-                                .get_dependend_modules(node)
-                                .iter()
-                                .map({
-                                    |((c, _), _)| match c {
-                                        UnityComponent::Editor(_) => Ok("Unity".to_string()),
-                                        UnityComponent::Module(m) => Ok(m.id().to_string()),
-                                    }
-                                })
-                                .collect(),
-                        );
-                        if self.install_sync {
-                            out.append(
-                                &mut graph
-                                    .get_sub_modules(node)
-                                    .iter()
-                                    .map({
-                                        |((c, _), _)| match c {
-                                            UnityComponent::Editor(_) => Ok("Unity".to_string()),
-                                            UnityComponent::Module(m) => Ok(m.id().to_string()),
-                                        }
-                                    })
-                                    .collect(),
-                            );
-                        }
-                        out
-                    }
-                    Err(err) => vec![Err(err.into())],
-                }
-            })
-            .chain(base_iterator.map(|c| Ok(c)))
-            .chain(additional_modules_iterator.map(|c| Ok(c)))
-            .collect::<Result<HashSet<_>>>()?;
+        let mut all_components =
+            resolve_components(&graph, &self.requested_modules, self.install_sync, version)?;
+        all_components.extend(additional_modules);
 
         debug!("\nAll requested components");
         for c in all_components.iter() {
@@ -445,6 +663,39 @@ impl InstallOptions {
         info!("\nInstall Graph");
         print_graph(&graph);
 
+        // Fail before anything is downloaded if an EULA of a missing component is not accepted
+        let download_mirror = self.download_mirror();
+        let plan = InstallPlan::from_graph(&graph, architecture, &base_dir, &download_mirror);
+        let mut eula_acceptance = self.eula_acceptance.clone();
+        let mut unaccepted: Vec<String> = Vec::new();
+        for (component, eula) in plan.unaccepted_eulas(&self.eula_acceptance) {
+            if eula_acceptance.accepts(&component.id) || unaccepted.contains(&component.id) {
+                continue;
+            }
+            match &self.eula_prompt {
+                Some(prompt) if prompt(component, eula) => {
+                    eula_acceptance = eula_acceptance.accept(component.id.as_str());
+                }
+                Some(_) => return Err(InstallError::EulaNotAccepted(vec![component.id.clone()])),
+                None => unaccepted.push(component.id.clone()),
+            }
+        }
+        if !unaccepted.is_empty() {
+            return Err(InstallError::EulaNotAccepted(unaccepted));
+        }
+        // Recorded in uvm-eula.json once their component is installed
+        let newly_accepted: Vec<AcceptedEula> = plan
+            .eulas()
+            .filter(|(_, eula)| !eula.accepted)
+            .map(|(component, eula)| {
+                AcceptedEula::new(
+                    component.id.as_str(),
+                    eula.label.as_str(),
+                    eula.release_url.as_str(),
+                )
+            })
+            .collect();
+
         // Collect all components with their status for progress tracking (deduplicate during collection)
         let mut all_graph_components: Vec<(String, String, InstallStatus)> = Vec::new();
         let mut seen_ids = HashSet::new();
@@ -484,6 +735,44 @@ impl InstallOptions {
             handler.initialize_components(&component_list);
         }
 
+        let retry_policy = self.retry_policy.unwrap_or_else(RetryPolicy::from_env);
+        let installer = RealModuleInstaller {
+            graph: &graph,
+            max_parallel_downloads: self.max_parallel_downloads,
+            download_mirror: &download_mirror,
+            retry_policy,
+            offline,
+            accepted_eulas: &newly_accepted,
+            progress_handler: self.progress_handler.as_ref().map(|h| h.as_ref()),
+        };
+
+        // Fail early with a complete list of missing installers before touching the installation
+        if offline {
+            let missing: Vec<InstallError> = all_graph_components
+                .iter()
+                .filter(|(_, _, status)| *status == InstallStatus::Missing)
+                .filter_map(|(id, _, _)| installer.find_cached_installer(id).err())
+                .collect();
+            if !missing.is_empty() {
+                return Err(InstallError::OfflineInstallersMissing(missing));
+            }
+        }
+
+        let space_requirement =
+            SpaceRequirement::from_graph(&graph, |id| installer.find_cached_installer(id).is_ok());
+        debug!(
+            "{} to download, {} to install",
+            space::format_bytes(space_requirement.download),
+            space::format_bytes(space_requirement.installed)
+        );
+        if !self.force {
+            let cache_dir = installer_cache::installer_dir(
+                &unity_release.version,
+                &unity_release.short_revision,
+            )?;
+            space::check_free_space(space_requirement, &cache_dir, &base_dir)?;
+        }
+
         // Ensure base directory exists before installation
         fs::DirBuilder::new().recursive(true).create(&base_dir)?;
 
@@ -496,8 +785,7 @@ impl InstallOptions {
         };
 
         // Install modules and update state incrementally
-        let progress_handler_ref = self.progress_handler.as_ref().map(|h| h.as_ref());
-        install_module_and_dependencies(&graph, &base_dir, &mut modules, progress_handler_ref)?;
+        install_modules_with_installer(&graph, &base_dir, &mut modules, &installer)?;
 
         // Get or create installation handle for final operations
         let installation = installation.or_else(|_| UnityInstallation::new(&base_dir))?;
@@ -512,6 +800,7 @@ impl InstallOptions {
 
         // Write final state
         installation.write_modules(modules)?;
+        InstallJournal::open(&base_dir)?.remove()?;
 
         //write new api hub editor installation
         if let Some(installation) = editor_installation {
@@ -533,6 +822,123 @@ fn fetch_modules_from_release(modules: &mut Vec<Module>, module: &uvm_live_platf
     }
 }
 
+/// Marks the components of an existing installation as installed and everything else as
+/// missing.
+///
+/// Returns the installed modules if the installation has the wrong architecture and has to be
+/// reinstalled completely, e.g. when it doesn't match the requested `architecture`.
+fn mark_installed_components(
+    graph: &mut InstallGraph,
+    installation: Option<&UnityInstallation>,
+    architecture: Option<InstallArchitecture>,
+) -> Result<Option<Vec<String>>> {
+    let Some(installation) = installation else {
+        info!("\nFresh install");
+        graph.mark_all_missing();
+        return Ok(None);
+    };
+
+    info!("Installation found at {}", installation.path().display());
+    let requested_architecture = match (architecture, installation_architecture(installation)) {
+        (Some(requested), Some(installed)) => requested == installed,
+        _ => true,
+    };
+    if requested_architecture && ensure_installation_architecture_is_correct(installation)? {
+        let modules = installation.installed_modules()?;
+        let mut module_ids: HashSet<String> =
+            modules.into_iter().map(|m| m.id().to_string()).collect();
+        module_ids.insert("Unity".to_string());
+        graph.mark_installed(&module_ids);
+        Ok(None)
+    } else {
+        info!("Architecture mismatch, reinstalling");
+        info!("Fetch installed modules:");
+        let reinstall = installation
+            .installed_modules()?
+            .into_iter()
+            .map(|m| m.id().to_string())
+            .collect();
+        graph.mark_all_missing();
+        Ok(Some(reinstall))
+    }
+}
+
+fn verify_release_revision(
+    version: &str,
+    short_revision: &str,
+    expected: &RevisionHash,
+) -> Result<()> {
+    if short_revision == expected.as_str() {
+        Ok(())
+    } else {
+        Err(InstallError::RevisionMismatch(
+            version.to_string(),
+            expected.to_string(),
+            short_revision.to_string(),
+        ))
+    }
+}
+
+/// Collects the requested modules of the graph together with their dependencies
+/// and the editor itself.
+///
+/// Synced sub modules are added when `install_sync` is set.
+fn resolve_components(
+    graph: &InstallGraph,
+    requested_modules: &HashSet<String>,
+    install_sync: bool,
+    version: &Version,
+) -> Result<HashSet<String>> {
+    let base_iterator = ["Unity".to_string()].into_iter();
+    requested_modules
+        .iter()
+        .flat_map(|module| {
+            let node = graph.get_node_id(module).ok_or_else(|| {
+                debug!(
+                    "Unsupported module '{}' for selected api version {}",
+                    module, version
+                );
+                InstallError::UnsupportedModule(module.to_string(), version.to_string())
+            });
+
+            match node {
+                Ok(node) => {
+                    let mut out = vec![Ok(module.to_string())];
+                    out.append(
+                        &mut graph
+                            .get_dependend_modules(node)
+                            .iter()
+                            .map({
+                                |((c, _), _)| match c {
+                                    UnityComponent::Editor(_) => Ok("Unity".to_string()),
+                                    UnityComponent::Module(m) => Ok(m.id().to_string()),
+                                }
+                            })
+                            .collect(),
+                    );
+                    if install_sync {
+                        out.append(
+                            &mut graph
+                                .get_sub_modules(node)
+                                .iter()
+                                .map({
+                                    |((c, _), _)| match c {
+                                        UnityComponent::Editor(_) => Ok("Unity".to_string()),
+                                        UnityComponent::Module(m) => Ok(m.id().to_string()),
+                                    }
+                                })
+                                .collect(),
+                        );
+                    }
+                    out
+                }
+                Err(err) => vec![Err(err)],
+            }
+        })
+        .chain(base_iterator.map(Ok))
+        .collect::<Result<HashSet<_>>>()
+}
+
 struct UnityComponent2<'a>(UnityComponent<'a>);
 
 impl<'a> Deref for UnityComponent2<'a> {
@@ -623,37 +1029,72 @@ fn strip_unity_base_url<P: AsRef<Path>, Q: AsRef<Path>>(path: P, base_dir: Q) ->
 }
 
 /// Trait for installing individual modules, allowing for mocking in tests
-trait ModuleInstaller {
-    fn install_module(&self, module_id: &str, base_dir: &Path) -> Result<()>;
+trait ModuleInstaller: Sync {
+    /// Download the installer for the given module and return its location.
+    fn download_module(&self, module_id: &str) -> Result<PathBuf>;
+
+    /// Install an already downloaded installer for the given module.
+    fn install_downloaded_module(
+        &self,
+        module_id: &str,
+        installer_path: &Path,
+        base_dir: &Path,
+    ) -> Result<()>;
+
+    /// The number of installers that may be downloaded at the same time.
+    fn max_parallel_downloads(&self) -> usize {
+        DEFAULT_MAX_PARALLEL_DOWNLOADS
+    }
+
+    /// The directory the given module is extracted to, recorded in the install journal.
+    fn install_destination(&self, _module_id: &str, _base_dir: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    /// The EULAs accepted for the given module, recorded once it is installed.
+    fn accepted_eulas(&self, _module_id: &str) -> Vec<AcceptedEula> {
+        Vec::new()
+    }
+
     fn progress_handler(&self) -> Option<&dyn install::ProgressHandler>;
 }
 
 /// Default implementation that uses the real download and install process
 struct RealModuleInstaller<'a> {
     graph: &'a InstallGraph<'a>,
+    max_parallel_downloads: usize,
+    download_mirror: &'a DownloadMirror,
+    retry_policy: RetryPolicy,
+    offline: bool,
+    accepted_eulas: &'a [AcceptedEula],
     progress_handler: Option<&'a dyn install::ProgressHandler>,
 }
 
-impl<'a> ModuleInstaller for RealModuleInstaller<'a> {
-    fn install_module(&self, module_id: &str, base_dir: &Path) -> Result<()> {
+impl<'a> RealModuleInstaller<'a> {
+    fn component(&self, module_id: &str) -> Result<UnityComponent2<'_>> {
         let node = self.graph.get_node_id(module_id).ok_or_else(|| {
             InstallError::UnsupportedModule(module_id.to_string(), "unknown".to_string())
         })?;
 
-        let component = self.graph.component(node).unwrap();
-        let unity_module = UnityComponent2(component);
-        let version = &self.graph.release().version;
-        let hash = &self.graph.release().short_revision;
+        Ok(UnityComponent2(self.graph.component(node).unwrap()))
+    }
 
-        // Determine component type for progress display
-        let component_type = if unity_module.is_editor() {
-            "Editor"
-        } else {
-            "Module"
-        };
+    /// Looks the installer up in the installer cache. The checksum is verified when it's loaded.
+    fn find_cached_installer(&self, module_id: &str) -> Result<PathBuf> {
+        let unity_module = self.component(module_id)?;
+        let release = self.graph.release();
+        Loader::new(&release.version, &release.short_revision, &unity_module)
+            .find_cached_installer()
+            .map_err(LoadingInstallerFailed)
+    }
 
-        // Get the pre-created progress handler for this component
-        let component_handler = self.progress_handler.and_then(|parent| {
+    /// Get the pre-created progress handler for this component
+    fn component_handler(
+        &self,
+        module_id: &str,
+        component_type: &str,
+    ) -> Option<Box<dyn install::ProgressHandler>> {
+        self.progress_handler.and_then(|parent| {
             let handler = parent.get_component_handler(module_id);
             if handler.is_none() {
                 debug!(
@@ -665,7 +1106,24 @@ impl<'a> ModuleInstaller for RealModuleInstaller<'a> {
             } else {
                 handler
             }
-        });
+        })
+    }
+}
+
+impl<'a> ModuleInstaller for RealModuleInstaller<'a> {
+    fn download_module(&self, module_id: &str) -> Result<PathBuf> {
+        let unity_module = self.component(module_id)?;
+        let version = &self.graph.release().version;
+        let hash = &self.graph.release().short_revision;
+
+        // Determine component type for progress display
+        let component_type = if unity_module.is_editor() {
+            "Editor"
+        } else {
+            "Module"
+        };
+
+        let component_handler = self.component_handler(module_id, component_type);
 
         // Set initial downloading state
         if let Some(ref handler) = component_handler {
@@ -674,12 +1132,33 @@ impl<'a> ModuleInstaller for RealModuleInstaller<'a> {
 
         info!("download installer for {}", module_id);
         let mut loader = Loader::new(version, hash, &unity_module);
+        if !self.download_mirror.is_empty() {
+            loader.set_download_mirror(self.download_mirror);
+        }
+        loader.set_retry_policy(self.retry_policy);
+        loader.set_offline(self.offline);
         if let Some(ref handler) = component_handler {
             loader.set_progress_handle(&**handler);
         }
-        let installer_path = loader
+        loader
             .download()
-            .map_err(|installer_err| LoadingInstallerFailed(installer_err))?;
+            .map_err(|installer_err| LoadingInstallerFailed(installer_err))
+    }
+
+    fn install_downloaded_module(
+        &self,
+        module_id: &str,
+        installer_path: &Path,
+        base_dir: &Path,
+    ) -> Result<()> {
+        let unity_module = self.component(module_id)?;
+        let component = *unity_module;
+        let component_type = if unity_module.is_editor() {
+            "Editor"
+        } else {
+            "Module"
+        };
+        let component_handler = self.component_handler(module_id, component_type);
 
         // Update to installing state (fallback for installers without sub-phases)
         if let Some(ref handler) = component_handler {
@@ -715,24 +1194,29 @@ impl<'a> ModuleInstaller for RealModuleInstaller<'a> {
         Ok(())
     }
 
+    fn max_parallel_downloads(&self) -> usize {
+        self.max_parallel_downloads
+    }
+
+    fn install_destination(&self, module_id: &str, base_dir: &Path) -> Option<PathBuf> {
+        self.component(module_id)
+            .ok()
+            .and_then(|component| component.install_destination(base_dir))
+    }
+
+    fn accepted_eulas(&self, module_id: &str) -> Vec<AcceptedEula> {
+        self.accepted_eulas
+            .iter()
+            .filter(|eula| eula.component == module_id)
+            .cloned()
+            .collect()
+    }
+
     fn progress_handler(&self) -> Option<&dyn install::ProgressHandler> {
         self.progress_handler
     }
 }
 
-fn install_module_and_dependencies<'a, P: AsRef<Path>>(
-    graph: &'a InstallGraph<'a>,
-    base_dir: P,
-    modules: &mut Vec<Module>,
-    progress_handler: Option<&'a dyn install::ProgressHandler>,
-) -> Result<()> {
-    let installer = RealModuleInstaller {
-        graph,
-        progress_handler,
-    };
-    install_modules_with_installer(graph, base_dir, modules, &installer)
-}
-
 fn install_modules_with_installer<'a, P: AsRef<Path>, I: ModuleInstaller>(
     graph: &'a InstallGraph<'a>,
     base_dir: P,
@@ -741,73 +1225,132 @@ fn install_modules_with_installer<'a, P: AsRef<Path>, I: ModuleInstaller>(
 ) -> Result<()> {
     let base_dir = base_dir.as_ref();
     let mut errors = Vec::new();
+    // Shared with the download workers, which record finished downloads
+    let journal = Mutex::new(InstallJournal::open(base_dir)?);
+    let record = |module_id: &str, state: ComponentState| {
+        let destination = installer.install_destination(module_id, base_dir);
+        let mut journal = journal.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = journal.record(module_id, state, destination.as_deref()) {
+            warn!("Failed to write install journal: {}", e);
+        }
+    };
 
+    let mut components: Vec<(String, Option<InstallStatus>)> = Vec::new();
     for node in graph.topo().iter(graph.context()) {
-        let status = graph.install_status(node);
-        let component = graph.component(node).unwrap();
-        let module_id = match component {
+        let module_id = match graph.component(node).unwrap() {
             UnityComponent::Editor(_) => "Unity".to_string(),
             UnityComponent::Module(m) => m.id().to_string(),
         };
+        if !components.iter().any(|(id, _)| *id == module_id) {
+            components.push((module_id, graph.install_status(node).copied()));
+        }
+    }
+
+    // Downloads run in the background in dependency order while the components
+    // are installed one after another on this thread.
+    let missing = components
+        .iter()
+        .filter(|(_, status)| matches!(status, Some(InstallStatus::Missing)))
+        .map(|(id, _)| id.clone());
+    let queue = DownloadQueue::new(missing, installer.max_parallel_downloads());
+    let download = |module_id: &str| {
+        let installer_path = installer.download_module(module_id)?;
+        record(module_id, ComponentState::Downloaded);
+        Ok(installer_path)
+    };
 
-        match status {
-            Some(InstallStatus::Installed) => {
-                // Mark already installed components
-                if let Some(handler) = installer.progress_handler() {
-                    if let Some(comp_handler) = handler.get_component_handler(&module_id) {
-                        comp_handler.set_message("✓ Already installed");
-                        comp_handler.finish();
+    thread::scope(|scope| {
+        queue.spawn(scope, &download);
+
+        for (module_id, status) in &components {
+            match status {
+                Some(InstallStatus::Installed) => {
+                    // Mark already installed components
+                    if let Some(handler) = installer.progress_handler() {
+                        if let Some(comp_handler) = handler.get_component_handler(module_id) {
+                            comp_handler.set_message("✓ Already installed");
+                            comp_handler.finish();
+                        }
+                        handler.mark_component_complete();
                     }
-                    handler.mark_component_complete();
+                    continue;
                 }
-                continue;
-            }
-            Some(InstallStatus::Missing) => {
-                // Install missing components
+                Some(InstallStatus::Missing) => {
+                    // Install missing components
+                }
+                _ => continue,
             }
-            _ => continue,
-        }
 
-        info!("install {}", module_id);
+            info!("install {}", module_id);
 
-        let install_result = installer.install_module(&module_id, base_dir);
+            let mut extracted = false;
+            let install_result = queue
+                .wait_for(module_id)
+                .unwrap_or_else(|| {
+                    Err(InstallError::UnsupportedModule(
+                        module_id.to_string(),
+                        "unknown".to_string(),
+                    ))
+                })
+                .and_then(|installer_path| {
+                    record(module_id, ComponentState::Extracting);
+                    extracted = true;
+                    installer.install_downloaded_module(module_id, &installer_path, base_dir)
+                });
+            // Only a component that started extracting can leave files behind
+            if extracted {
+                let state = if install_result.is_ok() {
+                    ComponentState::Installed
+                } else {
+                    ComponentState::Failed
+                };
+                record(module_id, state);
+            }
 
-        match install_result {
-            Err(err) if module_id == "Unity" => {
-                // Editor installation failed - cleanup and abort
-                log::error!("Editor installation failed, cleaning up");
-                if base_dir.exists() {
-                    if let Err(cleanup_err) = std::fs::remove_dir_all(base_dir) {
-                        log::warn!("Failed to cleanup installation directory: {}", cleanup_err);
+            match install_result {
+                Err(err) if module_id == "Unity" => {
+                    // Editor installation failed - stop pending downloads, cleanup and abort
+                    log::error!("Editor installation failed, cleaning up");
+                    queue.cancel();
+                    if base_dir.exists() {
+                        if let Err(cleanup_err) = std::fs::remove_dir_all(base_dir) {
+                            log::warn!("Failed to cleanup installation directory: {}", cleanup_err);
+                        }
                     }
+                    return Err(InstallError::EditorInstallationFailed(Box::new(err)));
                 }
-                return Err(InstallError::EditorInstallationFailed(Box::new(err)));
-            }
-            Err(err) => {
-                // Module failure - collect and continue
-                log::warn!("Failed to install module {}: {}", module_id, err);
-                errors.push(err);
-            }
-            Ok(()) => {
-                // Mark module as installed in modules list
-                if let Some(m) = modules.iter_mut().find(|m| m.id() == module_id) {
-                    m.is_installed = true;
-                    trace!("module {} installed successfully", module_id);
+                Err(err) => {
+                    // Module failure - collect and continue
+                    log::warn!("Failed to install module {}: {}", module_id, err);
+                    errors.push(err);
+                }
+                Ok(()) => {
+                    let eulas = installer.accepted_eulas(module_id);
+                    if !eulas.is_empty() {
+                        if let Err(err) = record_accepted_eulas(base_dir, eulas) {
+                            warn!("Failed to record accepted EULAs: {}", err);
+                        }
+                    }
+                    // Mark module as installed in modules list
+                    if let Some(m) = modules.iter_mut().find(|m| m.id() == module_id) {
+                        m.is_installed = true;
+                        trace!("module {} installed successfully", module_id);
+                    }
                 }
             }
-        }
 
-        // Write modules.json after each module (success or failure)
-        // Note: This won't run if we returned early from Editor failure above
-        write_modules_json(base_dir, modules);
-    }
+            // Write modules.json after each module (success or failure)
+            // Note: This won't run if we returned early from Editor failure above
+            write_modules_json(base_dir, modules);
+        }
 
-    // Return appropriate result based on collected errors
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(InstallError::ModuleInstallationsFailed(errors))
-    }
+        // Return appropriate result based on collected errors
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(InstallError::ModuleInstallationsFailed(errors))
+        }
+    })
 }
 
 fn write_modules_json(base_dir: &Path, modules: &[Module]) {
@@ -1137,18 +1680,51 @@ mod tests {
         struct MockModuleInstaller {
             /// Module IDs that should fail installation
             fail_modules: HashSet<String>,
+            /// Module IDs that should fail to download
+            fail_downloads: HashSet<String>,
+            /// Number of parallel download workers
+            max_parallel_downloads: usize,
             /// Tracks the order of install attempts
             install_order: Arc<Mutex<Vec<String>>>,
+            /// Tracks the download attempts
+            downloads: Arc<Mutex<Vec<String>>>,
         }
 
         impl MockModuleInstaller {
             fn new(fail_modules: HashSet<String>) -> Self {
                 Self {
                     fail_modules,
+                    fail_downloads: HashSet::new(),
+                    max_parallel_downloads: 1,
                     install_order: Arc::new(Mutex::new(Vec::new())),
+                    downloads: Arc::new(Mutex::new(Vec::new())),
                 }
             }
 
+            fn with_download_failures<I: IntoIterator<Item = S>, S: Into<String>>(
+                mut self,
+                modules: I,
+            ) -> Self {
+                self.fail_downloads = modules.into_iter().map(|s| s.into()).collect();
+                self
+            }
+
+            fn with_max_parallel_downloads(mut self, max_parallel_downloads: usize) -> Self {
+                self.max_parallel_downloads = max_parallel_downloads;
+                self
+            }
+
+            fn install_module(&self, module_id: &str, base_dir: &Path) -> Result<()> {
+                let installer_path = self.download_module(module_id)?;
+                self.install_downloaded_module(module_id, &installer_path, base_dir)
+            }
+
+            fn get_downloads(&self) -> Vec<String> {
+                let mut downloads = self.downloads.lock().unwrap().clone();
+                downloads.sort();
+                downloads
+            }
+
             fn with_no_failures() -> Self {
                 Self::new(HashSet::new())
             }
@@ -1167,7 +1743,39 @@ mod tests {
                 None
             }
 
-            fn install_module(&self, module_id: &str, _base_dir: &Path) -> Result<()> {
+            fn max_parallel_downloads(&self) -> usize {
+                self.max_parallel_downloads
+            }
+
+            fn accepted_eulas(&self, module_id: &str) -> Vec<AcceptedEula> {
+                vec![AcceptedEula::new(
+                    module_id.to_string(),
+                    format!("{} EULA", module_id),
+                    format!("https://example.com/{}-eula.html", module_id),
+                )]
+            }
+
+            fn download_module(&self, module_id: &str) -> Result<PathBuf> {
+                self.downloads.lock().unwrap().push(module_id.to_string());
+
+                if self.fail_downloads.contains(module_id) {
+                    Err(LoadingInstallerFailed(
+                        crate::install::error::InstallerError::from(io::Error::new(
+                            io::ErrorKind::Other,
+                            format!("Mock download failure for {}", module_id),
+                        )),
+                    ))
+                } else {
+                    Ok(PathBuf::from(module_id))
+                }
+            }
+
+            fn install_downloaded_module(
+                &self,
+                module_id: &str,
+                _installer_path: &Path,
+                _base_dir: &Path,
+            ) -> Result<()> {
                 // Record this install attempt
                 self.install_order
                     .lock()
@@ -1203,6 +1811,19 @@ mod tests {
             assert_eq!(release.downloads[0].modules[2].id(), "webgl");
         }
 
+        #[test]
+        fn test_verify_release_revision() {
+            let expected = RevisionHash::new("f87d5274e360").unwrap();
+            assert!(verify_release_revision("2022.3.0f1", "f87d5274e360", &expected).is_ok());
+
+            let err = verify_release_revision("2022.3.0f1", "4016570cf34f", &expected).unwrap_err();
+            assert!(matches!(err, InstallError::RevisionMismatch(..)));
+            assert_eq!(
+                err.to_string(),
+                "Unity 2022.3.0f1 requires revision f87d5274e360 but the release has revision 4016570cf34f"
+            );
+        }
+
         #[test]
         fn test_install_graph_from_release() {
             let release = create_test_release(&["android", "ios"]);
@@ -1231,6 +1852,128 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_install_plan_lists_components_with_status() {
+            let mut json: serde_json::Value =
+                serde_json::from_str(&create_test_release_json(&["android", "ios", "webgl"]))
+                    .unwrap();
+            json["downloads"][0]["modules"][1]["eula"] = serde_json::json!([{
+                "url": "https://example.com/ios-eula.html",
+                "label": "iOS EULA",
+                "message": "Accept the iOS terms"
+            }]);
+            let release: uvm_live_platform::Release = serde_json::from_value(json).unwrap();
+            let mut graph = InstallGraph::from(&release);
+            graph.mark_all_missing();
+            graph.mark_installed(&HashSet::from(["Unity".to_string(), "android".to_string()]));
+
+            let keep_set: HashSet<String> = ["Unity", "android", "ios"]
+                .iter()
+                .map(|id| id.to_string())
+                .collect();
+            graph.keep(&keep_set);
+
+            let base_dir = Path::new("/opt/unity/2022.3.0f1");
+            let plan = InstallPlan::from_graph(
+                &graph,
+                UnityReleaseDownloadArchitecture::X86_64,
+                base_dir,
+                &DownloadMirror::new(),
+            );
+
+            assert_eq!(plan.version, "2022.3.0f1");
+            assert_eq!(plan.revision, "abc123");
+            let statuses: Vec<(&str, ComponentStatus)> = plan
+                .components
+                .iter()
+                .map(|component| (component.id.as_str(), component.status))
+                .collect();
+            assert!(statuses.contains(&("android", ComponentStatus::Installed)));
+            assert!(statuses.contains(&("ios", ComponentStatus::Missing)));
+            assert!(!statuses.iter().any(|(id, _)| *id == "webgl"));
+
+            assert_eq!(plan.download_size(), 500_000);
+            assert_eq!(plan.installed_size(), 1_000_000);
+            assert!(!plan.is_up_to_date());
+            let eulas: Vec<(&str, &str)> = plan
+                .eulas()
+                .map(|(component, eula)| (component.id.as_str(), eula.label.as_str()))
+                .collect();
+            assert_eq!(eulas, vec![("ios", "iOS EULA")]);
+            assert_eq!(plan.unaccepted_eulas(&EulaAcceptance::None).count(), 1);
+            assert_eq!(
+                plan.unaccepted_eulas(&EulaAcceptance::None.accept("ios"))
+                    .count(),
+                0
+            );
+            assert_eq!(plan.unaccepted_eulas(&EulaAcceptance::All).count(), 0);
+
+            let json = serde_json::to_value(&plan).unwrap();
+            assert_eq!(json["destination"], "/opt/unity/2022.3.0f1");
+            assert!(json["components"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|c| c["id"] == "ios" && c["status"] == "missing"));
+        }
+
+        #[test]
+        fn test_install_plan_respects_previously_accepted_eulas() {
+            let mut json: serde_json::Value =
+                serde_json::from_str(&create_test_release_json(&["ios"])).unwrap();
+            json["downloads"][0]["modules"][0]["eula"] = serde_json::json!([{
+                "url": "https://example.com/ios-eula.html",
+                "label": "iOS EULA",
+                "message": "Accept the iOS terms"
+            }]);
+            let release: uvm_live_platform::Release = serde_json::from_value(json).unwrap();
+            let mut graph = InstallGraph::from(&release);
+            graph.mark_all_missing();
+            graph.keep(&HashSet::from(["Unity".to_string(), "ios".to_string()]));
+
+            let temp_dir = tempfile::tempdir().unwrap();
+            record_accepted_eulas(
+                temp_dir.path(),
+                [AcceptedEula::new(
+                    "ios",
+                    "iOS EULA",
+                    "https://example.com/ios-eula.html",
+                )],
+            )
+            .unwrap();
+
+            let plan = InstallPlan::from_graph(
+                &graph,
+                UnityReleaseDownloadArchitecture::X86_64,
+                temp_dir.path(),
+                &DownloadMirror::new()
+                    .with_rule("https://example.com/", "https://proxy.example.com/"),
+            );
+            assert_eq!(plan.eulas().count(), 1);
+            assert_eq!(plan.unaccepted_eulas(&EulaAcceptance::None).count(), 0);
+            let (_, eula) = plan.eulas().next().unwrap();
+            assert_eq!(eula.url, "https://proxy.example.com/ios-eula.html");
+        }
+
+        #[test]
+        fn test_space_requirement_sums_missing_components() {
+            let release = create_test_release(&["android", "ios", "webgl"]);
+            let mut graph = InstallGraph::from(&release);
+            graph.mark_all_missing();
+            graph.mark_installed(&HashSet::from(["android".to_string()]));
+
+            let keep_set: HashSet<String> = ["Unity", "android", "ios"]
+                .iter()
+                .map(|id| id.to_string())
+                .collect();
+            graph.keep(&keep_set);
+
+            let requirement = SpaceRequirement::from_graph(&graph, |id| id == "ios");
+            // the editor is downloaded, the cached ios installer only extracted
+            assert_eq!(requirement.download, 1_000_000);
+            assert_eq!(requirement.installed, 3_000_000);
+        }
+
         #[test]
         fn test_mock_installer_success() {
             let installer = MockModuleInstaller::with_no_failures();
@@ -1411,6 +2154,33 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_install_records_eulas_of_installed_modules_only() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let base_dir = temp_dir.path();
+
+            let release = create_test_release(&["android", "ios"]);
+            let mut graph = InstallGraph::from(&release);
+            graph.mark_all_missing();
+            graph.keep(&HashSet::from(["android".to_string(), "ios".to_string()]));
+
+            let mut modules = vec![
+                create_hub_module("android", false),
+                create_hub_module("ios", false),
+            ];
+
+            let installer = MockModuleInstaller::with_failures(["ios"]);
+            let _errors =
+                install_modules_with_installer(&graph, base_dir, &mut modules, &installer);
+
+            let recorded: Vec<String> = accepted_eulas(base_dir)
+                .unwrap()
+                .into_iter()
+                .map(|eula| eula.component)
+                .collect();
+            assert_eq!(recorded, vec!["android".to_string()]);
+        }
+
         #[test]
         fn test_install_continues_after_failure() {
             let temp_dir = tempfile::tempdir().unwrap();
@@ -1583,5 +2353,151 @@ mod tests {
             let modules_json_path = base_dir.join("modules.json");
             assert!(modules_json_path.exists(), "modules.json should exist");
         }
+
+        #[test]
+        fn test_parallel_downloads_install_in_topo_order() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let base_dir = temp_dir.path();
+
+            let release = create_test_release(&["android", "ios", "webgl"]);
+            let mut graph = InstallGraph::from(&release);
+            graph.mark_all_missing();
+
+            let mut keep_set = HashSet::new();
+            keep_set.insert("Unity".to_string());
+            keep_set.insert("android".to_string());
+            keep_set.insert("ios".to_string());
+            keep_set.insert("webgl".to_string());
+            graph.keep(&keep_set);
+
+            let expected_order: Vec<String> = graph
+                .topo()
+                .iter(graph.context())
+                .map(|node| match graph.component(node).unwrap() {
+                    UnityComponent::Editor(_) => "Unity".to_string(),
+                    UnityComponent::Module(m) => m.id().to_string(),
+                })
+                .collect();
+
+            let mut modules = vec![
+                create_hub_module("android", false),
+                create_hub_module("ios", false),
+                create_hub_module("webgl", false),
+            ];
+
+            let installer = MockModuleInstaller::with_no_failures().with_max_parallel_downloads(3);
+            let result = install_modules_with_installer(&graph, base_dir, &mut modules, &installer);
+
+            assert!(result.is_ok(), "Expected Ok(()), got {:?}", result);
+            assert_eq!(installer.get_install_order(), expected_order);
+            assert_eq!(
+                installer.get_downloads(),
+                vec!["Unity", "android", "ios", "webgl"]
+            );
+            assert!(modules.iter().all(|m| m.is_installed));
+        }
+
+        #[test]
+        fn test_module_download_failure_is_collected() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let base_dir = temp_dir.path();
+
+            let release = create_test_release(&["android", "ios", "webgl"]);
+            let mut graph = InstallGraph::from(&release);
+            graph.mark_all_missing();
+
+            let mut keep_set = HashSet::new();
+            keep_set.insert("android".to_string());
+            keep_set.insert("ios".to_string());
+            keep_set.insert("webgl".to_string());
+            graph.keep(&keep_set);
+
+            let mut modules = vec![
+                create_hub_module("android", false),
+                create_hub_module("ios", false),
+                create_hub_module("webgl", false),
+            ];
+
+            let installer = MockModuleInstaller::with_no_failures()
+                .with_download_failures(["ios"])
+                .with_max_parallel_downloads(2);
+            let result = install_modules_with_installer(&graph, base_dir, &mut modules, &installer);
+
+            match result {
+                Err(InstallError::ModuleInstallationsFailed(errors)) => {
+                    assert_eq!(errors.len(), 1, "Expected 1 module error");
+                    assert!(matches!(errors[0], LoadingInstallerFailed(_)));
+                }
+                _ => panic!("Expected ModuleInstallationsFailed error, got {:?}", result),
+            }
+
+            // ios never reached the install step
+            assert!(!installer.get_install_order().contains(&"ios".to_string()));
+            assert!(modules[0].is_installed, "android should be installed");
+            assert!(!modules[1].is_installed, "ios should NOT be installed");
+            assert!(modules[2].is_installed, "webgl should be installed");
+        }
+
+        #[test]
+        fn test_install_journal_records_component_states() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let base_dir = temp_dir.path();
+
+            let release = create_test_release(&["android", "ios", "webgl"]);
+            let mut graph = InstallGraph::from(&release);
+            graph.mark_all_missing();
+
+            let mut keep_set = HashSet::new();
+            keep_set.insert("android".to_string());
+            keep_set.insert("ios".to_string());
+            keep_set.insert("webgl".to_string());
+            graph.keep(&keep_set);
+
+            let mut modules = vec![
+                create_hub_module("android", false),
+                create_hub_module("ios", false),
+                create_hub_module("webgl", false),
+            ];
+
+            let installer =
+                MockModuleInstaller::with_failures(["ios"]).with_download_failures(["webgl"]);
+            let result = install_modules_with_installer(&graph, base_dir, &mut modules, &installer);
+            assert!(result.is_err(), "Expected error");
+
+            let journal = InstallJournal::open(base_dir).unwrap();
+            assert_eq!(journal.state("android"), Some(ComponentState::Installed));
+            assert_eq!(journal.state("ios"), Some(ComponentState::Failed));
+            // webgl failed to download and never touched the installation
+            assert_eq!(journal.state("webgl"), None);
+            let states = |component: &str| -> Vec<ComponentState> {
+                journal
+                    .records()
+                    .iter()
+                    .filter(|record| record.component == component)
+                    .map(|record| record.state)
+                    .collect()
+            };
+            assert_eq!(
+                states("android"),
+                vec![
+                    ComponentState::Downloaded,
+                    ComponentState::Extracting,
+                    ComponentState::Installed
+                ]
+            );
+            assert_eq!(
+                states("ios"),
+                vec![
+                    ComponentState::Downloaded,
+                    ComponentState::Extracting,
+                    ComponentState::Failed
+                ]
+            );
+
+            let rolled_back = rollback(base_dir).unwrap();
+            assert_eq!(rolled_back.len(), 1);
+            assert_eq!(rolled_back[0].component, "ios");
+            assert!(!base_dir.join(JOURNAL_FILE_NAME).exists());
+        }
     }
 }
@@ -0,0 +1,179 @@
+//! A structured description of what an installation would do, see [`InstallOptions::plan`].
+//!
+//! [`InstallOptions::plan`]: crate::InstallOptions::plan
+use crate::eula::{self, EulaAcceptance};
+use crate::install::{DownloadMirror, InstallManifest};
+use crate::UnityComponent2;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use uvm_install_graph::{InstallGraph, InstallStatus, UnityComponent, Walker};
+use uvm_live_platform::UnityReleaseDownloadArchitecture;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    /// The component is already installed and is kept as is.
+    Installed,
+    /// The component will be downloaded and installed.
+    Missing,
+}
+
+/// A license agreement shown for a component before it is installed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedEula {
+    pub label: String,
+    pub message: String,
+    /// The link to the EULA, rewritten through the download mirror.
+    pub url: String,
+    /// The url of the EULA in the release, which identifies accepted EULAs.
+    #[serde(skip)]
+    pub(crate) release_url: String,
+    /// Accepted by a previous installation of the component.
+    pub accepted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedComponent {
+    /// The component id, `Unity` for the editor.
+    pub id: String,
+    pub description: String,
+    pub status: ComponentStatus,
+    /// Download size in bytes
+    pub download_size: u64,
+    /// Size on disk after installation in bytes
+    pub installed_size: u64,
+    pub destination: Option<PathBuf>,
+    pub eulas: Vec<PlannedEula>,
+}
+
+impl PlannedComponent {
+    pub fn is_missing(&self) -> bool {
+        self.status == ComponentStatus::Missing
+    }
+}
+
+/// The components of an installation in install order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InstallPlan {
+    pub version: String,
+    pub revision: String,
+    pub architecture: String,
+    pub destination: PathBuf,
+    pub components: Vec<PlannedComponent>,
+}
+
+impl InstallPlan {
+    pub(crate) fn from_graph(
+        graph: &InstallGraph,
+        architecture: UnityReleaseDownloadArchitecture,
+        base_dir: &Path,
+        download_mirror: &DownloadMirror,
+    ) -> Self {
+        let release = graph.release();
+        let accepted_eulas = eula::accepted_eulas(base_dir).unwrap_or_default();
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+        for node in graph.topo().iter(graph.context()) {
+            let status = match graph.install_status(node) {
+                Some(InstallStatus::Installed) => ComponentStatus::Installed,
+                Some(InstallStatus::Missing) => ComponentStatus::Missing,
+                _ => continue,
+            };
+            let Some(component) = graph.component(node) else {
+                continue;
+            };
+            let (description, download_size, installed_size, eulas) = match component {
+                UnityComponent::Editor(editor) => (
+                    "Unity Editor".to_string(),
+                    &editor.download_size,
+                    &editor.installed_size,
+                    Vec::new(),
+                ),
+                UnityComponent::Module(module) => (
+                    module.description().to_string(),
+                    &module.download_size,
+                    &module.installed_size,
+                    module
+                        .eula()
+                        .iter()
+                        .map(|eula| PlannedEula {
+                            label: eula.label.clone(),
+                            message: eula.message.clone(),
+                            url: download_mirror.mirror_url(&eula.release_file.url),
+                            release_url: eula.release_file.url.clone(),
+                            accepted: eula::is_accepted(
+                                &accepted_eulas,
+                                module.id(),
+                                &eula.release_file.url,
+                            ),
+                        })
+                        .collect(),
+                ),
+            };
+            let component = UnityComponent2(component);
+            if !seen.insert(component.id().to_string()) {
+                continue;
+            }
+            components.push(PlannedComponent {
+                id: component.id().to_string(),
+                description,
+                status,
+                download_size: download_size.to_bytes() as u64,
+                installed_size: installed_size.to_bytes() as u64,
+                destination: component.install_destination(base_dir),
+                eulas,
+            });
+        }
+
+        Self {
+            version: release.version.clone(),
+            revision: release.short_revision.clone(),
+            architecture: architecture.to_string(),
+            destination: base_dir.to_path_buf(),
+            components,
+        }
+    }
+
+    /// The components which will be downloaded and installed.
+    pub fn missing(&self) -> impl Iterator<Item = &PlannedComponent> {
+        self.components
+            .iter()
+            .filter(|component| component.is_missing())
+    }
+
+    /// Returns `true` if everything is installed already.
+    pub fn is_up_to_date(&self) -> bool {
+        self.missing().next().is_none()
+    }
+
+    /// Bytes to download for the missing components.
+    pub fn download_size(&self) -> u64 {
+        self.missing()
+            .map(|component| component.download_size)
+            .sum()
+    }
+
+    /// Bytes on disk of the missing components after installation.
+    pub fn installed_size(&self) -> u64 {
+        self.missing()
+            .map(|component| component.installed_size)
+            .sum()
+    }
+
+    /// The EULAs of the missing components together with their component.
+    pub fn eulas(&self) -> impl Iterator<Item = (&PlannedComponent, &PlannedEula)> {
+        self.missing()
+            .flat_map(|component| component.eulas.iter().map(move |eula| (component, eula)))
+    }
+
+    /// The EULAs of the missing components which are neither accepted by `acceptance` nor by
+    /// a previous installation.
+    pub fn unaccepted_eulas<'a>(
+        &'a self,
+        acceptance: &'a EulaAcceptance,
+    ) -> impl Iterator<Item = (&'a PlannedComponent, &'a PlannedEula)> {
+        self.eulas()
+            .filter(move |(component, eula)| !eula.accepted && !acceptance.accepts(&component.id))
+    }
+}
@@ -0,0 +1,323 @@
+use crate::error::{InstallError, Result};
+use log::{debug, info};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use unity_version::error::VersionError;
+use unity_version::{ReleaseType, Version, VersionReq};
+use uvm_live_platform::error::{ErrorRepr, LivePlatformError};
+use uvm_live_platform::{ListVersions, UnityReleaseEntitlement, UnityReleaseStream};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid version requirement '{requirement}': {reason}")]
+pub struct VersionRequirementError {
+    requirement: String,
+    reason: String,
+}
+
+/// A Unity version or a requirement which is resolved to a concrete version.
+///
+/// # Syntax
+///
+/// - `2022.3.10f1` - exactly this version
+/// - `2022.3` or `2023.2.x` - the newest release of the `2022.3` or `2023.2` line
+/// - `^2022.3`, `>=2021.3.10f1, <2023` - any Unity [`VersionReq`]
+/// - `latest`, `latest-lts`, `latest-beta`, `latest-alpha`, `latest-tech`, `latest-supported` -
+///   the newest release, optionally of a release stream
+///
+/// When resolving, final and patch releases are preferred over alpha and beta releases which
+/// match the same requirement. A [`VersionReq`] only matches alpha and beta releases if it names
+/// a pre-release type, e.g. `>=2023.1.0b1`, or if no stable release matches it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionRequirement {
+    Exact(Version),
+    Matching(VersionReq),
+    Latest(Option<UnityReleaseStream>),
+}
+
+impl VersionRequirement {
+    /// Returns the version if the requirement only matches one exact version.
+    pub fn exact(&self) -> Option<&Version> {
+        match self {
+            VersionRequirement::Exact(version) => Some(version),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `version` satisfies the requirement.
+    ///
+    /// Installed versions don't record the stream they were released in, so `latest-<stream>`
+    /// matches by release type: alpha and beta streams match alpha and beta releases, all other
+    /// streams match final, patch and China releases.
+    pub fn matches(&self, version: &Version) -> bool {
+        match self {
+            VersionRequirement::Exact(exact) => exact == version,
+            VersionRequirement::Matching(req) => req.matches(version),
+            VersionRequirement::Latest(None) => true,
+            VersionRequirement::Latest(Some(UnityReleaseStream::Alpha)) => {
+                version.release_type() == ReleaseType::Alpha
+            }
+            VersionRequirement::Latest(Some(UnityReleaseStream::Beta)) => {
+                version.release_type() == ReleaseType::Beta
+            }
+            VersionRequirement::Latest(Some(_)) => is_stable(version),
+        }
+    }
+
+    /// Returns the newest of `versions` which satisfies the requirement.
+    ///
+    /// Stable releases are preferred. Without a matching stable release, alpha and beta
+    /// releases are matched as well, even if a [`VersionReq`] doesn't name a pre-release type,
+    /// so that e.g. `2023.3` resolves to the newest `2023.3` beta of the beta stream.
+    pub fn best_match<'a, I: IntoIterator<Item = &'a Version>>(&self, versions: I) -> Option<Version> {
+        let versions: Vec<&Version> = versions.into_iter().collect();
+        versions
+            .iter()
+            .filter(|version| is_stable(version) && self.matches(version))
+            .max()
+            .or_else(|| {
+                versions
+                    .iter()
+                    .filter(|version| self.matches_pre_release(version))
+                    .max()
+            })
+            .map(|version| (*version).to_owned())
+    }
+
+    fn matches_pre_release(&self, version: &Version) -> bool {
+        match self {
+            VersionRequirement::Matching(req) => req.matches_pre_release(version),
+            _ => self.matches(version),
+        }
+    }
+
+    /// Resolves the requirement against the releases listed by the Unity live platform.
+    ///
+    /// Only releases of the given `streams` and `entitlements` are considered; empty filters
+    /// consider all releases. Exact versions are returned without a network request.
+    pub fn resolve(
+        &self,
+        streams: &[UnityReleaseStream],
+        entitlements: &[UnityReleaseEntitlement],
+    ) -> Result<Version> {
+        if let VersionRequirement::Exact(version) = self {
+            return Ok(version.to_owned());
+        }
+
+        let mut streams = streams.to_vec();
+        if let VersionRequirement::Latest(Some(stream)) = self {
+            streams = vec![*stream];
+        }
+
+        debug!("resolve version requirement {} for streams {:?}", self, streams);
+        let versions: Vec<Version> = ListVersions::builder()
+            .with_current_platform()
+            .with_streams(streams)
+            .with_entitlements(entitlements.to_vec())
+            .autopage(matches!(self, VersionRequirement::Matching(_)))
+            .list()
+            .map_err(|e| {
+                LivePlatformError::new("Failed to list versions", ErrorRepr::ListVersionsError(e))
+            })?
+            .filter_map(|version| Version::from_str(&version).ok())
+            .collect();
+
+        let version = self
+            .best_match(&versions)
+            .ok_or_else(|| InstallError::NoMatchingVersion(self.to_string()))?;
+        info!("resolved version requirement {} to {}", self, version);
+        Ok(version)
+    }
+}
+
+fn is_stable(version: &Version) -> bool {
+    matches!(
+        version.release_type(),
+        ReleaseType::Final | ReleaseType::Patch | ReleaseType::China
+    )
+}
+
+fn parse_stream(stream: &str) -> Option<UnityReleaseStream> {
+    match stream {
+        "lts" => Some(UnityReleaseStream::Lts),
+        "beta" => Some(UnityReleaseStream::Beta),
+        "alpha" => Some(UnityReleaseStream::Alpha),
+        "tech" => Some(UnityReleaseStream::Tech),
+        "supported" => Some(UnityReleaseStream::Supported),
+        _ => None,
+    }
+}
+
+fn stream_name(stream: &UnityReleaseStream) -> &'static str {
+    match stream {
+        UnityReleaseStream::Lts => "lts",
+        UnityReleaseStream::Beta => "beta",
+        UnityReleaseStream::Alpha => "alpha",
+        UnityReleaseStream::Tech => "tech",
+        UnityReleaseStream::Supported => "supported",
+    }
+}
+
+impl FromStr for VersionRequirement {
+    type Err = VersionRequirementError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        let error = |reason: String| VersionRequirementError {
+            requirement: s.to_string(),
+            reason,
+        };
+
+        if let Ok(version) = Version::from_str(s) {
+            return Ok(VersionRequirement::Exact(version));
+        }
+
+        let lowercase = s.to_ascii_lowercase();
+        if lowercase == "latest" {
+            return Ok(VersionRequirement::Latest(None));
+        }
+        if let Some(stream) = lowercase.strip_prefix("latest-") {
+            return parse_stream(stream)
+                .map(|stream| VersionRequirement::Latest(Some(stream)))
+                .ok_or_else(|| error(format!("unknown release stream '{}'", stream)));
+        }
+
+        VersionReq::from_str(s)
+            .map(VersionRequirement::Matching)
+            .map_err(|e| match e {
+                VersionError::RequirementParsingFailed { reason, .. } => error(reason),
+                e => error(e.to_string()),
+            })
+    }
+}
+
+impl fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionRequirement::Exact(version) => write!(f, "{}", version),
+            VersionRequirement::Matching(req) => write!(f, "{}", req),
+            VersionRequirement::Latest(None) => write!(f, "latest"),
+            VersionRequirement::Latest(Some(stream)) => write!(f, "latest-{}", stream_name(stream)),
+        }
+    }
+}
+
+impl From<Version> for VersionRequirement {
+    fn from(version: Version) -> Self {
+        VersionRequirement::Exact(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn versions(versions: &[&str]) -> Vec<Version> {
+        versions.iter().map(|v| Version::from_str(v).unwrap()).collect()
+    }
+
+    #[rstest]
+    #[case("2022.3.10f1", "2022.3.10f1")]
+    #[case("2022.3", "=2022.3")]
+    #[case("2023.2.x", "=2023.2")]
+    #[case("^2022.3", "^2022.3")]
+    #[case(">=2021.3, <2023", ">=2021.3, <2023")]
+    #[case(">=2022.3.10f1", ">=2022.3.10f1")]
+    #[case("latest", "latest")]
+    #[case("latest-lts", "latest-lts")]
+    #[case("Latest-Beta", "latest-beta")]
+    fn parses_requirements(#[case] input: &str, #[case] expected: &str) {
+        let requirement = VersionRequirement::from_str(input).unwrap();
+        assert_eq!(requirement.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("latest-nightly")]
+    #[case("not a version")]
+    #[case("")]
+    fn rejects_invalid_requirements(#[case] input: &str) {
+        assert!(VersionRequirement::from_str(input).is_err());
+    }
+
+    #[test]
+    fn exact_requirement_only_matches_version() {
+        let requirement = VersionRequirement::from_str("2022.3.10f1").unwrap();
+        assert_eq!(requirement.exact().unwrap().to_string(), "2022.3.10f1");
+        assert!(requirement.matches(&Version::from_str("2022.3.10f1").unwrap()));
+        assert!(!requirement.matches(&Version::from_str("2022.3.11f1").unwrap()));
+    }
+
+    #[rstest]
+    #[case("2022.3", "2022.3.20f1")]
+    #[case("2023.2.x", "2023.2.5f1")]
+    #[case("^2022.3", "2022.3.20f1")]
+    #[case(">=2022.3.15", "2023.2.5f1")]
+    #[case("<2022.3.20f1", "2022.3.10f1")]
+    #[case("<2022", "2021.3.30f1")]
+    #[case("latest", "2023.2.5f1")]
+    #[case("latest-lts", "2023.2.5f1")]
+    #[case("latest-beta", "2023.3.0b4")]
+    fn best_match_prefers_newest_stable_release(#[case] input: &str, #[case] expected: &str) {
+        let installed = versions(&[
+            "2021.3.30f1",
+            "2022.3.10f1",
+            "2022.3.20f1",
+            "2023.2.5f1",
+            "2023.3.0b4",
+        ]);
+        let requirement = VersionRequirement::from_str(input).unwrap();
+        assert_eq!(
+            requirement.best_match(&installed).unwrap().to_string(),
+            expected
+        );
+    }
+
+    #[test]
+    fn best_match_falls_back_to_pre_releases() {
+        let installed = versions(&["2022.3.20f1", "2023.3.0b4", "2023.3.0a18"]);
+        let requirement = VersionRequirement::from_str(">=2023.3.0a1").unwrap();
+        assert_eq!(
+            requirement.best_match(&installed).unwrap().to_string(),
+            "2023.3.0b4"
+        );
+        let requirement = VersionRequirement::from_str("2024.1").unwrap();
+        assert_eq!(requirement.best_match(&installed), None);
+    }
+
+    #[test]
+    fn best_match_resolves_pre_releases_of_pre_release_streams() {
+        // the versions `resolve` lists for `--stream beta`
+        let beta_stream = versions(&["2023.3.0b3", "2023.3.0b4", "2023.2.0b9"]);
+        let requirement = VersionRequirement::from_str("2023.3").unwrap();
+        assert_eq!(
+            requirement.best_match(&beta_stream).unwrap().to_string(),
+            "2023.3.0b4"
+        );
+
+        let with_stable = versions(&["2023.3.0f1", "2023.3.1b1"]);
+        assert_eq!(
+            requirement.best_match(&with_stable).unwrap().to_string(),
+            "2023.3.0f1"
+        );
+    }
+
+    #[test]
+    fn matching_requirement_excludes_pre_releases_unless_requested() {
+        let requirement = VersionRequirement::from_str(">=2022.3.0").unwrap();
+        assert!(requirement.matches(&Version::from_str("2022.3.0f1").unwrap()));
+        assert!(!requirement.matches(&Version::from_str("2022.3.0b5").unwrap()));
+
+        let requirement = VersionRequirement::from_str("2023.3").unwrap();
+        assert!(!requirement.matches(&Version::from_str("2023.3.0b4").unwrap()));
+    }
+
+    #[test]
+    fn resolve_exact_version_without_network() {
+        let requirement = VersionRequirement::from_str("2022.3.10f1").unwrap();
+        assert_eq!(
+            requirement.resolve(&[], &[]).unwrap().to_string(),
+            "2022.3.10f1"
+        );
+    }
+}
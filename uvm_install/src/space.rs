@@ -0,0 +1,227 @@
+//! Disk space preflight check, run before anything is downloaded or installed.
+use crate::error::{InstallError, Result};
+use log::{debug, warn};
+use std::io;
+use std::path::{Path, PathBuf};
+use uvm_install_graph::{InstallGraph, InstallStatus, UnityComponent, Walker};
+
+/// The space needed to download and install the missing components of an install graph.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SpaceRequirement {
+    /// Bytes of installers to download into the installer cache.
+    pub download: u64,
+    /// Bytes of the extracted components in the installation.
+    pub installed: u64,
+}
+
+impl SpaceRequirement {
+    /// Sums the sizes of all missing components of `graph`.
+    ///
+    /// Installers for which `is_cached` returns `true` are not downloaded again and only
+    /// count towards the installed size.
+    pub fn from_graph<F: Fn(&str) -> bool>(graph: &InstallGraph, is_cached: F) -> Self {
+        let mut requirement = Self::default();
+        let mut seen = Vec::new();
+        for node in graph.topo().iter(graph.context()) {
+            if graph.install_status(node) != Some(&InstallStatus::Missing) {
+                continue;
+            }
+            let (id, download_size, installed_size) = match graph.component(node) {
+                Some(UnityComponent::Editor(editor)) => {
+                    ("Unity", &editor.download_size, &editor.installed_size)
+                }
+                Some(UnityComponent::Module(module)) => (
+                    module.id().as_str(),
+                    &module.download_size,
+                    &module.installed_size,
+                ),
+                None => continue,
+            };
+            if seen.contains(&id) {
+                continue;
+            }
+            seen.push(id);
+
+            if !is_cached(id) {
+                requirement.download += download_size.to_bytes() as u64;
+            }
+            requirement.installed += installed_size.to_bytes() as u64;
+        }
+        requirement
+    }
+}
+
+/// Fails with [`InstallError::InsufficientDiskSpace`] if the volume of the installer cache or
+/// the volume of the destination has not enough free space.
+///
+/// Both requirements are added up if cache and destination share a volume. Volumes whose
+/// free space can't be determined are skipped.
+pub fn check_free_space(
+    requirement: SpaceRequirement,
+    cache_dir: &Path,
+    destination: &Path,
+) -> Result<()> {
+    check_volumes(
+        &[
+            (cache_dir.to_path_buf(), requirement.download),
+            (destination.to_path_buf(), requirement.installed),
+        ],
+        |path| fs4::available_space(path),
+    )
+}
+
+fn check_volumes<F>(requirements: &[(PathBuf, u64)], available_space: F) -> Result<()>
+where
+    F: Fn(&Path) -> io::Result<u64>,
+{
+    // (volume, existing path on the volume, required bytes)
+    let mut volumes: Vec<(Option<u64>, PathBuf, u64)> = Vec::new();
+    for (path, required) in requirements {
+        let Some(existing) = existing_ancestor(path) else {
+            debug!(
+                "skip space check for {}, no existing parent",
+                path.display()
+            );
+            continue;
+        };
+        let volume = volume_id(&existing);
+        match volumes
+            .iter_mut()
+            .find(|(id, _, _)| volume.is_some() && *id == volume)
+        {
+            Some((_, _, total)) => *total += required,
+            None => volumes.push((volume, existing, *required)),
+        }
+    }
+
+    for (_, path, required) in volumes {
+        if required == 0 {
+            continue;
+        }
+        match available_space(&path) {
+            Ok(available) if available < required => {
+                return Err(InstallError::InsufficientDiskSpace {
+                    path,
+                    required,
+                    available,
+                });
+            }
+            Ok(available) => debug!(
+                "{} required, {} available at {}",
+                format_bytes(required),
+                format_bytes(available),
+                path.display()
+            ),
+            Err(e) => warn!(
+                "Unable to determine free disk space at {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// The path itself or its nearest parent that exists, since the destination and the cache
+/// directory are only created during the installation.
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(unix)]
+fn volume_id(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    path.metadata().ok().map(|metadata| metadata.dev())
+}
+
+#[cfg(windows)]
+fn volume_id(path: &Path) -> Option<u64> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::path::Component;
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) => {
+            let mut hasher = DefaultHasher::new();
+            prefix
+                .as_os_str()
+                .to_string_lossy()
+                .to_lowercase()
+                .hash(&mut hasher);
+            Some(hasher.finish())
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    let size_mb = bytes as f64 / 1_048_576.0;
+    if size_mb >= 1024.0 {
+        format!("{:.1} GB", size_mb / 1024.0)
+    } else {
+        format!("{:.0} MB", size_mb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn passes_with_enough_space() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = check_volumes(&[(temp_dir.path().to_path_buf(), GB)], |_| Ok(2 * GB));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn fails_with_too_little_space() {
+        let temp_dir = TempDir::new().unwrap();
+        let destination = temp_dir.path().join("2022.3.10f1");
+        let result = check_volumes(&[(destination, 3 * GB)], |_| Ok(2 * GB));
+        match result {
+            Err(InstallError::InsufficientDiskSpace {
+                path,
+                required,
+                available,
+            }) => {
+                assert_eq!(path, temp_dir.path());
+                assert_eq!(required, 3 * GB);
+                assert_eq!(available, 2 * GB);
+            }
+            other => panic!("expected InsufficientDiskSpace, got {:?}", other),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn adds_up_requirements_on_the_same_volume() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = temp_dir.path().join("cache");
+        let destination = temp_dir.path().join("editor");
+        let result = check_volumes(&[(cache, GB), (destination, GB)], |_| Ok(GB + GB / 2));
+        assert!(matches!(
+            result,
+            Err(InstallError::InsufficientDiskSpace { required, .. }) if required == 2 * GB
+        ));
+    }
+
+    #[test]
+    fn skips_volumes_with_unknown_free_space() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = check_volumes(&[(temp_dir.path().to_path_buf(), GB)], |_| {
+            Err(io::Error::other("unsupported"))
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512 * 1024 * 1024), "512 MB");
+        assert_eq!(format_bytes(3 * GB + GB / 2), "3.5 GB");
+    }
+}
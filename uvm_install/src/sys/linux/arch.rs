@@ -0,0 +1,67 @@
+use crate::InstallArchitecture;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+const ELF_BIG_ENDIAN: u8 = 2;
+const ELF_MACHINE_X86_64: u16 = 0x3e;
+const ELF_MACHINE_AARCH64: u16 = 0xb7;
+
+/// Reads the architecture of the ELF executable at `path`.
+pub fn binary_architectures<P: AsRef<Path>>(path: P) -> io::Result<Vec<InstallArchitecture>> {
+    let path = path.as_ref();
+    let mut header = [0u8; 20];
+    File::open(path)?
+        .read_exact(&mut header)
+        .ok()
+        .filter(|_| header.starts_with(ELF_MAGIC))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} is not an ELF executable", path.display()),
+            )
+        })?;
+
+    let machine = if header[5] == ELF_BIG_ENDIAN {
+        u16::from_be_bytes([header[18], header[19]])
+    } else {
+        u16::from_le_bytes([header[18], header[19]])
+    };
+    Ok(match machine {
+        ELF_MACHINE_X86_64 => vec![InstallArchitecture::X86_64],
+        ELF_MACHINE_AARCH64 => vec![InstallArchitecture::Arm64],
+        _ => vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_architecture_of_elf_executable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("Unity");
+        for (machine, expected) in [
+            (ELF_MACHINE_X86_64, vec![InstallArchitecture::X86_64]),
+            (ELF_MACHINE_AARCH64, vec![InstallArchitecture::Arm64]),
+            (0x28, vec![]),
+        ] {
+            let mut header = vec![0u8; 64];
+            header[..4].copy_from_slice(ELF_MAGIC);
+            header[5] = 1;
+            header[18..20].copy_from_slice(&machine.to_le_bytes());
+            std::fs::write(&path, header).unwrap();
+            assert_eq!(binary_architectures(&path).unwrap(), expected);
+        }
+
+        for content in ["", "#!/bin/sh\necho not an executable\n"] {
+            std::fs::write(&path, content).unwrap();
+            assert_eq!(
+                binary_architectures(&path).unwrap_err().kind(),
+                io::ErrorKind::InvalidData
+            );
+        }
+    }
+}
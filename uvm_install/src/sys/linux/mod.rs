@@ -9,9 +9,11 @@ use crate::install::installer::{Installer, ModulePoInstaller, ModuleZipInstaller
 use crate::install::InstallHandler;
 use crate::ProgressHandler;
 
+mod arch;
 mod pkg;
 mod xz;
 mod zip;
+pub use arch::binary_architectures;
 
 pub fn create_installer<P, I, M>(
     base_install_path: P,
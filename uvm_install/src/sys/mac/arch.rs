@@ -6,6 +6,7 @@ use std::str::FromStr;
 use log::{info, warn};
 use mach_object::{get_arch_name_from_types, OFile};
 use sysctl::Sysctl;
+use crate::InstallArchitecture;
 use unity_hub::unity::Installation;
 use unity_version::Version;
 use thiserror::Error;
@@ -74,6 +75,22 @@ fn fetch_architectures_from_binary<P: AsRef<Path>>(path: P) -> Result<Vec<String
     }
 }
 
+/// Reads the architectures of the (fat) Mach-O executable at `path`.
+///
+/// Architectures uvm can't install are skipped.
+pub fn binary_architectures<P: AsRef<Path>>(path: P) -> io::Result<Vec<InstallArchitecture>> {
+    let architectures = fetch_architectures_from_binary(path)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(architectures
+        .iter()
+        .filter_map(|arch| match arch.as_str() {
+            "x86_64" => Some(InstallArchitecture::X86_64),
+            "arm64" => Some(InstallArchitecture::Arm64),
+            _ => None,
+        })
+        .collect())
+}
+
 fn fetch_system_architecture() -> Result<String, ArchError> {
     let ctl = sysctl::Ctl::new("hw.machine")?;
     let value = ctl.value()?;
@@ -9,7 +9,7 @@ use std::path::Path;
 mod dmg;
 mod pkg;
 mod arch;
-pub use arch::ensure_installation_architecture_is_correct;
+pub use arch::{binary_architectures, ensure_installation_architecture_is_correct};
 
 pub fn create_installer<P, I, M>(
     base_install_path: P,
@@ -0,0 +1,47 @@
+use crate::InstallArchitecture;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+const PE_MAGIC: &[u8] = b"MZ";
+const PE_SIGNATURE: &[u8] = b"PE\0\0";
+const PE_HEADER_OFFSET: u64 = 0x3c;
+const PE_MACHINE_AMD64: u16 = 0x8664;
+const PE_MACHINE_ARM64: u16 = 0xaa64;
+
+/// Reads the architecture of the PE executable at `path`.
+pub fn binary_architectures<P: AsRef<Path>>(path: P) -> io::Result<Vec<InstallArchitecture>> {
+    let path = path.as_ref();
+    let mut file = File::open(path)?;
+    let machine = read_machine(&mut file).ok().flatten().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not a PE executable", path.display()),
+        )
+    })?;
+    Ok(match machine {
+        PE_MACHINE_AMD64 => vec![InstallArchitecture::X86_64],
+        PE_MACHINE_ARM64 => vec![InstallArchitecture::Arm64],
+        _ => vec![],
+    })
+}
+
+fn read_machine(file: &mut File) -> io::Result<Option<u16>> {
+    let mut magic = [0u8; 2];
+    file.read_exact(&mut magic)?;
+    if magic != PE_MAGIC {
+        return Ok(None);
+    }
+
+    let mut offset = [0u8; 4];
+    file.seek(SeekFrom::Start(PE_HEADER_OFFSET))?;
+    file.read_exact(&mut offset)?;
+    file.seek(SeekFrom::Start(u32::from_le_bytes(offset) as u64))?;
+
+    let mut header = [0u8; 6];
+    file.read_exact(&mut header)?;
+    if &header[..4] != PE_SIGNATURE {
+        return Ok(None);
+    }
+    Ok(Some(u16::from_le_bytes([header[4], header[5]])))
+}
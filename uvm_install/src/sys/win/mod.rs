@@ -7,8 +7,10 @@ use crate::install::InstallHandler;
 use crate::ProgressHandler;
 use self::exe::*;
 use self::msi::ModuleMsiInstaller;
+mod arch;
 mod exe;
 mod msi;
+pub use arch::binary_architectures;
 
 pub fn create_installer<P, I, M>(
     base_install_path: P,
@@ -0,0 +1,280 @@
+//! Record/replay of live platform requests.
+//!
+//! In record mode every successful request is written to a cassette directory together with
+//! its options. In replay mode the recorded responses are served from the cassette and any
+//! request that wasn't recorded fails, so a bug report recorded on a customer machine can be
+//! reproduced deterministically without network access.
+//!
+//! Cassettes are configured with [`CASSETTE_ENV`] and [`CASSETTE_MODE_ENV`]. Only the GraphQL
+//! requests are recorded, release notes and installers are downloaded as usual.
+use crate::api::middleware::{Middleware, MiddlewareChain};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Environment variable with the cassette directory to record to or replay from.
+pub const CASSETTE_ENV: &str = "UVM_LIVE_PLATFORM_CASSETTE";
+
+/// Environment variable selecting the [`CassetteMode`], `record` or `replay` (default).
+pub const CASSETTE_MODE_ENV: &str = "UVM_LIVE_PLATFORM_CASSETTE_MODE";
+
+#[derive(Error, Debug)]
+pub enum CassetteError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("JSON serialization error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("no recorded {name} request in {} for {options}", .path.display())]
+    UnknownRequest {
+        name: String,
+        path: PathBuf,
+        options: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests and write the responses to the cassette.
+    Record,
+    /// Serve responses from the cassette and fail on unknown requests.
+    Replay,
+}
+
+impl FromStr for CassetteMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "record" => Ok(Self::Record),
+            "replay" => Ok(Self::Replay),
+            other => Err(format!("unknown cassette mode '{}'", other)),
+        }
+    }
+}
+
+/// A recorded request.
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteEntry<Res> {
+    options: Value,
+    response: Res,
+}
+
+/// Records or replays the requests of one API, e.g. `fetch_release`.
+#[derive(Debug, Clone)]
+pub struct CassetteMiddleware<Opts, Res> {
+    dir: PathBuf,
+    mode: CassetteMode,
+    name: String,
+    _phantom: PhantomData<fn(Opts) -> Res>,
+}
+
+impl<Opts, Res> CassetteMiddleware<Opts, Res>
+where
+    Opts: Serialize,
+    Res: Serialize + DeserializeOwned,
+{
+    pub fn new<P: Into<PathBuf>, S: Into<String>>(dir: P, mode: CassetteMode, name: S) -> Self {
+        Self {
+            dir: dir.into(),
+            mode,
+            name: name.into(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The cassette configured with [`CASSETTE_ENV`], if any.
+    ///
+    /// An unknown [`CASSETTE_MODE_ENV`] replays, which fails on unknown requests instead of
+    /// silently sending them.
+    pub fn from_env<S: Into<String>>(name: S) -> Option<Self> {
+        let dir = std::env::var_os(CASSETTE_ENV).filter(|dir| !dir.is_empty())?;
+        let mode = std::env::var(CASSETTE_MODE_ENV)
+            .ok()
+            .and_then(|mode| mode.parse().ok())
+            .unwrap_or(CassetteMode::Replay);
+        Some(Self::new(dir, mode, name))
+    }
+
+    /// Recordings are named after the API and a stable hash of the request options, so the
+    /// same request maps to the same file on every machine.
+    fn entry_path(&self, options: &Value) -> PathBuf {
+        self.dir.join(format!(
+            "{}-{:016x}.json",
+            self.name,
+            fnv1a(options.to_string().as_bytes())
+        ))
+    }
+
+    fn replay(&self, options: &Value) -> Result<Res, CassetteError> {
+        let path = self.entry_path(options);
+        let unknown = || CassetteError::UnknownRequest {
+            name: self.name.clone(),
+            path: self.dir.clone(),
+            options: options.to_string(),
+        };
+        if !path.exists() {
+            return Err(unknown());
+        }
+        let entry: CassetteEntry<Res> = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        if &entry.options != options {
+            return Err(unknown());
+        }
+        Ok(entry.response)
+    }
+
+    fn record(&self, options: Value, response: &Res) -> Result<(), CassetteError> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.entry_path(&options);
+        let entry = CassetteEntry { options, response };
+        fs::write(path, serde_json::to_string_pretty(&entry)?)?;
+        Ok(())
+    }
+}
+
+impl<Opts, Res, Err> Middleware<Opts, Res, Err> for CassetteMiddleware<Opts, Res>
+where
+    Opts: Serialize,
+    Res: Serialize + DeserializeOwned,
+    Err: From<CassetteError>,
+{
+    fn process(
+        &self,
+        options: &Opts,
+        next: &dyn Fn(&Opts) -> Result<Res, Err>,
+    ) -> Result<Res, Err> {
+        let recorded_options = serde_json::to_value(options).map_err(CassetteError::from)?;
+        match self.mode {
+            CassetteMode::Replay => Ok(self.replay(&recorded_options)?),
+            CassetteMode::Record => {
+                let response = next(options)?;
+                self.record(recorded_options, &response)?;
+                Ok(response)
+            }
+        }
+    }
+}
+
+impl<'a, Opts, Res, Err> MiddlewareChain<'a, Opts, Res, Err>
+where
+    Opts: Serialize + 'a,
+    Res: Serialize + DeserializeOwned + 'a,
+    Err: From<CassetteError>,
+{
+    /// A chain starting with the cassette configured in the environment, if any.
+    pub(crate) fn with_cassette_from_env(name: &str) -> Self {
+        match CassetteMiddleware::from_env(name) {
+            Some(cassette) => Self::new().add(cassette),
+            None => Self::new(),
+        }
+    }
+}
+
+/// 64-bit FNV-1a, unlike `DefaultHasher` stable across Rust versions and platforms.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Serialize)]
+    struct Options {
+        version: String,
+    }
+
+    #[derive(Debug)]
+    struct TestError(String);
+
+    impl From<CassetteError> for TestError {
+        fn from(err: CassetteError) -> Self {
+            Self(err.to_string())
+        }
+    }
+
+    fn options(version: &str) -> Options {
+        Options {
+            version: version.to_string(),
+        }
+    }
+
+    fn chain(
+        dir: &Path,
+        mode: CassetteMode,
+    ) -> MiddlewareChain<'static, Options, String, TestError> {
+        MiddlewareChain::new().add(CassetteMiddleware::new(dir, mode, "fetch_release"))
+    }
+
+    #[test]
+    fn replays_recorded_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let calls = AtomicUsize::new(0);
+        let handler = |options: &Options| -> Result<String, TestError> {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("release {}", options.version))
+        };
+
+        let recorded = chain(dir.path(), CassetteMode::Record)
+            .execute(&options("2022.3.0f1"), handler)
+            .unwrap();
+        assert_eq!(recorded, "release 2022.3.0f1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let replayed = chain(dir.path(), CassetteMode::Replay)
+            .execute(&options("2022.3.0f1"), handler)
+            .unwrap();
+        assert_eq!(replayed, "release 2022.3.0f1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1, "replay must not send the request");
+    }
+
+    #[test]
+    fn replay_fails_on_unknown_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = chain(dir.path(), CassetteMode::Replay).execute(
+            &options("2022.3.0f1"),
+            |_: &Options| -> Result<String, TestError> { panic!("request sent in replay mode") },
+        );
+        let TestError(message) = result.unwrap_err();
+        assert!(
+            message.contains("no recorded fetch_release request"),
+            "{}",
+            message
+        );
+        assert!(message.contains("2022.3.0f1"), "{}", message);
+    }
+
+    #[test]
+    fn record_skips_failed_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = chain(dir.path(), CassetteMode::Record).execute(
+            &options("2022.3.0f1"),
+            |_: &Options| -> Result<String, TestError> { Err(TestError("offline".into())) },
+        );
+        assert!(result.is_err());
+        assert!(fs::read_dir(dir.path()).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn parses_modes() {
+        assert_eq!("record".parse(), Ok(CassetteMode::Record));
+        assert_eq!(" Replay ".parse(), Ok(CassetteMode::Replay));
+        assert!("rewind".parse::<CassetteMode>().is_err());
+    }
+
+    #[test]
+    fn hashes_are_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
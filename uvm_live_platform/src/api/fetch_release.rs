@@ -47,6 +47,7 @@ pub struct FetchReleaseBuilder<'a> {
     stream: Vec<UnityReleaseStream>,
     entitlements: Vec<UnityReleaseEntitlement>,
     version: Version,
+    endpoint: String,
     middleware: FetchReleaseMiddlewareChain<'a>,
 }
 
@@ -58,23 +59,30 @@ impl<'a> FetchReleaseBuilder<'a> {
             platform: Default::default(),
             stream: Default::default(),
             entitlements: Default::default(),
+            endpoint: crate::api::endpoint(),
             middleware: {
                 #[cfg(feature = "cache")]
                 {
-                    FetchReleaseMiddlewareChain::new().add(FetchReleaseCacheMiddleware::from_env())
+                    FetchReleaseMiddlewareChain::with_cassette_from_env("fetch_release").add(FetchReleaseCacheMiddleware::from_env())
                 }
                 #[cfg(not(feature = "cache"))]
                 {
-                    FetchReleaseMiddlewareChain::new()
+                    FetchReleaseMiddlewareChain::with_cassette_from_env("fetch_release")
                 }
             },
         }
     }
 
+    /// Query the GraphQL API at `endpoint` instead of the configured default.
+    pub fn with_endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
     /// Control caching for this request
     pub fn without_cache(mut self, no_cache: bool) -> Self {
         if no_cache {
-            self.middleware = FetchReleaseMiddlewareChain::new();
+            self.middleware = FetchReleaseMiddlewareChain::with_cassette_from_env("fetch_release");
         }
         self
     }
@@ -84,7 +92,7 @@ impl<'a> FetchReleaseBuilder<'a> {
     /// Useful for --refresh flags
     pub fn with_refresh(mut self, refresh: bool) -> Self {
         if refresh {
-            self.middleware = FetchReleaseMiddlewareChain::new().add(FetchReleaseCacheMiddleware::refresh_mode());
+            self.middleware = FetchReleaseMiddlewareChain::with_cassette_from_env("fetch_release").add(FetchReleaseCacheMiddleware::refresh_mode());
         }
         self
     }
@@ -105,15 +113,15 @@ impl<'a> FetchReleaseBuilder<'a> {
             stream: self.stream.clone(),
             version: self.version.to_string(),
             entitlements: self.entitlements.clone(),
+            endpoint: self.endpoint.clone(),
         };
         
         // Define the core fetch logic that will be called by middlewares
         let core_fetch = |options: &FetchReleaseOptions| -> Result<Release, FetchReleaseError> {
-            let url = "https://live-platform-api.prd.ld.unity3d.com/graphql";
             let request_body = FetchReleaseRequestBody::new(options.clone());
-            let client = reqwest::blocking::Client::new();
+            let client = crate::http_client::client()?;
             let mut res: FetchReleaseResultBody = client
-                .post(url)
+                .post(&options.endpoint)
                 .json(&request_body)
                 .send()
                 .map_err(FetchReleaseError::NetworkError)?
@@ -194,6 +202,9 @@ pub struct FetchReleaseOptions {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub entitlements: Vec<UnityReleaseEntitlement>,
     pub version: String,
+    /// Not sent to the API, part of the cache key only.
+    #[serde(skip)]
+    pub endpoint: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -266,6 +277,7 @@ impl<'a> From<FetchReleaseBuilder<'a>> for FetchReleaseOptions {
             stream: value.stream,
             version: value.version.to_string(),
             entitlements: value.entitlements,
+            endpoint: value.endpoint,
         }
     }
 }
@@ -52,6 +52,7 @@ pub struct ListVersionsBuilder<'a> {
     include_revision: bool,
     autopage: bool,
     version: Option<String>,
+    endpoint: String,
     middleware: ListVersionsMiddlewareChain<'a>,
 }
 
@@ -67,22 +68,29 @@ impl<'a> ListVersionsBuilder<'a> {
             include_revision: false,
             autopage: false,
             version: None,
+            endpoint: crate::api::endpoint(),
             middleware: {
                 #[cfg(feature = "cache")]
                 {
-                    ListVersionsMiddlewareChain::new().add(ListVersionsPageCacheMiddleware::from_env())
+                    ListVersionsMiddlewareChain::with_cassette_from_env("list_versions").add(ListVersionsPageCacheMiddleware::from_env())
                 }
                 #[cfg(not(feature = "cache"))]
                 {
-                    ListVersionsMiddlewareChain::new()
+                    ListVersionsMiddlewareChain::with_cassette_from_env("list_versions")
                 }
             },
         }
     }
 
+    /// Query the GraphQL API at `endpoint` instead of the configured default.
+    pub fn with_endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
     pub fn without_cache(mut self, no_cache: bool) -> Self {
         if no_cache {
-            self.middleware = ListVersionsMiddlewareChain::new();
+            self.middleware = ListVersionsMiddlewareChain::with_cassette_from_env("list_versions");
         }
         self
     }
@@ -92,7 +100,7 @@ impl<'a> ListVersionsBuilder<'a> {
     /// Useful for --refresh flags
     pub fn with_refresh(mut self, refresh: bool) -> Self {
         if refresh {
-            self.middleware = ListVersionsMiddlewareChain::new().add(ListVersionsPageCacheMiddleware::refresh_mode());
+            self.middleware = ListVersionsMiddlewareChain::with_cassette_from_env("list_versions").add(ListVersionsPageCacheMiddleware::refresh_mode());
         }
         self
     }
@@ -126,15 +134,15 @@ impl<'a> ListVersionsBuilder<'a> {
             stream: self.stream.clone(),
             entitlements: self.entitlements.clone(),
             version: self.version.clone(),
+            endpoint: self.endpoint.clone(),
         };
 
         // Define the core fetch logic that will be called by middlewares
         let core_fetch = move |options: &ListVersionsOptions| -> Result<ListVersionsPageResult, ListVersionsError> {
-            let url = "https://live-platform-api.prd.ld.unity3d.com/graphql";
             let request_body = ListVersionsRequestBody::new(options.clone());
-            let client = reqwest::blocking::Client::new();
+            let client = crate::http_client::client()?;
             let res: ListVersionsResultBody = client
-                .post(url)
+                .post(&options.endpoint)
                 .json(&request_body)
                 .send()
                 .map_err(ListVersionsError::NetworkError)?
@@ -161,7 +169,8 @@ impl<'a> ListVersionsBuilder<'a> {
                 options.entitlements.clone(),
                 options.version.clone(),
                 include_revision,
-            );
+            )
+            .with_endpoint(options.endpoint.clone());
             Ok(result)
         };
 
@@ -272,6 +281,9 @@ struct ListVersionsOptions {
     entitlements: Vec<UnityReleaseEntitlement>,
     #[serde(skip_serializing_if = "Option::is_none")]
     version: Option<String>,
+    /// Not sent to the API, part of the cache key only.
+    #[serde(skip)]
+    endpoint: String,
 }
 
 impl Default for ListVersionsOptions {
@@ -284,6 +296,7 @@ impl Default for ListVersionsOptions {
             stream: Default::default(),
             entitlements: Default::default(),
             version: None,
+            endpoint: crate::api::endpoint(),
         }
     }
 }
@@ -366,6 +379,8 @@ pub struct ListVersionsPageResult {
     entitlements: Vec<UnityReleaseEntitlement>,
     version: Option<String>,
     include_revision: bool,
+    #[serde(default)]
+    endpoint: Option<String>,
 }
 
 impl ListVersionsPageResult {
@@ -392,9 +407,16 @@ impl ListVersionsPageResult {
             entitlements,
             version,
             include_revision,
+            endpoint: None,
         }
     }
 
+    /// The endpoint to request the following pages from.
+    pub fn with_endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
     pub fn has_next_page(&self) -> bool {
         self.has_next_page
     }
@@ -421,6 +443,11 @@ impl ListVersionsPageResult {
         } else {
             builder
         };
+        let builder = if let Some(endpoint) = self.endpoint {
+            builder.with_endpoint(endpoint)
+        } else {
+            builder
+        };
 
         Some(builder.send())
     }
@@ -445,6 +472,7 @@ impl<'a> From<ListVersionsBuilder<'a>> for ListVersionsOptions {
             stream: value.stream,
             entitlements: value.entitlements,
             version: value.version,
+            endpoint: value.endpoint,
         }
     }
 }
\ No newline at end of file
@@ -131,6 +131,7 @@ mod tests {
             platform: vec![],
             stream: vec![],
             entitlements: vec![],
+            endpoint: String::new(),
         };
 
         // Mock final handler that records the call
@@ -1,7 +1,22 @@
 pub mod list_versions;
 pub mod fetch_release;
+pub mod release_notes;
 #[cfg(feature = "cache")]
 pub(crate) mod cache;
+pub mod cassette;
 mod middleware;
 
+/// The GraphQL endpoint of the Unity live platform API.
+pub const DEFAULT_ENDPOINT: &str = "https://live-platform-api.prd.ld.unity3d.com/graphql";
 
+/// Environment variable to query another GraphQL endpoint than [`DEFAULT_ENDPOINT`], e.g. a
+/// local stand-in during integration tests.
+pub const ENDPOINT_ENV: &str = "UVM_LIVE_PLATFORM_ENDPOINT";
+
+/// The endpoint set with [`ENDPOINT_ENV`] or [`DEFAULT_ENDPOINT`].
+pub fn endpoint() -> String {
+    std::env::var(ENDPOINT_ENV)
+        .ok()
+        .filter(|endpoint| !endpoint.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string())
+}
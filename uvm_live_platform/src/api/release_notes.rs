@@ -0,0 +1,27 @@
+use crate::error::ReleaseNotesError;
+use crate::ReleaseNotes;
+
+/// Downloads the release notes document of a release, a markdown file for current releases.
+///
+/// The document is checked against the integrity of the release notes if the API provides one.
+pub fn fetch_release_notes(notes: &ReleaseNotes) -> Result<String, ReleaseNotesError> {
+    if notes.url.is_empty() {
+        return Err(ReleaseNotesError::NotAvailable);
+    }
+
+    let client = crate::http_client::client()?;
+    let content = client
+        .get(&notes.url)
+        .send()
+        .and_then(|res| res.error_for_status())
+        .and_then(|res| res.bytes())
+        .map_err(ReleaseNotesError::NetworkError)?;
+
+    if let Some(integrity) = &notes.integrity {
+        integrity
+            .check(&content)
+            .map_err(|_| ReleaseNotesError::IntegrityMismatch(notes.url.clone()))?;
+    }
+
+    Ok(String::from_utf8_lossy(&content).into_owned())
+}
@@ -51,6 +51,12 @@ pub enum FetchReleaseError {
     #[error("Cache error: {0}")]
     CacheError(String),
 
+    #[error("Cassette error: {0}")]
+    CassetteError(String),
+
+    #[error(transparent)]
+    HttpClientError(#[from] HttpClientError),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -84,6 +90,27 @@ pub enum ListVersionsError {
     
     #[error("Cache error: {0}")]
     CacheError(String),
+
+    #[error("Cassette error: {0}")]
+    CassetteError(String),
+
+    #[error(transparent)]
+    HttpClientError(#[from] HttpClientError),
+}
+
+#[derive(Error, Debug)]
+pub enum ReleaseNotesError {
+    #[error("No release notes available")]
+    NotAvailable,
+
+    #[error("Network error: {0}")]
+    NetworkError(#[source] reqwest::Error),
+
+    #[error("Release notes at {0} do not match their checksum")]
+    IntegrityMismatch(String),
+
+    #[error(transparent)]
+    HttpClientError(#[from] HttpClientError),
 }
 
 #[cfg(feature = "cache")]
@@ -91,4 +118,56 @@ impl From<crate::api::cache::CacheError> for ListVersionsError {
     fn from(cache_error: crate::api::cache::CacheError) -> Self {
         Self::CacheError(cache_error.to_string())
     }
-}
\ No newline at end of file
+}
+
+impl From<crate::api::cassette::CassetteError> for FetchReleaseError {
+    fn from(cassette_error: crate::api::cassette::CassetteError) -> Self {
+        Self::CassetteError(cassette_error.to_string())
+    }
+}
+
+impl From<crate::api::cassette::CassetteError> for ListVersionsError {
+    fn from(cassette_error: crate::api::cassette::CassetteError) -> Self {
+        Self::CassetteError(cassette_error.to_string())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum HttpClientError {
+    #[error("Failed to read HTTP client config {}: {source}", .path.display())]
+    ConfigIo {
+        path: std::path::PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid HTTP client config {}: {source}", .path.display())]
+    InvalidConfig {
+        path: std::path::PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid value for {name}: {value}")]
+    InvalidValue { name: String, value: String },
+
+    #[error("Invalid proxy {url}: {source}")]
+    InvalidProxy {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("Failed to read CA certificates {}: {source}", .path.display())]
+    CertificateIo {
+        path: std::path::PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("No valid PEM certificates in {}", .0.display())]
+    InvalidCertificate(std::path::PathBuf),
+
+    #[error("Failed to create HTTP client: {0}")]
+    Build(#[source] reqwest::Error),
+}
@@ -2,9 +2,15 @@ pub mod error;
 mod model;
 pub use model::*;
 mod api;
+pub mod http_client;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 use crate::error::ErrorRepr;
 pub use api::fetch_release::FetchRelease;
 pub use api::list_versions::ListVersions;
+pub use api::release_notes::fetch_release_notes;
+pub use api::cassette::{CassetteMode, CASSETTE_ENV, CASSETTE_MODE_ENV};
+pub use api::{endpoint, DEFAULT_ENDPOINT, ENDPOINT_ENV};
 use unity_version::Version;
 
 pub type Result<T> = std::result::Result<T, error::LivePlatformError>;
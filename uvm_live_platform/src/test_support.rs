@@ -0,0 +1,392 @@
+//! A local stand-in for the live platform API which serves recorded responses from fixture
+//! files, so integration tests can run offline against fake releases.
+//!
+//! The fixture directory is laid out as follows:
+//!
+//! - `graphql/<Operation>/<version>.json` is the response to a query named `<Operation>`
+//!   (`FetchReleaseQuery`, `ListVersionsQuery`) with the `version` variable.
+//!   `graphql/<Operation>/default.json` answers the queries without a matching fixture.
+//!   Queries without any fixture get an empty result, like the API does for unknown versions.
+//! - `files/<path>` is served for `GET` and `HEAD` requests of `/files/<path>`, e.g. fake
+//!   installers and release notes.
+//!
+//! `{{base_url}}` in `.json`, `.md` and `.txt` fixtures is replaced with the url of the server,
+//! so fixture releases can point their downloads at it.
+//!
+//! ```no_run
+//! use uvm_live_platform::test_support::FixtureServer;
+//! use uvm_live_platform::FetchRelease;
+//!
+//! let server = FixtureServer::start("tests/fixtures/live_platform").unwrap();
+//! let release = FetchRelease::try_builder("2022.3.0f1")
+//!     .unwrap()
+//!     .with_endpoint(server.endpoint())
+//!     .without_cache(true)
+//!     .fetch()
+//!     .unwrap();
+//! ```
+use serde_json::Value;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+const BASE_URL_PLACEHOLDER: &str = "{{base_url}}";
+const EMPTY_RESULT: &str = r#"{"data":{"getUnityReleases":{"edges":[],"pageInfo":{"hasNextPage":false,"hasPreviousPage":false},"totalCount":0}}}"#;
+
+/// A request received by a [`FixtureServer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureRequest {
+    pub method: String,
+    pub path: String,
+    /// The name of the GraphQL operation, e.g. `FetchReleaseQuery`.
+    pub operation: Option<String>,
+    /// The `version` variable of the GraphQL query.
+    pub version: Option<String>,
+}
+
+/// Serves the fixtures of a directory on a random localhost port until dropped.
+#[derive(Debug)]
+pub struct FixtureServer {
+    address: SocketAddr,
+    fixtures: Arc<Fixtures>,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FixtureServer {
+    pub fn start<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let fixtures = Arc::new(Fixtures {
+            dir: dir.as_ref().to_path_buf(),
+            base_url: format!("http://{}", address),
+            requests: Mutex::new(Vec::new()),
+        });
+        let running = Arc::new(AtomicBool::new(true));
+
+        let handle = {
+            let fixtures = fixtures.clone();
+            let running = running.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if !running.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let fixtures = fixtures.clone();
+                        thread::spawn(move || {
+                            let _ = fixtures.handle(stream);
+                        });
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            address,
+            fixtures,
+            running,
+            handle: Some(handle),
+        })
+    }
+
+    /// The url of the server, e.g. `http://127.0.0.1:49152`.
+    pub fn base_url(&self) -> &str {
+        &self.fixtures.base_url
+    }
+
+    /// The GraphQL endpoint to pass to `with_endpoint` or to set as [`ENDPOINT_ENV`].
+    ///
+    /// [`ENDPOINT_ENV`]: crate::ENDPOINT_ENV
+    pub fn endpoint(&self) -> String {
+        format!("{}/graphql", self.base_url())
+    }
+
+    /// The requests received so far.
+    pub fn requests(&self) -> Vec<FixtureRequest> {
+        self.fixtures.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for FixtureServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        // wake up the accept loop
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Fixtures {
+    dir: PathBuf,
+    base_url: String,
+    requests: Mutex<Vec<FixtureRequest>>,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn not_found() -> Self {
+        Self {
+            status: "404 Not Found",
+            content_type: "text/plain",
+            body: b"not found".to_vec(),
+        }
+    }
+
+    fn write_to(&self, stream: &mut TcpStream, include_body: bool) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            self.status,
+            self.content_type,
+            self.body.len()
+        )?;
+        if include_body {
+            stream.write_all(&self.body)?;
+        }
+        stream.flush()
+    }
+}
+
+impl Fixtures {
+    fn handle(&self, mut stream: TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line)? == 0 {
+            return Ok(());
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or_default();
+        let path = target.split('?').next().unwrap_or_default().to_string();
+
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+
+        let mut request = FixtureRequest {
+            method: method.clone(),
+            path: path.clone(),
+            operation: None,
+            version: None,
+        };
+        let response = match (method.as_str(), path.strip_prefix("/files/")) {
+            ("POST", _) if path == "/graphql" => {
+                let query: Value = serde_json::from_slice(&body).unwrap_or_default();
+                request.operation = query["query"].as_str().and_then(operation_name);
+                request.version = query["variables"]["version"].as_str().map(str::to_string);
+                self.graphql(request.operation.as_deref(), request.version.as_deref())
+            }
+            ("GET" | "HEAD", Some(file)) => self.file(file),
+            _ => Response::not_found(),
+        };
+        self.requests.lock().unwrap().push(request);
+        response.write_to(&mut stream, method != "HEAD")
+    }
+
+    fn graphql(&self, operation: Option<&str>, version: Option<&str>) -> Response {
+        let fixture = operation.and_then(|operation| {
+            let dir = self.dir.join("graphql").join(operation);
+            version
+                .map(|version| dir.join(format!("{}.json", version)))
+                .filter(|path| path.is_file())
+                .or_else(|| Some(dir.join("default.json")).filter(|path| path.is_file()))
+        });
+        let body = match fixture {
+            Some(path) => match self.read(&path) {
+                Ok(body) => body,
+                Err(_) => return Response::not_found(),
+            },
+            None => EMPTY_RESULT.as_bytes().to_vec(),
+        };
+        Response {
+            status: "200 OK",
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    fn file(&self, file: &str) -> Response {
+        let relative = Path::new(file);
+        if relative
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Response::not_found();
+        }
+        let path = self.dir.join("files").join(relative);
+        match self.read(&path) {
+            Ok(body) => Response {
+                status: "200 OK",
+                content_type: content_type(&path),
+                body,
+            },
+            Err(_) => Response::not_found(),
+        }
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let content = fs::read(path)?;
+        let is_text = matches!(
+            path.extension().and_then(|extension| extension.to_str()),
+            Some("json" | "md" | "txt")
+        );
+        if !is_text {
+            return Ok(content);
+        }
+        Ok(String::from_utf8_lossy(&content)
+            .replace(BASE_URL_PLACEHOLDER, &self.base_url)
+            .into_bytes())
+    }
+}
+
+fn operation_name(query: &str) -> Option<String> {
+    let name: String = query
+        .trim_start()
+        .strip_prefix("query")?
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    Some(name).filter(|name| !name.is_empty())
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => "application/json",
+        Some("md") => "text/markdown",
+        Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fetch_release_notes, FetchRelease, ListVersions};
+
+    fn fixtures() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let graphql = dir.path().join("graphql");
+        fs::create_dir_all(graphql.join("FetchReleaseQuery")).unwrap();
+        fs::create_dir_all(graphql.join("ListVersionsQuery")).unwrap();
+        fs::create_dir_all(dir.path().join("files")).unwrap();
+        fs::write(
+            graphql.join("FetchReleaseQuery/2022.3.0f1.json"),
+            r#"{"data": {"getUnityReleases": {"totalCount": 1, "edges": [{"node": {
+                "version": "2022.3.0f1",
+                "productName": "Unity",
+                "releaseDate": "2023-01-01",
+                "releaseNotes": {"url": "{{base_url}}/files/2022.3.0f1.md", "type": "MD"},
+                "stream": "LTS",
+                "skuFamily": "CLASSIC",
+                "recommended": true,
+                "unityHubDeepLink": "unityhub://2022.3.0f1/abc123",
+                "shortRevision": "abc123",
+                "downloads": []
+            }}]}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            graphql.join("ListVersionsQuery/default.json"),
+            r#"{"data": {"getUnityReleases": {"totalCount": 2, "edges": [
+                {"node": {"version": "2022.3.1f1", "shortRevision": "def456"}},
+                {"node": {"version": "2022.3.0f1", "shortRevision": "abc123"}}
+            ], "pageInfo": {"hasNextPage": false, "hasPreviousPage": false}}}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("files/2022.3.0f1.md"), "## Fixes\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn serves_recorded_queries() {
+        let dir = fixtures();
+        let server = FixtureServer::start(dir.path()).unwrap();
+
+        let release = FetchRelease::try_builder("2022.3.0f1")
+            .unwrap()
+            .with_endpoint(server.endpoint())
+            .without_cache(true)
+            .fetch()
+            .unwrap();
+        assert_eq!(release.short_revision, "abc123");
+        assert_eq!(
+            release.release_notes.url,
+            format!("{}/files/2022.3.0f1.md", server.base_url())
+        );
+        assert_eq!(
+            fetch_release_notes(&release.release_notes).unwrap(),
+            "## Fixes\n"
+        );
+
+        let versions: Vec<String> = ListVersions::builder()
+            .with_endpoint(server.endpoint())
+            .without_cache(true)
+            .include_revision(true)
+            .list()
+            .unwrap()
+            .collect();
+        assert_eq!(versions, vec!["2022.3.1f1 (def456)", "2022.3.0f1 (abc123)"]);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].operation.as_deref(), Some("FetchReleaseQuery"));
+        assert_eq!(requests[0].version.as_deref(), Some("2022.3.0f1"));
+        assert_eq!(requests[1].path, "/files/2022.3.0f1.md");
+        assert_eq!(requests[2].operation.as_deref(), Some("ListVersionsQuery"));
+    }
+
+    #[test]
+    fn answers_unknown_versions_with_an_empty_result() {
+        let dir = fixtures();
+        let server = FixtureServer::start(dir.path()).unwrap();
+
+        let result = FetchRelease::try_builder("2019.1.0f1")
+            .unwrap()
+            .with_endpoint(server.endpoint())
+            .without_cache(true)
+            .fetch();
+        assert!(matches!(
+            result,
+            Err(crate::error::FetchReleaseError::NotFound(..))
+        ));
+    }
+
+    #[test]
+    fn rejects_paths_outside_of_the_fixtures() {
+        let dir = fixtures();
+        let server = FixtureServer::start(dir.path()).unwrap();
+        // reqwest normalizes `..`, send the request by hand
+        let mut stream =
+            TcpStream::connect(server.base_url().trim_start_matches("http://")).unwrap();
+        stream
+            .write_all(b"GET /files/../graphql/ListVersionsQuery/default.json HTTP/1.1\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 404"), "{}", response);
+    }
+}